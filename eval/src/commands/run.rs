@@ -3,18 +3,20 @@ use crate::commands::internal::{CommandInfo, NumberType, StdKind};
 use crate::custom_function::CustomFunction;
 use bigdecimal::BigDecimal;
 use crossterm::event::{self, Event, KeyCode};
-use math_engine::context::validate::{check_token_name, TokenKind};
 use math_engine::context::{Config, Context, DefaultContext};
 use math_engine::error::{Error, ErrorKind};
 use math_engine::evaluator::Evaluator;
+use math_engine::function::MaybeSync;
 use math_engine::Result;
+use math_engine::Shared;
 use std::convert::TryFrom;
 use std::fmt::{Debug, Display};
 use std::iter::Iterator;
-use std::rc::Rc;
 use std::str::FromStr;
 use math_engine::complex::Complex;
 use crate::commands::internal;
+use crate::context_file;
+use math_engine::context::state::ContextState;
 use crossterm::style::Color;
 
 pub struct RunCommand;
@@ -26,11 +28,43 @@ impl RunCommand {
     const RESULT_VAR_NAME: &'static str = "result";
     const BACKSPACE: &'static str = "\x08 \x08";
 
-    fn eval_expr<N>(buffer: &mut String, evaluator: &mut Rc<Evaluator<'_, N>>)
+    const SAVE_COMMAND: &'static str = ":save ";
+    const UNDEF_COMMAND: &'static str = ":undef ";
+    const CLEAR_COMMAND: &'static str = ":clear";
+    const LIST_COMMAND: &'static str = ":list";
+
+    fn eval_expr<N>(buffer: &mut String, evaluator: &mut Shared<Evaluator<'_, N>>)
     where
-        N: FromStr + Debug + Display + Clone,
+        N: FromStr + Debug + Display + Clone + MaybeSync,
     {
-        if buffer.contains("=") {
+        if buffer.starts_with(Self::SAVE_COMMAND) {
+            let path = buffer[Self::SAVE_COMMAND.len()..].trim();
+            let state = evaluator.context().save_state();
+            match context_file::save(&state, path) {
+                Ok(()) => internal::print_color(format!(" Saved to `{}`", path), Self::RESULT_COLOR, StdKind::Output),
+                Err(e) => internal::print_color(format!(" [Error] {}", e), Self::ERROR_COLOR, StdKind::Error),
+            }
+        } else if buffer.starts_with(Self::UNDEF_COMMAND) {
+            let name = buffer[Self::UNDEF_COMMAND.len()..].trim();
+            match Self::undefine(name, evaluator) {
+                Ok(()) => internal::print_color(format!(" Removed `{}`", name), Self::RESULT_COLOR, StdKind::Output),
+                Err(e) => internal::print_color(format!(" [Error] {}", e), Self::ERROR_COLOR, StdKind::Error),
+            }
+        } else if buffer.trim() == Self::CLEAR_COMMAND {
+            Shared::make_mut(evaluator).mut_context().clear_variables();
+            internal::print_color(" Variables removed", Self::RESULT_COLOR, StdKind::Output);
+        } else if buffer.trim() == Self::LIST_COMMAND {
+            let context = evaluator.context();
+            let lines = [
+                ("Variables", Self::sorted_names(context.variable_names())),
+                ("Constants", Self::sorted_names(context.constant_names())),
+                ("Functions", Self::sorted_names(context.function_names())),
+            ];
+
+            for (kind, names) in lines.iter() {
+                internal::print_color(format!("\n {}: {}", kind, names), Self::RESULT_COLOR, StdKind::Output);
+            }
+        } else if buffer.contains("=") {
             match Self::eval_assign(buffer, evaluator) {
                 Ok(()) => {}
                 Err(e) => {
@@ -41,7 +75,7 @@ impl RunCommand {
             match evaluator.eval(buffer) {
                 Ok(n) => {
                     internal::print_color(format!(" = {}", n), Self::RESULT_COLOR, StdKind::Output);
-                    Rc::make_mut(evaluator)
+                    Shared::make_mut(evaluator)
                         .mut_context()
                         .set_variable(Self::RESULT_VAR_NAME, n);
                 }
@@ -53,8 +87,48 @@ impl RunCommand {
         buffer.clear();
     }
 
-    fn eval_assign<N>(expression: &str, evaluator: &mut Rc<Evaluator<'_, N>>) -> Result<()>
-    where N: FromStr + Debug + Display + Clone, {
+    fn undefine<N>(name: &str, evaluator: &mut Shared<Evaluator<'_, N>>) -> Result<()>
+    where N: FromStr + Debug + Display + Clone + MaybeSync, {
+        let context = Shared::make_mut(evaluator).mut_context();
+
+        // Only the variables and functions can be undefined, the constants and operators are builtin
+        if context.remove_variable(name).is_none() && context.remove_function(name).is_none() {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{}` is not a variable or function", name),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // Loads the state of the context and defines the user functions
+    fn load<'a, N>(builtins: DefaultContext<'a, N>, state: Option<&ContextState>) -> Result<Shared<Evaluator<'a, N>>>
+    where
+        N: FromStr + Display + Debug + Clone + MaybeSync,
+    {
+        let state = match state {
+            Some(state) => state,
+            None => return Ok(Shared::new(Evaluator::with_context(builtins))),
+        };
+
+        let context = DefaultContext::load_state(state, &builtins)?;
+        let mut evaluator = Shared::new(Evaluator::with_context(context));
+        for source in &state.user_functions {
+            RunCommand::eval_assign(source, &mut evaluator)?;
+        }
+
+        Ok(evaluator)
+    }
+
+    fn sorted_names<'c>(names: impl Iterator<Item = &'c str>) -> String {
+        let mut names = names.collect::<Vec<&str>>();
+        names.sort_by_key(|s| s.to_lowercase());
+        names.join(", ")
+    }
+
+    fn eval_assign<N>(expression: &str, evaluator: &mut Shared<Evaluator<'_, N>>) -> Result<()>
+    where N: FromStr + Debug + Display + Clone + MaybeSync, {
         // Could be a variable assignment or a function assignment
         // * Variable Assignment: `variable_name = expression`.
         //      Eg.: `x = 10`, `y = x ^ 2`
@@ -78,21 +152,9 @@ impl RunCommand {
             // Takes the entire expression
             match CustomFunction::from_str(evaluator.clone(), expression) {
                 Ok(f) => {
-                    let ev = Rc::make_mut(evaluator);
-                    let context = ev.mut_context();
-
-                    // Checks the function do not exists
-                    // `DefaultContext` panics if tries to add a function that exists.
-                    if context.is_function(f.name()) {
-                        return Err(Error::new(
-                            ErrorKind::Other,
-                            format!("Function `{}` already exists in the context", f.name()),
-                        ));
-                    } else {
-                        // Checks the function name is valid
-                        check_token_name(TokenKind::Function, f.name())?;
-                        context.add_function(f)
-                    }
+                    Shared::make_mut(evaluator)
+                        .mut_context()
+                        .try_add_function(f)?;
                 }
                 // Function parse failed
                 Err(e) => {
@@ -100,14 +162,11 @@ impl RunCommand {
                 }
             }
         } else {
-            // Checks the variable name is valid
-            check_token_name(TokenKind::Variable, var)?;
-
             let value = evaluator.eval(expr)?;
 
-            Rc::make_mut(evaluator)
+            Shared::make_mut(evaluator)
                 .mut_context()
-                .set_variable(var, value);
+                .try_set_variable(var, value)?;
         }
 
         Ok(())
@@ -129,18 +188,20 @@ Executes the evaluator in a loop
 
 USAGE:
     eval --run | --r
-    eval [--OPTION] --run | --r
+    eval [--OPTION] --run | --r [--load FILE]
 
 OPTIONS:
     --decimal, --d          Evaluates using a 128 bits decimal number. Used by default
     --bigdecimal, --b       Evaluates using an arbitrary decimal number
     --complex, --c          Evaluates using a complex number
+    --load FILE             Loads the variables, constants and functions from a `.json` or `.toml` file
 
 EXAMPLES:
     eval --run
     eval --r
     eval --bigdecimal --run
     eval --c --r
+    eval --run --load context.toml
 
 REMARKS:
     There are some unique behaviours when the evaluator runs using `--r | --run`:
@@ -148,12 +209,17 @@ REMARKS:
     - You can assign variables: `variable_name = expression`.
         Eg.: x = 10, y = Sin(45)
     - You can create functions: `function_name(arguments) = expression`.
-        Eg.: double(x) = x * 2, getThree() = 3"
+        Eg.: double(x) = x * 2, getThree() = 3
+    - You can save the variables, constants and functions to a `.json` or `.toml` file: `:save FILE`.
+        Eg.: :save context.toml
+    - You can remove a variable or function: `:undef NAME`, or all the variables: `:clear`.
+        Eg.: :undef x, :undef double
+    - You can list the variables, constants and functions: `:list`"
     }
 
     fn execute(&self, args: CommandArgs<'_, String>) -> Result<()> {
         // Actual loop
-        fn run<N: FromStr + Display + Debug + Clone>(mut evaluator: Rc<Evaluator<'_, N>>) {
+        fn run<N: FromStr + Display + Debug + Clone + MaybeSync>(mut evaluator: Shared<Evaluator<'_, N>>) {
             let mut buffer = String::new();
             internal::print_color(">> ", RunCommand::NEWLINE_COLOR, StdKind::Output);
 
@@ -182,41 +248,48 @@ REMARKS:
             }
         }
 
-        // eval [--run | --r] [--OPTION]
-        if args.len() > 1 {
-            return Err(Error::new(
-                ErrorKind::InvalidExpression,
-                format!(
-                    "Invalid arguments: expected: `eval [--option] [{}]`",
-                    self.name()
-                ),
-            ));
-        }
+        // eval [--OPTION] [--run | --r] [--load FILE]
+        let mut number_type = None;
+        let mut state = None;
+        let mut iter = args.iter();
 
-        let number_type = if args.is_empty() {
-            NumberType::default()
-        } else {
-            NumberType::try_from(args[0].as_str()).unwrap_or_default()
-        };
+        while let Some(arg) = iter.next() {
+            if arg == "--load" && state.is_none() {
+                match iter.next() {
+                    Some(path) => state = Some(context_file::load(path)?),
+                    None => return Err(Error::new(ErrorKind::InvalidInput, "Expected a file after `--load`")),
+                }
+            } else if let (Ok(n), None) = (NumberType::try_from(arg.as_str()), number_type) {
+                number_type = Some(n);
+            } else {
+                return Err(Error::new(
+                    ErrorKind::InvalidExpression,
+                    format!(
+                        "Invalid arguments: expected: `eval [--option] [{}] [--load FILE]`",
+                        self.name()
+                    ),
+                ));
+            }
+        }
 
-        match number_type {
+        match number_type.unwrap_or_default() {
             NumberType::Decimal => {
                 let config = Config::new().with_implicit_mul(true);
                 let context = DefaultContext::new_decimal_with_config(config);
-                run(Rc::new(Evaluator::with_context(context)));
+                run(RunCommand::load(context, state.as_ref())?);
             }
             NumberType::BigDecimal => {
                 let config = Config::new().with_implicit_mul(true);
                 let context: DefaultContext<BigDecimal> =
                     DefaultContext::new_unchecked_with_config(config);
-                run(Rc::new(Evaluator::with_context(context)));
+                run(RunCommand::load(context, state.as_ref())?);
             }
             NumberType::Complex => {
                 let config = Config::new()
                     .with_implicit_mul(true)
                     .with_complex_number(true);
                 let context = DefaultContext::<Complex<f64>>::new_complex_with_config(config);
-                run(Rc::new(Evaluator::with_context(context)));
+                run(RunCommand::load(context, state.as_ref())?);
             }
         }
 
@@ -225,4 +298,22 @@ REMARKS:
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_test() {
+        let mut evaluator = Shared::new(Evaluator::with_context(DefaultContext::new_decimal()));
+        RunCommand::eval_assign("sq(x) = x * x", &mut evaluator).unwrap();
+        RunCommand::eval_assign("area(r) = 3 * sq(r)", &mut evaluator).unwrap();
+        assert_eq!(Ok(12.into()), evaluator.eval("area(2)"));
+
+        // `area` calls `sq` so must be defined after it
+        let state = evaluator.context().save_state();
+        let loaded = RunCommand::load(DefaultContext::new_decimal(), Some(&state)).unwrap();
+        assert_eq!(Ok(12.into()), loaded.eval("area(2)"));
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs;
+use std::path::Path;
+use math_engine::context::state::ContextState;
+use math_engine::error::{Error, ErrorKind};
+use math_engine::Result;
+
+/// The formats used for save the state of a context.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum FileFormat {
+    Json,
+    Toml,
+}
+
+impl FileFormat {
+    /// Gets the format of the file from its extension, `.json` or `.toml`.
+    pub fn from_path(path: &Path) -> Result<FileFormat> {
+        match path.extension().and_then(|s| s.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("json") => Ok(FileFormat::Json),
+            Some(ext) if ext.eq_ignore_ascii_case("toml") => Ok(FileFormat::Toml),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Invalid file `{}`, expected a `.json` or `.toml` file", path.display()),
+            )),
+        }
+    }
+}
+
+/// Converts the state of a context to text in the given format.
+pub fn to_string(state: &ContextState, format: FileFormat) -> Result<String> {
+    match format {
+        FileFormat::Json => serde_json::to_string_pretty(state).map_err(|e| Error::new(ErrorKind::Other, e)),
+        FileFormat::Toml => toml::to_string(state).map_err(|e| Error::new(ErrorKind::Other, e)),
+    }
+}
+
+/// Gets the state of a context from text in the given format.
+pub fn from_str(s: &str, format: FileFormat) -> Result<ContextState> {
+    match format {
+        FileFormat::Json => serde_json::from_str(s).map_err(|e| Error::new(ErrorKind::InvalidInput, e)),
+        FileFormat::Toml => toml::from_str(s).map_err(|e| Error::new(ErrorKind::InvalidInput, e)),
+    }
+}
+
+/// Saves the state of a context to the given `.json` or `.toml` file.
+pub fn save<P: AsRef<Path>>(state: &ContextState, path: P) -> Result<()> {
+    let path = path.as_ref();
+    let text = to_string(state, FileFormat::from_path(path)?)?;
+    fs::write(path, text).map_err(|e| Error::new(ErrorKind::Other, e))
+}
+
+/// Loads the state of a context from the given `.json` or `.toml` file.
+pub fn load<P: AsRef<Path>>(path: P) -> Result<ContextState> {
+    let path = path.as_ref();
+    let format = FileFormat::from_path(path)?;
+    let text = fs::read_to_string(path).map_err(|e| Error::new(ErrorKind::Other, e))?;
+    from_str(&text, format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use math_engine::context::{Context, DefaultContext};
+
+    fn state() -> ContextState {
+        let mut context: DefaultContext<f64> = DefaultContext::new_unchecked();
+        context.set_variable("x", 2.5);
+        context.add_constant("TWO", 2.0);
+
+        let mut state = context.save_state();
+        state.user_functions.push("double(x) = x * 2".to_string());
+        state
+    }
+
+    #[test]
+    fn file_format_test() {
+        assert_eq!(FileFormat::Json, FileFormat::from_path(Path::new("ctx.json")).unwrap());
+        assert_eq!(FileFormat::Toml, FileFormat::from_path(Path::new("dir/ctx.TOML")).unwrap());
+        assert!(FileFormat::from_path(Path::new("ctx.txt")).is_err());
+        assert!(FileFormat::from_path(Path::new("ctx")).is_err());
+    }
+
+    #[test]
+    fn round_trip_test() {
+        let state = state();
+        for format in &[FileFormat::Json, FileFormat::Toml] {
+            let text = to_string(&state, *format).unwrap();
+            assert_eq!(state, from_str(&text, *format).unwrap(), "{:?}:\n{}", format, text);
+        }
+    }
+}
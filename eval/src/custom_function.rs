@@ -2,18 +2,21 @@ use std::error;
 use std::fmt::{Debug, Display, Formatter};
 use std::marker::PhantomData;
 use std::panic::RefUnwindSafe;
-use std::rc::Rc;
 use std::str::FromStr;
 use math_engine::context::Context;
+use math_engine::context::scoped::ScopedContext;
+use math_engine::context::validate::{check_token_name, TokenKind};
 use math_engine::error::{Error, ErrorKind};
-use math_engine::evaluator::Evaluator;
-use math_engine::function::Function;
+use math_engine::evaluator::{rpn_eval, Evaluator};
+use math_engine::function::{Function, MaybeSync};
+use math_engine::tokenizer::{Tokenize, Tokenizer};
+use math_engine::Shared;
 
 pub struct CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr {
     function_name: String,
     params: Vec<String>,
     body: String,
-    evaluator: Rc<Evaluator<'a, T>>,
+    evaluator: Shared<Evaluator<'a, T>>,
     _marker: PhantomData<T>,
 }
 
@@ -25,7 +28,7 @@ impl<'a, T> CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr {
         function_name: String,
         params: Vec<String>,
         body: String,
-        evaluator: Rc<Evaluator<'a, T>>,
+        evaluator: Shared<Evaluator<'a, T>>,
     ) -> Self {
         CustomFunction {
             function_name,
@@ -36,10 +39,9 @@ impl<'a, T> CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr {
         }
     }
 
-    pub fn from_str(evaluator: Rc<Evaluator<'a, T>>, s: &str) -> Result<Self, ParseFunctionError> {
+    pub fn from_str(evaluator: Shared<Evaluator<'a, T>>, s: &str) -> Result<Self, ParseFunctionError> {
         fn check_name(name: &str) -> Result<(), ParseFunctionError> {
-            if name.is_empty()
-                || name.chars().any(char::is_whitespace)
+            if check_token_name(TokenKind::Variable, name).is_err()
                 || !name.chars().all(char::is_alphanumeric)
             {
                 return Err(ParseFunctionError::from(FunctionErrorKind::InvalidName(
@@ -139,7 +141,7 @@ impl<'a, T> CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr {
     }
 }
 
-impl<'a, T> Function<T> for CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr {
+impl<'a, T> Function<T> for CustomFunction<'a, T> where T: Display + Debug + Clone + FromStr + MaybeSync {
     #[inline]
     fn name(&self) -> &str {
         self.name()
@@ -150,12 +152,18 @@ impl<'a, T> Function<T> for CustomFunction<'a, T> where T: Display + Debug + Clo
             return Err(Error::from(ErrorKind::InvalidArgumentCount));
         }
 
-        let mut expr = self.body.clone();
-        for i in 0..self.params.len() {
-            expr = expr.replace(&self.params[i], &args[i].to_string())
+        // Binds the arguments to the params without modify the context of the evaluator
+        let mut scope = ScopedContext::new(self.evaluator.context());
+        for (param, arg) in self.params.iter().zip(args) {
+            scope.try_set_variable(param, arg.clone())?;
         }
 
-        self.evaluator.eval(&expr)
+        let tokens = Tokenizer::with_context(&scope).tokenize(&self.body)?;
+        rpn_eval(&tokens, &scope)
+    }
+
+    fn source(&self) -> Option<String> {
+        Some(format!("{}({}) = {}", self.function_name, self.params.join(", "), self.body))
     }
 }
 
@@ -211,7 +219,7 @@ mod tests {
 
     fn try_from(expr: &str) -> Result<CustomFunction<f64>, ParseFunctionError> {
         let evaluator = Evaluator::new();
-        CustomFunction::from_str(Rc::new(evaluator), expr)
+        CustomFunction::from_str(Shared::new(evaluator), expr)
     }
 
     #[test]
@@ -234,7 +242,7 @@ mod tests {
     fn from_str_and_eval_test() {
         let evaluator: Evaluator<f64> = Evaluator::new();
         let func = CustomFunction::from_str(
-            Rc::new(evaluator),
+            Shared::new(evaluator),
             "Add2(x) = x + 2"
         ).unwrap();
 
@@ -251,6 +259,8 @@ mod tests {
         assert!(try_from("Sum(x, x) = x + x").is_err());
         assert!(try_from("Sum(x1, x2) = x1").is_err());
         assert!(try_from("Sum(x1, x2, x3,) = x1 + x3 + x3").is_err());
+        assert!(try_from("Add(1x) = 1x + 1").is_err());
+        assert!(try_from("Add(1) = 1 + 1").is_err());
 
         assert!(try_from("GetOne() = 1").is_ok());
         assert!(try_from("Sum(x1, x2) = x2 + x1").is_ok());
@@ -264,9 +274,35 @@ mod tests {
             "Plus".to_string(),
             vec!["x".to_string(), "y".to_string()],
             "x + y".to_string(),
-            Rc::new(evaluator),
+            Shared::new(evaluator),
         );
 
         assert_eq!(func.call(&[2_f64, 4_f64]), Ok(6_f64));
+
+        let func = CustomFunction::with_evaluator(
+            "Invalid".to_string(),
+            vec!["1x".to_string()],
+            "1".to_string(),
+            Shared::new(Evaluator::new()),
+        );
+
+        assert_eq!(func.call(&[2_f64]).unwrap_err().kind(), ErrorKind::InvalidName);
+    }
+
+    #[test]
+    fn call_binds_params_test() {
+        let square = try_from("Square(x) = x ^ 2").unwrap();
+        assert_eq!(square.call(&[-3_f64]), Ok(9_f64));
+
+        let sum = try_from("Sum(x, x1) = x * 10 + x1").unwrap();
+        assert_eq!(sum.call(&[1_f64, 2_f64]), Ok(12_f64));
+        assert!(sum.call(&[1_f64]).is_err());
+    }
+
+    #[test]
+    fn source_test() {
+        let func = try_from("Sum(x1, x2) = x1 + x2").unwrap();
+        assert_eq!(func.source(), Some("Sum(x1, x2) = x1 + x2".to_string()));
+        assert_eq!(try_from(&func.source().unwrap()).unwrap().source(), func.source());
     }
 }
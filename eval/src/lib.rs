@@ -1,3 +1,4 @@
 pub mod cli;
 pub mod commands;
+pub mod context_file;
 pub mod custom_function;
\ No newline at end of file
@@ -0,0 +1,51 @@
+#[macro_use]
+extern crate bencher;
+
+use bencher::{Bencher, black_box};
+use math_engine::context::{Context, DefaultContext};
+use math_engine::evaluator::batch::BatchExpr;
+use math_engine::evaluator::bytecode::{Program, Vm};
+use math_engine::evaluator::closure;
+use math_engine::evaluator::Evaluator;
+
+const EXPRESSION: &str = "Sqrt(x ^ 2 + y ^ 2) * Sin(x) + Max(x, y, 1) / 2";
+const X: f64 = 3.0;
+const Y: f64 = 4.0;
+
+fn evaluator_bench(b: &mut Bencher) {
+    let mut context = DefaultContext::new_unchecked();
+    context.set_variable("x", X);
+    context.set_variable("y", Y);
+    let evaluator = Evaluator::with_context(context);
+
+    b.iter(|| black_box(evaluator.eval(black_box(EXPRESSION)).unwrap()))
+}
+
+fn compiled_rpn_bench(b: &mut Bencher) {
+    let context = DefaultContext::new_unchecked();
+    let expr = BatchExpr::compile(EXPRESSION, &["x", "y"], &context).unwrap();
+    let mut output = [0.0];
+
+    b.iter(|| {
+        expr.eval_into(&[("x", &[black_box(X)]), ("y", &[black_box(Y)])], &mut output).unwrap();
+        black_box(output[0])
+    })
+}
+
+fn closure_bench(b: &mut Bencher) {
+    let context = DefaultContext::new_unchecked();
+    let f = closure::compile(EXPRESSION, &["x", "y"], &context).unwrap();
+
+    b.iter(|| black_box(f(&[black_box(X), black_box(Y)]).unwrap()))
+}
+
+fn bytecode_bench(b: &mut Bencher) {
+    let context = DefaultContext::new_unchecked();
+    let program = Program::compile(EXPRESSION, &["x", "y"], &context).unwrap();
+    let mut vm = Vm::with_capacity(program.stack_size());
+
+    b.iter(|| black_box(vm.run(&program, &[black_box(X), black_box(Y)]).unwrap()))
+}
+
+benchmark_group!(benches, evaluator_bench, compiled_rpn_bench, closure_bench, bytecode_bench);
+benchmark_main!(benches);
@@ -1,6 +1,5 @@
 use bigdecimal::BigDecimal;
 use math_engine::num::unchecked::UncheckedNum;
-use std::panic::{RefUnwindSafe, UnwindSafe};
 
 fn main(){
     compute::<BigDecimal>("100!");
@@ -9,12 +8,9 @@ fn main(){
     compute::<BigDecimal>("cos(180)");
 }
 
-fn compute<T>(expr: &str) where T: 'static
-    + UncheckedNum
-    + RefUnwindSafe
-    + UnwindSafe {
-    match math_engine::eval::<T>(expr){
+fn compute<T>(expr: &str) where T: 'static + UncheckedNum {
+    match math_engine::eval_unchecked::<T>(expr){
         Ok(n) => println!("{} = {}", expr, n),
         Err(e) => println!("{} = {:?}", expr, e),
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,305 @@
+use std::fmt::Debug;
+
+use crate::context::Context;
+use crate::error::{Error, ErrorKind};
+use crate::evaluator::infix_to_rpn;
+use crate::token::Token;
+use crate::Result;
+
+/// Represents an expression as a tree.
+///
+/// # Example
+/// ```
+/// use math_engine::ast::Expr;
+/// use math_engine::context::DefaultContext;
+/// use math_engine::token::Token::*;
+///
+/// let context = DefaultContext::new_checked();
+/// let tokens = [Number(2), BinaryOperator("+".to_string()), Number(3)];
+/// let expr = Expr::from_infix(&tokens, &context).unwrap();
+///
+/// assert_eq!(
+///     expr,
+///     Expr::BinaryOperator("+".to_string(), Box::new(Expr::Number(2)), Box::new(Expr::Number(3)))
+/// );
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Expr<N> {
+    /// A number
+    Number(N),
+    /// A variable
+    Variable(String),
+    /// A constant
+    Constant(String),
+    /// A function call and its arguments
+    Function(String, Vec<Expr<N>>),
+    /// A binary operator and its left and right operands
+    BinaryOperator(String, Box<Expr<N>>, Box<Expr<N>>),
+    /// An unary operator and its operand
+    UnaryOperator(String, Box<Expr<N>>),
+}
+
+impl<N> Expr<N> {
+    /// Constructs an `Expr` from the given tokens in `Reverse Polish Notation`.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::ast::Expr;
+    /// use math_engine::token::Token::*;
+    ///
+    /// // 10 2 -
+    /// let rpn = [Number(10), Number(2), BinaryOperator("-".to_string())];
+    /// let expr = Expr::from_rpn(&rpn).unwrap();
+    ///
+    /// assert_eq!(expr.to_rpn(), rpn);
+    /// ```
+    pub fn from_rpn(rpn: &[Token<N>]) -> Result<Expr<N>>
+    where
+        N: Clone + Debug,
+    {
+        // Stores the resulting sub-expressions
+        let mut values: Vec<Expr<N>> = Vec::new();
+        // Stores the argument count of the current function, if any.
+        let mut arg_count: Option<usize> = None;
+
+        for token in rpn {
+            match token {
+                Token::Number(n) => values.push(Expr::Number(n.clone())),
+                Token::Variable(name) => values.push(Expr::Variable(name.clone())),
+                Token::Constant(name) => values.push(Expr::Constant(name.clone())),
+                Token::ArgCount(n) => arg_count = Some(*n),
+                Token::UnaryOperator(name) => {
+                    let operand = values.pop().ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidExpression,
+                            format!("Missing operand for unary operator `{}`", name),
+                        )
+                    })?;
+
+                    values.push(Expr::UnaryOperator(name.clone(), Box::new(operand)));
+                }
+                Token::BinaryOperator(name) => match (values.pop(), values.pop()) {
+                    (Some(right), Some(left)) => values.push(Expr::BinaryOperator(
+                        name.clone(),
+                        Box::new(left),
+                        Box::new(right),
+                    )),
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidExpression,
+                            format!("Missing operands for binary operator `{}`", name),
+                        ))
+                    }
+                },
+                Token::Function(name) => {
+                    let n = arg_count.take().ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Unknown number of arguments of function `{}`", name),
+                        )
+                    })?;
+
+                    if n > values.len() {
+                        return Err(Error::new(
+                            ErrorKind::InvalidArgumentCount,
+                            format!("expected {} arguments but {} was get", n, values.len()),
+                        ));
+                    }
+
+                    let args = values.split_off(values.len() - n);
+                    values.push(Expr::Function(name.clone(), args));
+                }
+                _ => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unexpected token in RPN expression: `{:?}`", token),
+                    ))
+                }
+            }
+        }
+
+        // If there is a single value left, that is the expression
+        if values.len() == 1 {
+            Ok(values.pop().unwrap())
+        } else {
+            Err(Error::from(ErrorKind::InvalidExpression))
+        }
+    }
+
+    /// Constructs an `Expr` from the given tokens in `infix` notation, the
+    /// `Context` provides the precedence and associativity of the operators.
+    #[inline]
+    pub fn from_infix<'a, C>(tokens: &[Token<N>], context: &C) -> Result<Expr<N>>
+    where
+        N: Clone + Debug,
+        C: Context<'a, N>,
+    {
+        let rpn = infix_to_rpn(tokens, context)?;
+        Self::from_rpn(&rpn)
+    }
+
+    /// Converts this expression to tokens in `Reverse Polish Notation`.
+    pub fn to_rpn(&self) -> Vec<Token<N>>
+    where
+        N: Clone,
+    {
+        fn push_rpn<N: Clone>(expr: &Expr<N>, output: &mut Vec<Token<N>>) {
+            match expr {
+                Expr::Number(n) => output.push(Token::Number(n.clone())),
+                Expr::Variable(name) => output.push(Token::Variable(name.clone())),
+                Expr::Constant(name) => output.push(Token::Constant(name.clone())),
+                Expr::Function(name, args) => {
+                    for arg in args {
+                        push_rpn(arg, output);
+                    }
+
+                    output.push(Token::ArgCount(args.len()));
+                    output.push(Token::Function(name.clone()));
+                }
+                Expr::BinaryOperator(name, left, right) => {
+                    push_rpn(left, output);
+                    push_rpn(right, output);
+                    output.push(Token::BinaryOperator(name.clone()));
+                }
+                Expr::UnaryOperator(name, operand) => {
+                    push_rpn(operand, output);
+                    output.push(Token::UnaryOperator(name.clone()));
+                }
+            }
+        }
+
+        let mut output = Vec::new();
+        push_rpn(self, &mut output);
+        output
+    }
+
+    /// Checks if the expression is a number, variable or constant.
+    #[inline]
+    pub fn is_atom(&self) -> bool {
+        matches!(self, Expr::Number(_) | Expr::Variable(_) | Expr::Constant(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::DefaultContext;
+    use crate::tokenizer::{Tokenize, Tokenizer};
+
+    fn parse(expression: &str) -> Expr<i64> {
+        let context = DefaultContext::new_checked();
+        let tokens = Tokenizer::with_context(&context)
+            .tokenize(expression)
+            .unwrap();
+
+        Expr::from_infix(&tokens, &context).unwrap()
+    }
+
+    #[test]
+    fn from_infix_test() {
+        assert_eq!(
+            parse("2 + 3 * 5"),
+            Expr::BinaryOperator(
+                "+".to_string(),
+                Box::new(Expr::Number(2)),
+                Box::new(Expr::BinaryOperator(
+                    "*".to_string(),
+                    Box::new(Expr::Number(3)),
+                    Box::new(Expr::Number(5))
+                ))
+            )
+        );
+
+        assert_eq!(
+            parse("Max(1, -2, PI)"),
+            Expr::Function(
+                "Max".to_string(),
+                vec![
+                    Expr::Number(1),
+                    Expr::UnaryOperator("-".to_string(), Box::new(Expr::Number(2))),
+                    Expr::Constant("PI".to_string())
+                ]
+            )
+        );
+
+        assert_eq!(
+            parse("Random()"),
+            Expr::Function("Random".to_string(), vec![])
+        );
+    }
+
+    #[test]
+    fn rpn_round_trip_test() {
+        let context = DefaultContext::new_checked();
+        let tokens = Tokenizer::with_context(&context)
+            .tokenize("(5 + -3) ^ Max(1, 2 * 5, 30 / 2)! mod 4")
+            .unwrap();
+
+        let rpn: Vec<Token<i64>> = infix_to_rpn(&tokens, &context).unwrap();
+        let expr = Expr::from_rpn(&rpn).unwrap();
+        assert_eq!(expr.to_rpn(), rpn);
+    }
+
+    #[test]
+    fn from_rpn_error_test() {
+        let plus = || Token::BinaryOperator("+".to_string());
+
+        assert!(Expr::<i64>::from_rpn(&[]).is_err());
+        assert!(Expr::from_rpn(&[Token::Number(2), plus()]).is_err());
+        assert!(Expr::from_rpn(&[Token::Number(2), Token::Number(3)]).is_err());
+        assert!(Expr::from_rpn(&[Token::Number(2), Token::Function("max".to_string())]).is_err());
+        assert!(Expr::from_rpn(&[Token::Number(2), Token::Number(3), Token::Comma, plus()]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip_test() {
+        let context = DefaultContext::new_checked();
+        let tokens: Vec<Token<i64>> = Tokenizer::with_context(&context)
+            .tokenize("Max(2, -3) * (PI + 5)!")
+            .unwrap();
+
+        let json = serde_json::to_string(&tokens).unwrap();
+        let deserialized: Vec<Token<i64>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tokens, deserialized);
+
+        let expr = Expr::from_infix(&tokens, &context).unwrap();
+        let json = serde_json::to_string(&expr).unwrap();
+        let deserialized: Expr<i64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(expr, deserialized);
+    }
+
+    #[cfg(all(feature = "serde", feature = "decimal"))]
+    #[test]
+    fn serde_decimal_round_trip_test() {
+        use crate::decimal::Decimal;
+
+        let context = DefaultContext::new_decimal();
+        let tokens: Vec<Token<Decimal>> = Tokenizer::with_context(&context)
+            .tokenize("0.1 + Max(2.5, -3) * 10")
+            .unwrap();
+
+        let json = serde_json::to_string(&tokens).unwrap();
+        let deserialized: Vec<Token<Decimal>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(tokens, deserialized);
+    }
+
+    #[cfg(all(feature = "serde", feature = "complex"))]
+    #[test]
+    fn serde_complex_round_trip_test() {
+        use crate::complex::Complex;
+        use crate::context::Config;
+
+        let config = Config::new().with_complex_number(true);
+        let context = DefaultContext::<Complex<f64>>::new_complex_with_config(config);
+        let tokens = Tokenizer::with_context(&context)
+            .tokenize("(2 + 3i) * Sqrt(-4) - 1.5i")
+            .unwrap();
+
+        let expr = Expr::from_infix(&tokens, &context).unwrap();
+        let json = serde_json::to_string(&expr).unwrap();
+        let deserialized: Expr<Complex<f64>> = serde_json::from_str(&json).unwrap();
+        assert_eq!(expr, deserialized);
+    }
+}
@@ -5,9 +5,11 @@ pub mod ops {
     use num_traits::{Float, FromPrimitive};
 
     use crate::error::*;
-    use crate::function::{Associativity, BinaryFunction, Function, Precedence};
+    use crate::function::{Associativity, BinaryFunction, Function, Notation, Precedence, UnaryFunction};
+    use crate::context::AngleUnit;
+    use crate::ops::math::{angle_functions, WithAngleUnit};
     use crate::Result;
-    use rand::random;
+    use crate::utils::rng::ContextRng;
     use std::fmt::{Display, Debug};
 
     pub struct PowOperator;
@@ -53,7 +55,29 @@ pub mod ops {
         }
     }
 
-    pub struct RandFunction;
+    /// Gets a random complex number, the real and imaginary parts are drawn as
+    /// `ops::math::RandFunction`.
+    #[derive(Debug, Clone, Default)]
+    pub struct RandFunction {
+        rng: ContextRng,
+    }
+
+    impl RandFunction {
+        /// Constructs a new `RandFunction` with its own random number generator.
+        #[inline]
+        pub fn new() -> Self {
+            RandFunction {
+                rng: ContextRng::new(),
+            }
+        }
+
+        /// Constructs a new `RandFunction` which draws its values from the given generator.
+        #[inline]
+        pub fn with_rng(rng: ContextRng) -> Self {
+            RandFunction { rng }
+        }
+    }
+
     impl<T> Function<Complex<T>> for RandFunction where T: Float + FromPrimitive + Debug + Display{
         fn name(&self) -> &str {
             "random"
@@ -72,16 +96,15 @@ pub mod ops {
                 }
             }
 
-            #[inline(always)]
-            fn random_t<N: FromPrimitive>() -> Result<N>{
-                N::from_f64(random::<f64>())
+            let random_t = || -> Result<T> {
+                T::from_f64(self.rng.next_f64())
                     .ok_or(Error::from(ErrorKind::Overflow))
-            }
+            };
 
             match args.len(){
                 0 => {
-                    let re = random_t::<T>()?;
-                    let im = random_t::<T>()?;
+                    let re = random_t()?;
+                    let im = random_t()?;
                     Ok(Complex::new(re, im))
                 },
                 1 => {
@@ -90,8 +113,8 @@ pub mod ops {
                         return Err(Error::from(ErrorKind::NegativeValue))
                     }
 
-                    let re = random_t::<T>()? * max;
-                    let im = random_t::<T>()? * max;
+                    let re = random_t()? * max;
+                    let im = random_t()? * max;
                     Ok(Complex::new(re, im))
                 },
                 2 => {
@@ -104,8 +127,8 @@ pub mod ops {
                         ));
                     }
 
-                    let re = min + ((max - min) * random_t::<T>()?);
-                    let im = min + ((max - min) * random_t::<T>()?);
+                    let re = min + ((max - min) * random_t()?);
+                    let im = min + ((max - min) * random_t()?);
                     Ok(Complex::new(re, im))
                 }
                 _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
@@ -166,43 +189,92 @@ pub mod ops {
 
     //////////////////// Trigonometric ////////////////////
 
-    pub struct SinFunction;
-    forward_impl_func!(SinFunction, sin);
-
-    pub struct CosFunction;
-    forward_impl_func!(CosFunction, cos);
-
-    pub struct TanFunction;
-    forward_impl_func!(TanFunction, tan);
+    angle_functions!(
+        AngleUnit::Radians;
+        SinFunction, CosFunction, TanFunction, CscFunction, SecFunction, CotFunction,
+        ASinFunction, ACosFunction, ATanFunction, ACscFunction, ASecFunction, ACotFunction,
+        DegreeOperator,
+    );
+
+    /// Multiplies the value by the given factor, returns the same value if the factor is 1.
+    #[inline]
+    fn scale<T: Float>(value: Complex<T>, factor: f64) -> Result<Complex<T>> {
+        if factor == 1.0 {
+            Ok(value)
+        } else {
+            T::from(factor)
+                .map(|factor| value * factor)
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
 
-    pub struct CscFunction;
-    forward_impl_func_inv!(CscFunction, sin, csc);
+    macro_rules! impl_trig {
+        ($t:ty, $name:ident, |$z:ident| $body:expr) => {
+            impl<T> Function<Complex<T>> for WithAngleUnit<$t> where T: Float{
+                fn name(&self) -> &str {
+                    stringify!($name)
+                }
 
-    pub struct SecFunction;
-    forward_impl_func_inv!(SecFunction, cos, sec);
+                fn call(&self, args: &[Complex<T>]) -> Result<Complex<T>> {
+                    match args {
+                        [z] => {
+                            let $z = scale(*z, self.unit.radians_per_unit())?;
+                            Ok($body)
+                        }
+                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+                    }
+                }
+            }
+        };
+    }
 
-    pub struct CotFunction;
-    forward_impl_func_inv!(CotFunction, tan, cot);
+    impl_trig!(SinFunction, sin, |z| z.sin());
+    impl_trig!(CosFunction, cos, |z| z.cos());
+    impl_trig!(TanFunction, tan, |z| z.tan());
+    impl_trig!(CscFunction, csc, |z| z.sin().inv());
+    impl_trig!(SecFunction, sec, |z| z.cos().inv());
+    impl_trig!(CotFunction, cot, |z| z.tan().inv());
 
-    //////////////////// Inverse Trigonometric ////////////////////
+    /// Converts an angle in degrees to the `AngleUnit` of the context: `90°`.
+    impl<T> UnaryFunction<Complex<T>> for WithAngleUnit<DegreeOperator> where T: Float{
+        fn name(&self) -> &str {
+            "°"
+        }
 
-    pub struct ASinFunction;
-    forward_impl_func!(ASinFunction, asin);
+        fn notation(&self) -> Notation {
+            Notation::Postfix
+        }
 
-    pub struct ACosFunction;
-    forward_impl_func!(ACosFunction, acos);
+        fn call(&self, value: Complex<T>) -> Result<Complex<T>> {
+            scale(value, self.unit.from_degrees(1.0))
+        }
+    }
 
-    pub struct ATanFunction;
-    forward_impl_func!(ATanFunction, atan);
+    //////////////////// Inverse Trigonometric ////////////////////
 
-    pub struct ACscFunction;
-    forward_impl_func_inv!(ACscFunction, asin, acsc);
+    macro_rules! impl_arc_trig {
+        ($t:ty, $name:ident, |$z:ident| $body:expr) => {
+            impl<T> Function<Complex<T>> for WithAngleUnit<$t> where T: Float{
+                fn name(&self) -> &str {
+                    stringify!($name)
+                }
 
-    pub struct ASecFunction;
-    forward_impl_func_inv!(ASecFunction, acos, asec);
+                fn call(&self, args: &[Complex<T>]) -> Result<Complex<T>> {
+                    match args {
+                        [$z] => scale($body, self.unit.radians_per_unit().recip()),
+                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+                    }
+                }
+            }
+        };
+    }
 
-    pub struct ACotFunction;
-    forward_impl_func_inv!(ACotFunction, atan, acot);
+    impl_arc_trig!(ASinFunction, asin, |z| z.asin());
+    impl_arc_trig!(ACosFunction, acos, |z| z.acos());
+    impl_arc_trig!(ATanFunction, atan, |z| z.atan());
+    impl_arc_trig!(ACscFunction, acsc, |z| z.asin().inv());
+    impl_arc_trig!(ASecFunction, asec, |z| z.acos().inv());
+    impl_arc_trig!(ACotFunction, acot, |z| z.atan().inv());
 
     //////////////////// Hyperbolic Trigonometric ////////////////////
 
@@ -250,9 +322,11 @@ pub mod context {
     use num_traits::{FromPrimitive, Float};
 
     use crate::complex::ops::PowOperator;
-    use crate::context::{Config, Context, DefaultContext};
+    use crate::context::builder::{ContextBuilder, Pack};
+    use crate::context::{AngleUnit, Config, Context, DefaultContext};
     use crate::ops::unchecked::*;
     use crate::ops::math::UnaryPlus;
+    use crate::ops::random::ChoiceFunction;
     use super::ops::*;
     use std::fmt::{Debug, Display};
 
@@ -264,50 +338,90 @@ pub mod context {
         }
 
         pub fn new_complex_with_config(config: Config) -> Self {
-            let mut context = DefaultContext::empty_with_config(config.with_complex_number(true));
+            ContextBuilder::new().with_config(config).build_complex()
+        }
+    }
+
+    impl ContextBuilder {
+        /// Builds a context with `Complex` functions, see `DefaultContext::new_complex`.
+        ///
+        /// The `Rounding` pack and the functions which requires ordering as `max` or `min`
+        /// are not available for complex numbers, from the `Random` pack only `random`
+        /// and `choice` are available.
+        pub fn build_complex<'a, T>(self) -> DefaultContext<'a, Complex<T>>
+        where
+            T: Float + FromPrimitive + Debug + Display,
+        {
+            let config = self.config().clone().with_complex_number(true);
+            let mut context = DefaultContext::empty_with_config(config);
             context.add_constant("PI", Complex::from_f64(std::f64::consts::PI).unwrap());
             context.add_constant("E", Complex::from_f64(std::f64::consts::E).unwrap());
             context.add_constant("i", Complex::i());
-            context.add_binary_function(AddOperator);
-            context.add_binary_function(SubOperator);
-            context.add_binary_function(MulOperator);
-            context.add_binary_function(DivOperator);
-            context.add_binary_function(ModOperator);
-            context.add_binary_function(PowOperator);
-            context.add_unary_function(UnaryPlus);
-            context.add_unary_function(UnaryMinus);
-            context.add_function(SumFunction);
-            context.add_function(AvgFunction);
-            context.add_function(ProdFunction);
-            context.add_function(SqrtFunction);
-            context.add_function(LnFunction);
-            context.add_function(LogFunction);
-            context.add_function(RandFunction);
-            context.add_function(ExpFunction);
-            context.add_function(SinFunction);
-            context.add_function(CosFunction);
-            context.add_function(TanFunction);
-            context.add_function(CscFunction);
-            context.add_function(SecFunction);
-            context.add_function(CotFunction);
-            context.add_function(ASinFunction);
-            context.add_function(ACosFunction);
-            context.add_function(ATanFunction);
-            context.add_function(ACscFunction);
-            context.add_function(ASecFunction);
-            context.add_function(ACotFunction);
-            context.add_function(SinhFunction);
-            context.add_function(CoshFunction);
-            context.add_function(TanhFunction);
-            context.add_function(CschFunction);
-            context.add_function(SechFunction);
-            context.add_function(CothFunction);
-            context.add_function(ASinhFunction);
-            context.add_function(ACoshFunction);
-            context.add_function(ATanhFunction);
-            context.add_function(ACschFunction);
-            context.add_function(ASechFunction);
-            context.add_function(ACothFunction);
+
+            if self.has_pack(Pack::Arithmetic) {
+                context.add_binary_function(AddOperator);
+                context.add_binary_function(SubOperator);
+                context.add_binary_function(MulOperator);
+                context.add_binary_function(DivOperator);
+                context.add_binary_function(ModOperator);
+                context.add_binary_function(PowOperator);
+                context.add_unary_function(UnaryPlus);
+                context.add_unary_function(UnaryMinus);
+                context.add_function(SqrtFunction);
+            }
+
+            if self.has_pack(Pack::Trigonometry) {
+                let unit = self.config().angle_unit.unwrap_or(AngleUnit::Radians);
+                context.add_function(SinFunction::with_unit(unit));
+                context.add_function(CosFunction::with_unit(unit));
+                context.add_function(TanFunction::with_unit(unit));
+                context.add_function(CscFunction::with_unit(unit));
+                context.add_function(SecFunction::with_unit(unit));
+                context.add_function(CotFunction::with_unit(unit));
+                context.add_function(ASinFunction::with_unit(unit));
+                context.add_function(ACosFunction::with_unit(unit));
+                context.add_function(ATanFunction::with_unit(unit));
+                context.add_function(ACscFunction::with_unit(unit));
+                context.add_function(ASecFunction::with_unit(unit));
+                context.add_function(ACotFunction::with_unit(unit));
+                context.add_unary_function(DegreeOperator::with_unit(unit));
+            }
+
+            if self.has_pack(Pack::Hyperbolic) {
+                context.add_function(SinhFunction);
+                context.add_function(CoshFunction);
+                context.add_function(TanhFunction);
+                context.add_function(CschFunction);
+                context.add_function(SechFunction);
+                context.add_function(CothFunction);
+                context.add_function(ASinhFunction);
+                context.add_function(ACoshFunction);
+                context.add_function(ATanhFunction);
+                context.add_function(ACschFunction);
+                context.add_function(ASechFunction);
+                context.add_function(ACothFunction);
+            }
+
+            if self.has_pack(Pack::Logarithms) {
+                context.add_function(LnFunction);
+                context.add_function(LogFunction);
+                context.add_function(ExpFunction);
+            }
+
+            if self.has_pack(Pack::Aggregates) {
+                context.add_function(SumFunction);
+                context.add_function(AvgFunction);
+                context.add_function(ProdFunction);
+            }
+
+            if self.has_pack(Pack::Random) {
+                context.add_function(RandFunction::with_rng(context.rng().clone()));
+            }
+
+            if self.has_pack(Pack::Sampling) {
+                context.add_function(ChoiceFunction::with_rng(context.rng().clone()));
+            }
+
             context
         }
     }
@@ -1,15 +1,21 @@
 use std::borrow::Borrow;
 use std::collections::HashMap;
-use std::rc::Rc;
 
+use crate::error::{Error, ErrorKind};
 use crate::function::{BinaryFunction, Function, UnaryFunction};
 use crate::num::checked::CheckedNum;
 use crate::num::unchecked::UncheckedNum;
-use crate::ops::math::*;
 use crate::utils::ignore_case_string::IgnoreCaseString;
+use crate::utils::rng::ContextRng;
 use crate::utils::static_store::StaticStore;
+use crate::{Result, Shared};
+use builder::{ContextBuilder, Pack};
 use validate::{OrPanic, TokenKind};
 
+pub mod builder;
+pub mod scoped;
+pub mod state;
+
 /// Trait to provides the variables, constants and functions used for evaluate an expression.
 pub trait Context<'a, N> {
     /// Gets the configuration of the context.
@@ -37,13 +43,75 @@ pub trait Context<'a, N> {
     fn get_constant(&self, name: &str) -> Option<&N>;
 
     /// Gets a function with the given name.
-    fn get_function(&self, name: &str) -> Option<&Rc<dyn Function<N> + 'a>>;
+    fn get_function(&self, name: &str) -> Option<&Shared<dyn Function<N> + 'a>>;
 
     /// Gets an unary function with the given name.
-    fn get_unary_function(&self, name: &str) -> Option<&Rc<dyn UnaryFunction<N> + 'a>>;
+    fn get_unary_function(&self, name: &str) -> Option<&Shared<dyn UnaryFunction<N> + 'a>>;
 
     /// Gets a binary function with the given name.
-    fn get_binary_function(&self, name: &str) -> Option<&Rc<dyn BinaryFunction<N> + 'a>>;
+    fn get_binary_function(&self, name: &str) -> Option<&Shared<dyn BinaryFunction<N> + 'a>>;
+
+    /// Removes a variable from the context, returning its value if exists. By default `None`.
+    fn remove_variable(&mut self, _name: &str) -> Option<N> {
+        None
+    }
+
+    /// Removes a constant from the context, returning its value if exists. By default `None`.
+    fn remove_constant(&mut self, _name: &str) -> Option<N> {
+        None
+    }
+
+    /// Removes a function from the context, returning it if exists. By default `None`.
+    fn remove_function(&mut self, _name: &str) -> Option<Shared<dyn Function<N> + 'a>> {
+        None
+    }
+
+    /// Removes an unary function from the context, returning it if exists. By default `None`.
+    fn remove_unary_function(&mut self, _name: &str) -> Option<Shared<dyn UnaryFunction<N> + 'a>> {
+        None
+    }
+
+    /// Removes a binary function from the context, returning it if exists. By default `None`.
+    fn remove_binary_function(&mut self, _name: &str) -> Option<Shared<dyn BinaryFunction<N> + 'a>> {
+        None
+    }
+
+    /// Gets an iterator over the names of the variables in the context. By default empty.
+    fn variable_names(&self) -> Names<'_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Gets an iterator over the names of the constants in the context. By default empty.
+    fn constant_names(&self) -> Names<'_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Gets an iterator over the names of the functions in the context. By default empty.
+    fn function_names(&self) -> Names<'_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Gets an iterator over the names of the unary functions in the context. By default empty.
+    fn unary_function_names(&self) -> Names<'_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Gets an iterator over the names of the binary functions in the context. By default empty.
+    fn binary_function_names(&self) -> Names<'_> {
+        Box::new(std::iter::empty())
+    }
+
+    /// Removes all the variables of the context.
+    fn clear_variables(&mut self) {
+        let names = self
+            .variable_names()
+            .map(str::to_string)
+            .collect::<Vec<String>>();
+
+        for name in names {
+            self.remove_variable(&name);
+        }
+    }
 
     /// Checks if exists a variable with the given name.
     #[inline]
@@ -76,6 +144,9 @@ pub trait Context<'a, N> {
     }
 }
 
+/// An iterator over the names of the variables, constants or functions of a `Context`.
+pub type Names<'c> = Box<dyn Iterator<Item = &'c str> + 'c>;
+
 /// Provides a default implementation of a math `Context`.
 #[derive(Clone)]
 pub struct DefaultContext<'a, N> {
@@ -84,13 +155,15 @@ pub struct DefaultContext<'a, N> {
     /// The constants.
     constants: HashMap<IgnoreCaseString, N>,
     /// The functions.
-    functions: HashMap<IgnoreCaseString, Rc<dyn Function<N> + 'a>>,
+    functions: HashMap<IgnoreCaseString, Shared<dyn Function<N> + 'a>>,
     /// The unary functions.
-    unary_functions: HashMap<IgnoreCaseString, Rc<dyn UnaryFunction<N> + 'a>>,
+    unary_functions: HashMap<IgnoreCaseString, Shared<dyn UnaryFunction<N> + 'a>>,
     /// The binary functions.
-    binary_functions: HashMap<IgnoreCaseString, Rc<dyn BinaryFunction<N> + 'a>>,
+    binary_functions: HashMap<IgnoreCaseString, Shared<dyn BinaryFunction<N> + 'a>>,
     /// Additional information about this context
     config: Config,
+    /// The random number generator used by the random functions.
+    rng: ContextRng,
 }
 
 impl<'a, N> DefaultContext<'a, N> {
@@ -104,6 +177,7 @@ impl<'a, N> DefaultContext<'a, N> {
             binary_functions: Default::default(),
             unary_functions: Default::default(),
             config: Config::new(),
+            rng: ContextRng::new(),
         }
     }
 
@@ -117,10 +191,25 @@ impl<'a, N> DefaultContext<'a, N> {
             functions: Default::default(),
             binary_functions: Default::default(),
             unary_functions: Default::default(),
+            rng: ContextRng::from_seed(config.seed),
             config,
         }
     }
 
+    /// Gets the random number generator used by the random functions of this context.
+    #[inline]
+    pub fn rng(&self) -> &ContextRng {
+        &self.rng
+    }
+
+    /// Sets the seed of the random number generator of this context,
+    /// after this the random functions produces the same sequence of values for the same seed.
+    #[inline]
+    pub fn set_seed(&mut self, seed: u64) {
+        self.config.seed = Some(seed);
+        self.rng.set_seed(seed);
+    }
+
     /// Gets a reference to the variable values of this context.
     #[inline]
     pub fn variables(&self) -> &HashMap<IgnoreCaseString, N> {
@@ -135,19 +224,19 @@ impl<'a, N> DefaultContext<'a, N> {
 
     /// Gets a reference to the functions of this context.
     #[inline]
-    pub fn functions(&self) -> &HashMap<IgnoreCaseString, Rc<dyn Function<N> + 'a>> {
+    pub fn functions(&self) -> &HashMap<IgnoreCaseString, Shared<dyn Function<N> + 'a>> {
         &self.functions
     }
 
     /// Gets a reference to the unary functions of this context.
     #[inline]
-    pub fn unary_functions(&self) -> &HashMap<IgnoreCaseString, Rc<dyn UnaryFunction<N> + 'a>> {
+    pub fn unary_functions(&self) -> &HashMap<IgnoreCaseString, Shared<dyn UnaryFunction<N> + 'a>> {
         &self.unary_functions
     }
 
     /// Gets a reference to the binary functions of this context.
     #[inline]
-    pub fn binary_functions(&self) -> &HashMap<IgnoreCaseString, Rc<dyn BinaryFunction<N> + 'a>> {
+    pub fn binary_functions(&self) -> &HashMap<IgnoreCaseString, Shared<dyn BinaryFunction<N> + 'a>> {
         &self.binary_functions
     }
 
@@ -156,6 +245,9 @@ impl<'a, N> DefaultContext<'a, N> {
     /// # Remarks
     /// - This allows to use a function with an alias.
     ///
+    /// # Panics
+    /// If the name is invalid or a function with the same name exists, see `try_add_function_as`.
+    ///
     /// # Examples
     /// ```
     /// use math_engine::context::{DefaultContext, Context};
@@ -167,32 +259,20 @@ impl<'a, N> DefaultContext<'a, N> {
     /// ```
     #[inline]
     pub fn add_function_as<F: Function<N> + 'a>(&mut self, func: F, name: &str) {
-        #[cfg(debug_assertions)]
-        validate::check_token_name(TokenKind::Function, name).or_panic();
-
-        let function_name = IgnoreCaseString::from(name);
-        if self.functions.contains_key(&function_name) {
-            panic!("A function named '{}' already exists", function_name);
-        } else {
-            self.functions.insert(function_name, Rc::new(func));
-        }
+        self.try_add_function_as(func, name).or_panic()
     }
 
     /// Adds the specified unary function to the context using the given name.
     ///
     /// # Remarks
     /// - This allows to use an unary function with an alias.
+    ///
+    /// # Panics
+    /// If the name is invalid or an unary function with the same name exists,
+    /// see `try_add_unary_function_as`.
     #[inline]
     pub fn add_unary_function_as<F: UnaryFunction<N> + 'a>(&mut self, func: F, name: &str) {
-        #[cfg(debug_assertions)]
-        validate::check_token_name(TokenKind::Operator, name).or_panic();
-
-        let function_name = IgnoreCaseString::from(name);
-        if self.unary_functions.contains_key(&function_name) {
-            panic!("An unary function named '{}' already exists", function_name);
-        } else {
-            self.unary_functions.insert(function_name, Rc::new(func));
-        }
+        self.try_add_unary_function_as(func, name).or_panic()
     }
 
     /// Adds the specified binary function to the context using the given name.
@@ -200,6 +280,10 @@ impl<'a, N> DefaultContext<'a, N> {
     /// # Remarks
     /// - This allows to use a binary function with an alias.
     ///
+    /// # Panics
+    /// If the name is invalid or a binary function with the same name exists,
+    /// see `try_add_binary_function_as`.
+    ///
     /// # Examples
     /// ```
     /// use math_engine::context::{DefaultContext, Context};
@@ -211,25 +295,234 @@ impl<'a, N> DefaultContext<'a, N> {
     /// ```
     #[inline]
     pub fn add_binary_function_as<F: BinaryFunction<N> + 'a>(&mut self, func: F, name: &str) {
-        #[cfg(debug_assertions)]
-        {
-            if name.chars().count() == 1{
-                validate::check_token_name(TokenKind::Operator, name).or_panic();
-            }
-            else{
-                validate::check_token_name(TokenKind::Function, name).or_panic();
-            }
+        self.try_add_binary_function_as(func, name).or_panic()
+    }
+
+    /// Adds the specified function to the context using its name.
+    ///
+    /// Returns an error if the name is invalid or a function with the same name exists.
+    #[inline]
+    pub fn try_add_function<F: Function<N> + 'a>(&mut self, func: F) -> Result<()> {
+        let name = func.name().to_string();
+        self.try_add_function_as(func, &name)
+    }
+
+    /// Adds the specified function to the context using the given name.
+    ///
+    /// Returns an error if the name is invalid or a function with the same name exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use math_engine::context::DefaultContext;
+    /// use math_engine::error::ErrorKind;
+    /// use math_engine::ops::math::{MaxFunction, MinFunction};
+    ///
+    /// let mut context : DefaultContext<f64> = DefaultContext::new_checked();
+    /// let error = context.try_add_function_as(MinFunction, "max").unwrap_err();
+    /// assert_eq!(ErrorKind::DuplicateName, error.kind());
+    /// assert!(context.try_add_function_as(MaxFunction, "maximum").is_ok());
+    /// ```
+    pub fn try_add_function_as<F: Function<N> + 'a>(&mut self, func: F, name: &str) -> Result<()> {
+        validate::check_token_name(TokenKind::Function, name)?;
+
+        let function_name = IgnoreCaseString::from(name);
+        if self.functions.contains_key(&function_name) {
+            return Err(duplicated("function", name));
         }
 
+        self.functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds the specified unary function to the context using its name.
+    ///
+    /// Returns an error if the name is invalid or an unary function with the same name exists.
+    #[inline]
+    pub fn try_add_unary_function<F: UnaryFunction<N> + 'a>(&mut self, func: F) -> Result<()> {
+        let name = func.name().to_string();
+        self.try_add_unary_function_as(func, &name)
+    }
+
+    /// Adds the specified unary function to the context using the given name.
+    ///
+    /// Returns an error if the name is invalid or an unary function with the same name exists.
+    pub fn try_add_unary_function_as<F: UnaryFunction<N> + 'a>(&mut self, func: F, name: &str) -> Result<()> {
+        validate::check_token_name(TokenKind::Operator, name)?;
+
+        let function_name = IgnoreCaseString::from(name);
+        if self.unary_functions.contains_key(&function_name) {
+            return Err(duplicated("unary function", name));
+        }
+
+        self.unary_functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds the specified binary function to the context using its name.
+    ///
+    /// Returns an error if the name is invalid or a binary function with the same name exists.
+    #[inline]
+    pub fn try_add_binary_function<F: BinaryFunction<N> + 'a>(&mut self, func: F) -> Result<()> {
+        let name = func.name().to_string();
+        self.try_add_binary_function_as(func, &name)
+    }
+
+    /// Adds the specified binary function to the context using the given name.
+    ///
+    /// Returns an error if the name is invalid or a binary function with the same name exists.
+    pub fn try_add_binary_function_as<F: BinaryFunction<N> + 'a>(&mut self, func: F, name: &str) -> Result<()> {
+        check_binary_function_name(name)?;
+
         let function_name = IgnoreCaseString::from(name);
         if self.binary_functions.contains_key(&function_name) {
-            panic!("A binary function named '{}' already exists", function_name);
-        } else {
-            self.binary_functions.insert(function_name, Rc::new(func));
+            return Err(duplicated("binary function", name));
         }
+
+        self.binary_functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds a constant to the context.
+    ///
+    /// Returns an error if the name is invalid, a variable with the same name exists
+    /// or a constant with the same name exists, use `replace_constant` to change its value.
+    pub fn try_add_constant(&mut self, name: &str, value: N) -> Result<()> {
+        validate::check_token_name(TokenKind::Constant, name)?;
+
+        let string = IgnoreCaseString::from(name);
+        if self.variables.contains_key(&string) {
+            return Err(conflict("constant", "variable", name));
+        }
+
+        if self.constants.contains_key(&string) {
+            return Err(duplicated("constant", name));
+        }
+
+        self.constants.insert(string, value);
+        Ok(())
+    }
+
+    /// Adds or set the value of a variable in the context, returning the previous value if any.
+    ///
+    /// Returns an error if the name is invalid or a constant with the same name exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use math_engine::context::DefaultContext;
+    /// use math_engine::error::ErrorKind;
+    ///
+    /// let mut context : DefaultContext<f64> = DefaultContext::new_checked();
+    /// assert_eq!(Ok(None), context.try_set_variable("x", 10.0));
+    /// assert_eq!(Ok(Some(10.0)), context.try_set_variable("x", 2.0));
+    /// assert_eq!(ErrorKind::NameConflict, context.try_set_variable("PI", 3.0).unwrap_err().kind());
+    /// assert_eq!(ErrorKind::InvalidName, context.try_set_variable("1x", 3.0).unwrap_err().kind());
+    /// ```
+    pub fn try_set_variable(&mut self, name: &str, value: N) -> Result<Option<N>> {
+        validate::check_token_name(TokenKind::Variable, name)?;
+
+        let string = IgnoreCaseString::from(name);
+        if self.constants.contains_key(&string) {
+            return Err(conflict("variable", "constant", name));
+        }
+
+        Ok(self.variables.insert(string, value))
+    }
+
+    /// Adds the specified function using the given name replacing the function with the same name,
+    /// returning the replaced function if any.
+    ///
+    /// Returns an error if the name is invalid.
+    ///
+    /// # Examples
+    /// ```
+    /// use math_engine::context::DefaultContext;
+    /// use math_engine::ops::math::MinFunction;
+    /// use math_engine::evaluator::Evaluator;
+    ///
+    /// let mut context : DefaultContext<f64> = DefaultContext::new_checked();
+    /// let replaced = context.replace_function_as(MinFunction, "max").unwrap();
+    /// assert!(replaced.is_some());
+    ///
+    /// let evaluator = Evaluator::with_context(context);
+    /// assert_eq!(Ok(1.0), evaluator.eval("max(1, 2)"));
+    /// ```
+    pub fn replace_function_as<F: Function<N> + 'a>(
+        &mut self,
+        func: F,
+        name: &str,
+    ) -> Result<Option<Shared<dyn Function<N> + 'a>>> {
+        validate::check_token_name(TokenKind::Function, name)?;
+        Ok(self.functions.insert(IgnoreCaseString::from(name), Shared::new(func)))
+    }
+
+    /// Adds the specified unary function using the given name replacing the unary function
+    /// with the same name, returning the replaced function if any.
+    ///
+    /// Returns an error if the name is invalid.
+    pub fn replace_unary_function_as<F: UnaryFunction<N> + 'a>(
+        &mut self,
+        func: F,
+        name: &str,
+    ) -> Result<Option<Shared<dyn UnaryFunction<N> + 'a>>> {
+        validate::check_token_name(TokenKind::Operator, name)?;
+        Ok(self.unary_functions.insert(IgnoreCaseString::from(name), Shared::new(func)))
+    }
+
+    /// Adds the specified binary function using the given name replacing the binary function
+    /// with the same name, returning the replaced function if any.
+    ///
+    /// Returns an error if the name is invalid.
+    pub fn replace_binary_function_as<F: BinaryFunction<N> + 'a>(
+        &mut self,
+        func: F,
+        name: &str,
+    ) -> Result<Option<Shared<dyn BinaryFunction<N> + 'a>>> {
+        check_binary_function_name(name)?;
+        Ok(self.binary_functions.insert(IgnoreCaseString::from(name), Shared::new(func)))
+    }
+
+    /// Adds or replace the value of a constant, returning the previous value if any.
+    ///
+    /// Returns an error if the name is invalid or a variable with the same name exists.
+    pub fn replace_constant(&mut self, name: &str, value: N) -> Result<Option<N>> {
+        validate::check_token_name(TokenKind::Constant, name)?;
+
+        let string = IgnoreCaseString::from(name);
+        if self.variables.contains_key(&string) {
+            return Err(conflict("constant", "variable", name));
+        }
+
+        Ok(self.constants.insert(string, value))
+    }
+}
+
+/// Binary functions with a single `char` and the shifts `<<` and `>>` are operators, eg: `+`,
+/// others are named as functions, eg: `mod`.
+#[inline]
+fn check_binary_function_name(name: &str) -> Result<()> {
+    if name.chars().count() == 1 || name == "<<" || name == ">>" {
+        validate::check_token_name(TokenKind::Operator, name)
+    } else {
+        validate::check_token_name(TokenKind::Function, name)
     }
 }
 
+#[inline]
+fn duplicated(kind: &str, name: &str) -> Error {
+    Error::new(
+        ErrorKind::DuplicateName,
+        format!("A {} named `{}` already exists", kind, name),
+    )
+}
+
+#[inline]
+fn conflict(kind: &str, other_kind: &str, name: &str) -> Error {
+    Error::new(
+        ErrorKind::NameConflict,
+        format!("Invalid {} name, a {} named `{}` exists", kind, other_kind, name),
+    )
+}
+
 impl<'a, N> Context<'a, N> for DefaultContext<'a, N> {
     #[inline]
     fn config(&self) -> &Config {
@@ -256,35 +549,12 @@ impl<'a, N> Context<'a, N> for DefaultContext<'a, N> {
 
     #[inline]
     fn add_constant(&mut self, name: &str, value: N) {
-        #[cfg(debug_assertions)]
-        validate::check_token_name(TokenKind::Constant, name).or_panic();
-
-        //self.constants.insert(IgnoreCaseString::from(name), value);
-        let string = IgnoreCaseString::from(name);
-        if self.variables.contains_key(&string) {
-            panic!(
-                "Invalid constant name, a variable named `{}` exists",
-                string
-            )
-        } else {
-            self.constants.insert(string, value);
-        }
+        self.replace_constant(name, value).or_panic();
     }
 
     #[inline]
     fn set_variable(&mut self, name: &str, value: N) -> Option<N> {
-        #[cfg(debug_assertions)]
-        validate::check_token_name(TokenKind::Variable, name).or_panic();
-
-        let string = IgnoreCaseString::from(name);
-        if self.constants.contains_key(&string) {
-            panic!(
-                "Invalid variable name, a constant named `{}` exists",
-                string
-            )
-        } else {
-            self.variables.insert(string, value)
-        }
+        self.try_set_variable(name, value).or_panic()
     }
 
     #[inline]
@@ -298,19 +568,74 @@ impl<'a, N> Context<'a, N> for DefaultContext<'a, N> {
     }
 
     #[inline]
-    fn get_function(&self, name: &str) -> Option<&Rc<dyn Function<N> + 'a>> {
+    fn get_function(&self, name: &str) -> Option<&Shared<dyn Function<N> + 'a>> {
         self.functions.get(&IgnoreCaseString::from(name))
     }
 
     #[inline]
-    fn get_unary_function(&self, name: &str) -> Option<&Rc<dyn UnaryFunction<N> + 'a>> {
+    fn get_unary_function(&self, name: &str) -> Option<&Shared<dyn UnaryFunction<N> + 'a>> {
         self.unary_functions.get(&IgnoreCaseString::from(name))
     }
 
     #[inline]
-    fn get_binary_function(&self, name: &str) -> Option<&Rc<dyn BinaryFunction<N> + 'a>> {
+    fn get_binary_function(&self, name: &str) -> Option<&Shared<dyn BinaryFunction<N> + 'a>> {
         self.binary_functions.get(&IgnoreCaseString::from(name))
     }
+
+    #[inline]
+    fn remove_variable(&mut self, name: &str) -> Option<N> {
+        self.variables.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_constant(&mut self, name: &str) -> Option<N> {
+        self.constants.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_function(&mut self, name: &str) -> Option<Shared<dyn Function<N> + 'a>> {
+        self.functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_unary_function(&mut self, name: &str) -> Option<Shared<dyn UnaryFunction<N> + 'a>> {
+        self.unary_functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_binary_function(&mut self, name: &str) -> Option<Shared<dyn BinaryFunction<N> + 'a>> {
+        self.binary_functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn variable_names(&self) -> Names<'_> {
+        Box::new(self.variables.keys().map(IgnoreCaseString::as_raw_str))
+    }
+
+    #[inline]
+    fn constant_names(&self) -> Names<'_> {
+        Box::new(self.constants.keys().map(IgnoreCaseString::as_raw_str))
+    }
+
+    #[inline]
+    fn function_names(&self) -> Names<'_> {
+        Box::new(self.functions.keys().map(IgnoreCaseString::as_raw_str))
+    }
+
+    #[inline]
+    fn unary_function_names(&self) -> Names<'_> {
+        Box::new(self.unary_functions.keys().map(IgnoreCaseString::as_raw_str))
+    }
+
+    #[inline]
+    fn binary_function_names(&self) -> Names<'_> {
+        Box::new(self.binary_functions.keys().map(IgnoreCaseString::as_raw_str))
+    }
+
+    #[inline]
+    fn clear_variables(&mut self) {
+        self.variables.clear();
+    }
 }
 
 impl<'a, N: CheckedNum> DefaultContext<'a, N> {
@@ -318,6 +643,11 @@ impl<'a, N: CheckedNum> DefaultContext<'a, N> {
     ///
     /// # Safety
     /// Stores a cache of the `DefaultContext` used as raw pointers.
+    ///
+    /// The cache is initialized in a thread safe way, but the same instance is shared by all
+    /// the threads, which is only safe with the `sync` feature where the functions are stored in an `Arc`.
+    /// Without it, `Tokenizer::new()` and the formatters `new()` must not be used from multiple threads,
+    /// use `with_context` instead.
     pub unsafe fn instance() -> &'static DefaultContext<'a, N> {
         static STATIC_DEFAULT_CONTEXT : StaticStore = StaticStore::new();
         STATIC_DEFAULT_CONTEXT.load(|| DefaultContext::new_checked())
@@ -339,63 +669,7 @@ impl<'a, N: CheckedNum> DefaultContext<'a, N> {
     /// Some functions may cause overflow exceptions, the functions of this context
     /// ensures will return an error instead of throws an exception.
     pub fn new_checked_with_config(config: Config) -> Self {
-        use crate::ops::checked::*;
-
-        let mut context = Self::empty_with_config(config);
-        context.add_constant("PI", N::from_f64(std::f64::consts::PI).unwrap());
-        context.add_constant("E", N::from_f64(std::f64::consts::E).unwrap());
-        context.add_binary_function(AddOperator);
-        context.add_binary_function(SubOperator);
-        context.add_binary_function(MulOperator);
-        context.add_binary_function(DivOperator);
-        context.add_binary_function(PowOperator);
-        context.add_binary_function(ModOperator);
-        context.add_unary_function(UnaryPlus);
-        context.add_unary_function(UnaryMinus);
-        context.add_unary_function(Factorial);
-        context.add_function(SumFunction);
-        context.add_function(ProdFunction);
-        context.add_function(AvgFunction);
-        context.add_function(MaxFunction);
-        context.add_function(MinFunction);
-        context.add_function(AbsFunction);
-        context.add_function(SqrtFunction);
-        context.add_function(LnFunction);
-        context.add_function(LogFunction);
-        context.add_function(ExpFunction);
-        context.add_function(FloorFunction);
-        context.add_function(CeilFunction);
-        context.add_function(TruncateFunction);
-        context.add_function(RoundFunction);
-        context.add_function(SignFunction);
-        context.add_function(RandFunction);
-        context.add_function(ToRadiansFunction);
-        context.add_function(ToDegreesFunction);
-        context.add_function(SinFunction);
-        context.add_function(CosFunction);
-        context.add_function(TanFunction);
-        context.add_function(CscFunction);
-        context.add_function(SecFunction);
-        context.add_function(CotFunction);
-        context.add_function(ASinFunction);
-        context.add_function(ACosFunction);
-        context.add_function(ATanFunction);
-        context.add_function(ACscFunction);
-        context.add_function(ASecFunction);
-        context.add_function(ACotFunction);
-        context.add_function(SinhFunction);
-        context.add_function(CoshFunction);
-        context.add_function(TanhFunction);
-        context.add_function(CschFunction);
-        context.add_function(SechFunction);
-        context.add_function(CothFunction);
-        context.add_function(ASinhFunction);
-        context.add_function(ACoshFunction);
-        context.add_function(ATanhFunction);
-        context.add_function(ACschFunction);
-        context.add_function(ASechFunction);
-        context.add_function(ACothFunction);
-        context
+        ContextBuilder::new().with_config(config).build_checked()
     }
 }
 
@@ -414,62 +688,17 @@ impl<'a, N: UncheckedNum> DefaultContext<'a, N> {
     /// # Remarks
     /// Functions of this context may panic when the value overflows.
     pub fn new_unchecked_with_config(config: Config) -> Self {
-        use crate::ops::unchecked::*;
-
-        let mut context = Self::empty_with_config(config);
-        context.add_constant("PI", N::from_f64(std::f64::consts::PI).unwrap());
-        context.add_constant("E", N::from_f64(std::f64::consts::E).unwrap());
-        context.add_binary_function(AddOperator);
-        context.add_binary_function(SubOperator);
-        context.add_binary_function(MulOperator);
-        context.add_binary_function(DivOperator);
-        context.add_binary_function(PowOperator);
-        context.add_binary_function(ModOperator);
-        context.add_unary_function(UnaryPlus);
-        context.add_unary_function(UnaryMinus);
-        context.add_unary_function(Factorial);
-        context.add_function(SumFunction);
-        context.add_function(AvgFunction);
-        context.add_function(ProdFunction);
-        context.add_function(MaxFunction);
-        context.add_function(MinFunction);
-        context.add_function(SqrtFunction);
-        context.add_function(LnFunction);
-        context.add_function(LogFunction);
-        context.add_function(RandFunction);
-        context.add_function(ToRadiansFunction);
-        context.add_function(ToDegreesFunction);
-        context.add_function(ExpFunction);
-        context.add_function(SinFunction);
-        context.add_function(CosFunction);
-        context.add_function(TanFunction);
-        context.add_function(CscFunction);
-        context.add_function(SecFunction);
-        context.add_function(CotFunction);
-        context.add_function(ASinFunction);
-        context.add_function(ACosFunction);
-        context.add_function(ATanFunction);
-        context.add_function(ACscFunction);
-        context.add_function(ASecFunction);
-        context.add_function(ACotFunction);
-        context.add_function(SinhFunction);
-        context.add_function(CoshFunction);
-        context.add_function(TanhFunction);
-        context.add_function(CschFunction);
-        context.add_function(SechFunction);
-        context.add_function(CothFunction);
-        context.add_function(ASinhFunction);
-        context.add_function(ACoshFunction);
-        context.add_function(ATanhFunction);
-        context.add_function(ACschFunction);
-        context.add_function(ASechFunction);
-        context.add_function(ACothFunction);
-        context
+        // The unchecked context doesn't include the `abs` and rounding functions
+        ContextBuilder::new()
+            .with_config(config)
+            .without_pack(Pack::Rounding)
+            .build_unchecked()
     }
 }
 
 /// Represents the configuration used by a `Context`.
 #[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Config {
     /// Allows implicit multiplication.
     pub implicit_mul: bool,
@@ -477,8 +706,22 @@ pub struct Config {
     pub complex_number: bool,
     /// Allows using custom grouping symbols for function calls, eg: Max[1,2,3], Sum<2,4,6>
     pub custom_function_call: bool,
+    /// The unit of the angles taken and returned by the trigonometric functions, if `None` the
+    /// default of the number type is used: degrees for real and decimal numbers and radians for complex numbers.
+    ///
+    /// The decimal inverse trigonometric functions also return angles in this unit, before they returned radians.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub angle_unit: Option<AngleUnit>,
     /// Stores the grouping symbols as: `(`, `)`, `[`, `]`.
+    #[cfg_attr(feature = "serde", serde(with = "serde_grouping"))]
     grouping: HashMap<char, GroupingSymbol>,
+    /// The limits used for evaluate an expression, by default there is no limits.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub limits: Limits,
+    /// The seed of the random number generator of the context, if `None` the
+    /// generator is seeded from the operating system.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub seed: Option<u64>,
 }
 
 impl Config {
@@ -519,10 +762,60 @@ impl Config {
         self
     }
 
+    /// Sets the limits used for evaluate expressions with this `Config`.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::{Config, Limits};
+    ///
+    /// let config = Config::new().with_limits(Limits::new().with_max_expression_len(100));
+    /// assert_eq!(Some(100), config.limits.max_expression_len);
+    /// ```
+    #[inline]
+    pub fn with_limits(mut self, limits: Limits) -> Config {
+        self.limits = limits;
+        self
+    }
+
+    /// Sets the seed of the random number generator of the context.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::{Config, DefaultContext};
+    /// use math_engine::evaluator::Evaluator;
+    ///
+    /// let a = Evaluator::with_context(DefaultContext::<f64>::new_checked_with_config(Config::new().with_seed(10)));
+    /// let b = Evaluator::with_context(DefaultContext::<f64>::new_checked_with_config(Config::new().with_seed(10)));
+    /// assert_eq!(a.eval("random(100)"), b.eval("random(100)"));
+    /// ```
+    #[inline]
+    pub fn with_seed(mut self, seed: u64) -> Config {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Sets the unit of the angles used by the trigonometric functions of the context.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::{AngleUnit, Config, DefaultContext};
+    /// use math_engine::evaluator::Evaluator;
+    ///
+    /// let config = Config::new().with_angle_unit(AngleUnit::Radians);
+    /// let evaluator = Evaluator::with_context(DefaultContext::<f64>::new_checked_with_config(config));
+    /// assert_eq!(Ok(1.0), evaluator.eval("sin(PI / 2)"));
+    /// assert_eq!(Ok(1.0), evaluator.eval("sin(90°)"));
+    /// ```
+    #[inline]
+    pub fn with_angle_unit(mut self, angle_unit: AngleUnit) -> Config {
+        self.angle_unit = Some(angle_unit);
+        self
+    }
+
     /// Adds a pair of grouping symbols to this `Config`.
     ///
     /// # Panics
-    /// If the config already contains the given symbol.
+    /// If the config already contains the given symbol, see `try_with_group_symbol`.
     ///
     /// # Example
     /// ```
@@ -533,19 +826,46 @@ impl Config {
     ///     .with_group_symbol('(', ')')
     ///     .with_group_symbol('[', ']');
     /// ```
-    pub fn with_group_symbol(mut self, open_group: char, close_group: char) -> Config {
-        let grouping = &mut self.grouping;
-        let grouping_symbol = GroupingSymbol::new(open_group, close_group);
+    #[inline]
+    pub fn with_group_symbol(self, open_group: char, close_group: char) -> Config {
+        self.try_with_group_symbol(open_group, close_group).or_panic()
+    }
 
-        if grouping.insert(open_group, grouping_symbol).is_some() {
-            panic!("Duplicated symbol: `{}`", open_group);
+    /// Adds a pair of grouping symbols to this `Config`.
+    ///
+    /// Returns an error if the config already contains any of the given symbols
+    /// or both symbols are the same.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::Config;
+    /// use math_engine::error::ErrorKind;
+    ///
+    /// let config = Config::new().try_with_group_symbol('[', ']').unwrap();
+    /// let error = config.try_with_group_symbol('{', ']').unwrap_err();
+    /// assert_eq!(ErrorKind::DuplicateName, error.kind());
+    /// ```
+    pub fn try_with_group_symbol(mut self, open_group: char, close_group: char) -> Result<Config> {
+        if open_group == close_group {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Grouping symbols cannot be the same: `{}`", open_group),
+            ));
         }
 
-        if grouping.insert(close_group, grouping_symbol).is_some() {
-            panic!("Duplicated symbol: `{}`", close_group);
+        for symbol in &[open_group, close_group] {
+            if self.grouping.contains_key(symbol) {
+                return Err(Error::new(
+                    ErrorKind::DuplicateName,
+                    format!("Duplicated symbol: `{}`", symbol),
+                ));
+            }
         }
 
-        self
+        let grouping_symbol = GroupingSymbol::new(open_group, close_group);
+        self.grouping.insert(open_group, grouping_symbol);
+        self.grouping.insert(close_group, grouping_symbol);
+        Ok(self)
     }
 
     /// Gets a grouping symbol pair from this `Config`.
@@ -629,12 +949,196 @@ impl Default for Config {
             complex_number: false,
             custom_function_call: false,
             grouping: Default::default(),
+            angle_unit: None,
+            limits: Limits::new(),
+            seed: None,
+        }
+    }
+}
+
+/// Limits used for evaluate expressions from untrusted sources, `None` means no limit.
+///
+/// - `max_expression_len` is checked by the `Tokenizer`.
+/// - `max_tokens` is checked by the `Tokenizer`.
+/// - `max_depth` is checked when converting to `Reverse Polish Notation`.
+/// - `max_eval_steps` and `max_recursion_depth` are checked by `rpn_eval` and the expressions
+///   compiled by `BatchExpr`, `closure::compile` and `Program`.
+///
+/// Each number, variable, constant and operator is an step of the evaluation and a function
+/// is charged an step plus one for each argument. The work done inside a function or operator
+/// is not counted, except for the factorial which is charged an step for each multiplication
+/// and the integer power which is charged an step for each bit of the exponent,
+/// so `99999!` exceeds any limit below 100001 steps.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Limits {
+    /// Max number of `char`s of an expression.
+    pub max_expression_len: Option<usize>,
+    /// Max number of tokens of an expression.
+    pub max_tokens: Option<usize>,
+    /// Max depth of nested grouping symbols, eg: `((2))` have a depth of 2.
+    pub max_depth: Option<usize>,
+    /// Max number of operations performed in an evaluation, including nested evaluations.
+    pub max_eval_steps: Option<usize>,
+    /// Max depth of nested evaluations, as a function defined by the user which evaluates its body.
+    pub max_recursion_depth: Option<usize>,
+}
+
+impl Limits {
+    /// Constructs a new `Limits` without any limit.
+    #[inline]
+    pub const fn new() -> Self {
+        Limits {
+            max_expression_len: None,
+            max_tokens: None,
+            max_depth: None,
+            max_eval_steps: None,
+            max_recursion_depth: None,
+        }
+    }
+
+    /// Sets the max number of `char`s of an expression.
+    #[inline]
+    pub fn with_max_expression_len(mut self, max: usize) -> Limits {
+        self.max_expression_len = Some(max);
+        self
+    }
+
+    /// Sets the max number of tokens of an expression.
+    #[inline]
+    pub fn with_max_tokens(mut self, max: usize) -> Limits {
+        self.max_tokens = Some(max);
+        self
+    }
+
+    /// Sets the max depth of nested grouping symbols.
+    #[inline]
+    pub fn with_max_depth(mut self, max: usize) -> Limits {
+        self.max_depth = Some(max);
+        self
+    }
+
+    /// Sets the max number of operations performed in an evaluation.
+    #[inline]
+    pub fn with_max_eval_steps(mut self, max: usize) -> Limits {
+        self.max_eval_steps = Some(max);
+        self
+    }
+
+    /// Sets the max depth of nested evaluations.
+    #[inline]
+    pub fn with_max_recursion_depth(mut self, max: usize) -> Limits {
+        self.max_recursion_depth = Some(max);
+        self
+    }
+}
+
+/// Serializes the grouping symbols of a `Config` as a list of pairs,
+/// maps with `char` keys are not supported by all the formats.
+#[cfg(feature = "serde")]
+mod serde_grouping {
+    use std::collections::HashMap;
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use super::GroupingSymbol;
+
+    pub fn serialize<S>(grouping: &HashMap<char, GroupingSymbol>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut symbols = grouping
+            .iter()
+            .filter(|(c, symbol)| **c == symbol.group_open)
+            .map(|(_, symbol)| *symbol)
+            .collect::<Vec<GroupingSymbol>>();
+
+        symbols.sort_by_key(|symbol| symbol.as_tuple());
+        symbols.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<HashMap<char, GroupingSymbol>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut grouping = HashMap::new();
+        for symbol in Vec::<GroupingSymbol>::deserialize(deserializer)? {
+            for c in &[symbol.group_open, symbol.group_close] {
+                if grouping.insert(*c, symbol).is_some() {
+                    return Err(D::Error::custom(format!("Duplicated symbol: `{}`", c)));
+                }
+            }
+        }
+
+        Ok(grouping)
+    }
+}
+
+/// The unit of the angles used by the trigonometric functions.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AngleUnit {
+    /// A full turn is `2π` radians.
+    Radians,
+    /// A full turn is `360` degrees.
+    Degrees,
+    /// A full turn is `400` gradians.
+    Gradians,
+}
+
+impl AngleUnit {
+    /// Gets the number of radians of one unit of this angle unit.
+    #[inline]
+    pub fn radians_per_unit(self) -> f64 {
+        match self {
+            AngleUnit::Radians => 1.0,
+            AngleUnit::Degrees => std::f64::consts::PI / 180.0,
+            AngleUnit::Gradians => std::f64::consts::PI / 200.0,
+        }
+    }
+
+    /// Converts an angle in this unit to radians.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::AngleUnit;
+    ///
+    /// assert_eq!(std::f64::consts::PI, AngleUnit::Degrees.to_radians(180.0));
+    /// assert_eq!(std::f64::consts::PI, AngleUnit::Gradians.to_radians(200.0));
+    /// ```
+    #[inline]
+    pub fn to_radians(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => value,
+            AngleUnit::Degrees => value.to_radians(),
+            AngleUnit::Gradians => value * std::f64::consts::PI / 200.0,
+        }
+    }
+
+    /// Converts an angle in radians to this unit.
+    #[inline]
+    pub fn from_radians(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => value,
+            AngleUnit::Degrees => value.to_degrees(),
+            AngleUnit::Gradians => value * 200.0 / std::f64::consts::PI,
+        }
+    }
+
+    /// Converts an angle in degrees to this unit.
+    #[inline]
+    pub fn from_degrees(self, value: f64) -> f64 {
+        match self {
+            AngleUnit::Radians => value.to_radians(),
+            AngleUnit::Degrees => value,
+            AngleUnit::Gradians => value * 400.0 / 360.0,
         }
     }
 }
 
 /// Represents a grouping symbol.
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GroupingSymbol {
     /// The open symbol of teh grouping.
     pub group_open: char,
@@ -686,21 +1190,21 @@ pub mod validate {
     pub fn check_token_name(kind: TokenKind, name: &str) -> Result<()>{
         if name.is_empty(){
             return Err(Error::new(
-                ErrorKind::Empty,
+                ErrorKind::InvalidName,
                 format!("{} name is empty", kind))
             );
         }
 
         if name.chars().any(char::is_whitespace){
             return Err(Error::new(
-                ErrorKind::InvalidInput,
+                ErrorKind::InvalidName,
                 format!("{} names cannot contain whitespaces: `{}`", kind, name))
             );
         }
 
         if name.chars().any(char::is_control){
             return Err(Error::new(
-                ErrorKind::InvalidInput,
+                ErrorKind::InvalidName,
                 format!("{} names cannot contain control characters: `{}`", kind, name))
             );
         }
@@ -713,7 +1217,7 @@ pub mod validate {
                 let c = name.chars().next().unwrap();
                 if c.is_ascii_digit(){
                     return Err(Error::new(
-                        ErrorKind::InvalidInput,
+                        ErrorKind::InvalidName,
                         format!(
                             "{}s names cannot be an ASCII number: `{}`",
                             kind,
@@ -722,11 +1226,13 @@ pub mod validate {
                     );
                 }
             },
+            // The shift operators are the only multi-char symbols read as a single token
+            TokenKind::Operator if name == "<<" || name == ">>" => {},
             // PI, Sum, Mod, Sqrt, f, √(10, 2)
             TokenKind::Variable | TokenKind::Constant | TokenKind::Function | TokenKind::Operator => {
                 if !name.chars().any(|ref c| char::is_ascii_alphanumeric(c)){
                     return Err(Error::new(
-                        ErrorKind::InvalidInput,
+                        ErrorKind::InvalidName,
                         format!(
                             "{}s with names `length > 1` should only contains ASCII alphanumeric characters: `{}`",
                             kind,
@@ -737,7 +1243,7 @@ pub mod validate {
 
                 if !name.chars().next().map_or(false, |ref c| char::is_ascii_alphabetic(c)){
                     return Err(Error::new(
-                        ErrorKind::InvalidInput,
+                        ErrorKind::InvalidName,
                         format!(
                             "{}s with names `length > 1` should start with an ASCII alphabetic character: `{}`", kind, name
                         ))
@@ -878,4 +1384,198 @@ mod tests {
         assert!(context.is_unary_function("λ"));
         assert!(context.is_function("f"));
     }
+
+    #[test]
+    fn try_add_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_checked();
+
+        assert!(context.try_add_function(Dummy("double".to_string())).is_ok());
+        assert_eq!(ErrorKind::DuplicateName, context.try_add_function(Dummy("Double".to_string())).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidName, context.try_add_function(Dummy("my func".to_string())).unwrap_err().kind());
+        assert_eq!(ErrorKind::DuplicateName, context.try_add_unary_function(Dummy("!".to_string())).unwrap_err().kind());
+        assert_eq!(ErrorKind::DuplicateName, context.try_add_binary_function(Dummy("+".to_string())).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidName, context.try_add_binary_function(Dummy("2".to_string())).unwrap_err().kind());
+
+        assert!(context.try_add_constant("TAU", 6.28).is_ok());
+        assert_eq!(ErrorKind::DuplicateName, context.try_add_constant("tau", 6.0).unwrap_err().kind());
+        assert_eq!(Ok(None), context.try_set_variable("x", 1.0));
+        assert_eq!(ErrorKind::NameConflict, context.try_add_constant("X", 6.0).unwrap_err().kind());
+        assert_eq!(ErrorKind::NameConflict, context.try_set_variable("Tau", 1.0).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidName, context.try_set_variable("", 1.0).unwrap_err().kind());
+    }
+
+    #[test]
+    fn replace_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_checked();
+
+        assert!(context.replace_function_as(Dummy("max".to_string()), "Max").unwrap().is_some());
+        assert!(context.replace_function_as(Dummy("f".to_string()), "f").unwrap().is_none());
+        assert!(context.replace_unary_function_as(Dummy("!".to_string()), "!").unwrap().is_some());
+        assert!(context.replace_binary_function_as(Dummy("+".to_string()), "+").unwrap().is_some());
+        assert!(context.replace_function_as(Dummy("f".to_string()), "1f").is_err());
+
+        assert_eq!(Ok(Some(std::f64::consts::PI)), context.replace_constant("PI", 3.0));
+        assert_eq!(Some(&3.0), context.get_constant("pi"));
+
+        context.set_variable("x", 1.0);
+        assert_eq!(ErrorKind::NameConflict, context.replace_constant("x", 3.0).unwrap_err().kind());
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_checked();
+        context.set_variable("x", 1.0);
+        context.set_variable("y", 2.0);
+
+        assert_eq!(Some(1.0), context.remove_variable("X"));
+        assert_eq!(None, context.remove_variable("x"));
+        assert!(context.remove_function("max").is_some());
+        assert!(!context.is_function("Max"));
+        assert!(context.remove_unary_function("!").is_some());
+        assert!(context.remove_binary_function("+").is_some());
+        assert_eq!(Some(std::f64::consts::PI), context.remove_constant("PI"));
+
+        context.set_variable("z", 3.0);
+        context.clear_variables();
+        assert_eq!(0, context.variable_names().count());
+    }
+
+    #[test]
+    fn names_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::empty();
+        context.set_variable("x", 1.0);
+        context.add_constant("Two", 2.0);
+        context.add_function(Dummy("f".to_string()));
+        context.add_unary_function(Dummy("!".to_string()));
+        context.add_binary_function(Dummy("+".to_string()));
+
+        assert_eq!(vec!["x"], context.variable_names().collect::<Vec<&str>>());
+        assert_eq!(vec!["Two"], context.constant_names().collect::<Vec<&str>>());
+        assert_eq!(vec!["f"], context.function_names().collect::<Vec<&str>>());
+        assert_eq!(vec!["!"], context.unary_function_names().collect::<Vec<&str>>());
+        assert_eq!(vec!["+"], context.binary_function_names().collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn default_methods_test() {
+        /// A `Context` with a single variable which only implements the required methods.
+        struct SingleVariable(Config, f64);
+
+        impl<'a> Context<'a, f64> for SingleVariable {
+            fn config(&self) -> &Config {
+                &self.0
+            }
+
+            fn add_function<F: Function<f64> + 'a>(&mut self, _: F) {}
+
+            fn add_unary_function<F: UnaryFunction<f64> + 'a>(&mut self, _: F) {}
+
+            fn add_binary_function<F: BinaryFunction<f64> + 'a>(&mut self, _: F) {}
+
+            fn add_constant(&mut self, _: &str, _: f64) {}
+
+            fn set_variable(&mut self, _: &str, value: f64) -> Option<f64> {
+                Some(std::mem::replace(&mut self.1, value))
+            }
+
+            fn get_variable(&self, name: &str) -> Option<&f64> {
+                if name == "x" { Some(&self.1) } else { None }
+            }
+
+            fn get_constant(&self, _: &str) -> Option<&f64> {
+                None
+            }
+
+            fn get_function(&self, _: &str) -> Option<&Shared<dyn Function<f64> + 'a>> {
+                None
+            }
+
+            fn get_unary_function(&self, _: &str) -> Option<&Shared<dyn UnaryFunction<f64> + 'a>> {
+                None
+            }
+
+            fn get_binary_function(&self, _: &str) -> Option<&Shared<dyn BinaryFunction<f64> + 'a>> {
+                None
+            }
+        }
+
+        let mut context = SingleVariable(Config::new(), 1.0);
+        assert_eq!(None, context.remove_variable("x"));
+        assert!(context.remove_function("max").is_none());
+        assert_eq!(0, context.variable_names().count());
+        assert_eq!(0, context.function_names().count());
+
+        context.clear_variables();
+        assert!(context.is_variable("x"));
+    }
+
+    #[test]
+    fn seed_test() {
+        use crate::evaluator::Evaluator;
+
+        fn sequence<'a>(evaluator: &'a Evaluator<'a, f64>) -> Vec<f64> {
+            (0..10).map(|_| evaluator.eval("random(1, 10) + randnormal(0, 1) + randint(1, 6) + randpoisson(4) + choice(1, 2, 3)").unwrap()).collect()
+        }
+
+        fn context<'a>(config: Config) -> DefaultContext<'a, f64> {
+            ContextBuilder::new().with_config(config).with_pack(Pack::Sampling).build_checked()
+        }
+
+        let config = Config::new().with_seed(1234);
+        let a = Evaluator::with_context(context(config.clone()));
+        let b = Evaluator::with_context(context(config));
+        let expected = sequence(&a);
+        assert_eq!(expected, sequence(&b));
+
+        let mut c = Evaluator::with_context(context(Config::new()));
+        c.mut_context().set_seed(1234);
+        assert_eq!(Some(1234), c.context().config().seed);
+        assert_eq!(expected, sequence(&c));
+
+        let d = Evaluator::with_context(context(Config::new().with_seed(4321)));
+        assert_ne!(expected, sequence(&d));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn complex_seed_test() {
+        use crate::complex::Complex;
+        use crate::evaluator::Evaluator;
+
+        let config = Config::new().with_seed(99);
+        let a: Evaluator<Complex<f64>> = Evaluator::with_context(DefaultContext::new_complex_with_config(config.clone()));
+        let b: Evaluator<Complex<f64>> = Evaluator::with_context(DefaultContext::new_complex_with_config(config));
+
+        for _ in 0..10 {
+            assert_eq!(a.eval("random(2, 5)"), b.eval("random(2, 5)"));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn set_variable_conflict_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_checked();
+        context.set_variable("PI", 3.0);
+    }
+
+    #[test]
+    fn try_with_group_symbol_test() {
+        let config = Config::new().try_with_group_symbol('[', ']').unwrap();
+        assert_eq!(ErrorKind::DuplicateName, config.clone().try_with_group_symbol('(', '>').unwrap_err().kind());
+        assert_eq!(ErrorKind::DuplicateName, config.clone().try_with_group_symbol('<', ']').unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidInput, config.try_with_group_symbol('|', '|').unwrap_err().kind());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_config_test() {
+        let config = Config::new()
+            .with_group_symbol('[', ']')
+            .with_implicit_mul(true);
+
+        let json = serde_json::to_string(&config).unwrap();
+        let deserialized: Config = serde_json::from_str(&json).unwrap();
+        assert_eq!(config, deserialized);
+        assert_eq!(Some(&GroupingSymbol::new('[', ']')), deserialized.get_group_symbol('['));
+    }
 }
@@ -0,0 +1,825 @@
+use std::collections::HashSet;
+
+use crate::context::{AngleUnit, Config, Context, DefaultContext};
+use crate::num::checked::CheckedNum;
+use crate::num::unchecked::UncheckedNum;
+use crate::ops::bitwise::*;
+use crate::ops::distributions::*;
+use crate::ops::math::*;
+use crate::ops::random::*;
+use crate::ops::special::*;
+use num_traits::{FromPrimitive, PrimInt, ToPrimitive};
+
+/// A group of functions that can be added to a context using a `ContextBuilder`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Pack {
+    /// The operators `+`, `-`, `*`, `/`, `^`, `mod`, `!` and the `sqrt`, `cbrt` and `root` functions.
+    Arithmetic,
+    /// The trigonometric functions and their inverses, `toRadians`, `toDegrees` and the
+    /// postfix `°` operator, using the angle unit of the `Config`.
+    Trigonometry,
+    /// The hyperbolic functions and their inverses.
+    Hyperbolic,
+    /// The `ln`, `log` and `exp` functions.
+    Logarithms,
+    /// The `abs`, `floor`, `ceil`, `truncate`, `round` and `sign` functions.
+    Rounding,
+    /// The `sum`, `prod`, `avg`, `max` and `min` functions.
+    Aggregates,
+    /// The `random` function, drawing from the random number generator of the context.
+    Random,
+    /// The `count`, `median`, `mode`, `percentile`, `range`, `variance`, `pvariance`,
+    /// `stddev`, `pstddev`, `geomean` and `harmean` functions.
+    ///
+    /// This pack is opt-in, it's not included in the default packs.
+    Statistics,
+    /// The `randint`, `randnormal`, `randexp`, `randpoisson`, `randbinomial` and `choice`
+    /// functions, drawing from the random number generator of the context.
+    ///
+    /// This pack is opt-in, it's not included in the default packs.
+    Sampling,
+    /// The `gcd`, `lcm`, `ncr`, `choose`, `npr`, `isprime`, `nextprime`, `factorcount`,
+    /// `divisorcount`, `minfactor`, `fib`, `modpow` and `modinv` functions,
+    /// which use exact arithmetic and expect integer values.
+    ///
+    /// This pack is opt-in, it's not included in the default packs.
+    NumberTheory,
+    /// The `normpdf`, `normcdf`, `norminv`, `binompdf`, `binomcdf`, `poissonpdf`, `poissoncdf`,
+    /// `tpdf`, `tcdf`, `chi2pdf`, `chi2cdf`, `exppdf` and `expcdf` functions.
+    ///
+    /// This pack is opt-in, it's not included in the default packs.
+    Distributions,
+    /// The operators `&`, `|`, `xor`, `<<`, `>>`, `~`, `not` and the `popcount`, `bit`,
+    /// `rotl` and `rotr` functions.
+    ///
+    /// This pack is opt-in, it's not included in the default packs.
+    ///
+    /// Only available for integer types, these are added by `build_checked_integer` and
+    /// `build_unchecked_integer`. The operators have a lower precedence than `+`, from higher
+    /// to lower: `<<` and `>>`, `&`, `xor` and `|`.
+    Bitwise,
+    /// The `gamma`, `lgamma`, `digamma`, `beta`, `erf`, `erfc`, `erfinv`, `zeta`, `besselj`,
+    /// `bessely` and `lambertw` functions.
+    ///
+    /// This pack is opt-in, it's not included in the default packs because names
+    /// as `gamma`, `beta` or `zeta` are often used for variables.
+    Special,
+}
+
+impl Pack {
+    /// Gets all the packs.
+    #[inline]
+    pub fn all() -> &'static [Pack] {
+        &[
+            Pack::Arithmetic,
+            Pack::Trigonometry,
+            Pack::Hyperbolic,
+            Pack::Logarithms,
+            Pack::Rounding,
+            Pack::Aggregates,
+            Pack::Random,
+            Pack::Statistics,
+            Pack::Sampling,
+            Pack::NumberTheory,
+            Pack::Distributions,
+            Pack::Bitwise,
+            Pack::Special,
+        ]
+    }
+
+    /// Gets the packs included by default in a `ContextBuilder`, which are the functions
+    /// of `DefaultContext::new_checked`. The other packs are opt-in.
+    #[inline]
+    pub fn defaults() -> &'static [Pack] {
+        &[
+            Pack::Arithmetic,
+            Pack::Trigonometry,
+            Pack::Hyperbolic,
+            Pack::Logarithms,
+            Pack::Rounding,
+            Pack::Aggregates,
+            Pack::Random,
+        ]
+    }
+}
+
+/// Builds a `DefaultContext` with the selected packs of functions.
+///
+/// The constants `PI` and `E` are always added, the functions of a pack
+/// not available for a number type are ignored.
+///
+/// # Example
+/// ```
+/// use math_engine::context::Context;
+/// use math_engine::context::builder::{ContextBuilder, Pack};
+/// use math_engine::evaluator::Evaluator;
+///
+/// let context = ContextBuilder::new()
+///     .without_pack(Pack::Random)
+///     .build_checked::<f64>();
+///
+/// assert!(context.is_function("sin"));
+/// assert!(!context.is_function("random"));
+///
+/// let context = ContextBuilder::empty()
+///     .with_pack(Pack::Arithmetic)
+///     .build_checked::<i64>();
+///
+/// let evaluator = Evaluator::with_context(context);
+/// assert_eq!(Ok(14), evaluator.eval("2 + 3 * 4"));
+/// assert!(evaluator.eval("Max(2, 3)").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct ContextBuilder {
+    config: Config,
+    packs: HashSet<Pack>,
+}
+
+impl ContextBuilder {
+    /// Constructs a new `ContextBuilder` with the default packs, see `Pack::defaults`.
+    #[inline]
+    pub fn new() -> Self {
+        ContextBuilder {
+            config: Config::new(),
+            packs: Pack::defaults().iter().copied().collect(),
+        }
+    }
+
+    /// Constructs a new `ContextBuilder` with no packs.
+    #[inline]
+    pub fn empty() -> Self {
+        ContextBuilder {
+            config: Config::new(),
+            packs: HashSet::new(),
+        }
+    }
+
+    /// Sets the `Config` of the context.
+    #[inline]
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Adds the given pack.
+    #[inline]
+    pub fn with_pack(mut self, pack: Pack) -> Self {
+        self.packs.insert(pack);
+        self
+    }
+
+    /// Adds all the given packs.
+    #[inline]
+    pub fn with_packs(mut self, packs: &[Pack]) -> Self {
+        self.packs.extend(packs.iter().copied());
+        self
+    }
+
+    /// Removes the given pack.
+    #[inline]
+    pub fn without_pack(mut self, pack: Pack) -> Self {
+        self.packs.remove(&pack);
+        self
+    }
+
+    /// Checks if this builder contains the given pack.
+    #[inline]
+    pub fn has_pack(&self, pack: Pack) -> bool {
+        self.packs.contains(&pack)
+    }
+
+    /// Gets the `Config` of the context.
+    #[inline]
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Builds a context with checked functions, see `DefaultContext::new_checked`.
+    pub fn build_checked<'a, N: CheckedNum>(self) -> DefaultContext<'a, N> {
+        use crate::ops::checked::*;
+
+        let mut context = DefaultContext::empty_with_config(self.config.clone());
+        context.add_constant("PI", N::from_f64(std::f64::consts::PI).unwrap());
+        context.add_constant("E", N::from_f64(std::f64::consts::E).unwrap());
+
+        if self.has_pack(Pack::Arithmetic) {
+            context.add_binary_function(AddOperator);
+            context.add_binary_function(SubOperator);
+            context.add_binary_function(MulOperator);
+            context.add_binary_function(DivOperator);
+            context.add_binary_function(PowOperator);
+            context.add_binary_function(ModOperator);
+            context.add_unary_function(UnaryPlus);
+            context.add_unary_function(UnaryMinus);
+            context.add_unary_function(Factorial);
+            context.add_function(SqrtFunction);
+            context.add_function(RootFunction);
+        }
+
+        if self.has_pack(Pack::Rounding) {
+            context.add_function(AbsFunction);
+        }
+
+        self.add_math_functions(&mut context);
+
+        if self.has_pack(Pack::Aggregates) {
+            context.add_function(SumFunction);
+            context.add_function(ProdFunction);
+            context.add_function(AvgFunction);
+            context.add_function(MaxFunction);
+            context.add_function(MinFunction);
+        }
+
+        if self.has_pack(Pack::Statistics) {
+            context.add_function(MedianFunction);
+            context.add_function(ModeFunction);
+            context.add_function(PercentileFunction);
+            context.add_function(RangeFunction);
+        }
+
+        if self.has_pack(Pack::NumberTheory) {
+            context.add_function(GcdFunction);
+            context.add_function(LcmFunction);
+            context.add_function(NcrFunction);
+            context.add_function(ChooseFunction);
+            context.add_function(NprFunction);
+            context.add_function(IsPrimeFunction);
+            context.add_function(NextPrimeFunction);
+            context.add_function(FactorCountFunction);
+            context.add_function(DivisorCountFunction);
+            context.add_function(MinFactorFunction);
+            context.add_function(FibFunction);
+            context.add_function(ModPowFunction);
+            context.add_function(ModInvFunction);
+        }
+
+        context
+    }
+
+    /// Builds a context with unchecked functions, see `DefaultContext::new_unchecked`.
+    pub fn build_unchecked<'a, N: UncheckedNum>(self) -> DefaultContext<'a, N> {
+        use crate::ops::unchecked::*;
+
+        let mut context = DefaultContext::empty_with_config(self.config.clone());
+        context.add_constant("PI", N::from_f64(std::f64::consts::PI).unwrap());
+        context.add_constant("E", N::from_f64(std::f64::consts::E).unwrap());
+
+        if self.has_pack(Pack::Arithmetic) {
+            context.add_binary_function(AddOperator);
+            context.add_binary_function(SubOperator);
+            context.add_binary_function(MulOperator);
+            context.add_binary_function(DivOperator);
+            context.add_binary_function(PowOperator);
+            context.add_binary_function(ModOperator);
+            context.add_unary_function(UnaryPlus);
+            context.add_unary_function(UnaryMinus);
+            context.add_unary_function(Factorial);
+            context.add_function(SqrtFunction);
+            context.add_function(RootFunction);
+        }
+
+        if self.has_pack(Pack::Rounding) {
+            context.add_function(AbsFunction);
+        }
+
+        self.add_math_functions(&mut context);
+
+        if self.has_pack(Pack::Aggregates) {
+            context.add_function(SumFunction);
+            context.add_function(ProdFunction);
+            context.add_function(AvgFunction);
+            context.add_function(MaxFunction);
+            context.add_function(MinFunction);
+        }
+
+        if self.has_pack(Pack::Statistics) {
+            context.add_function(MedianFunction);
+            context.add_function(ModeFunction);
+            context.add_function(PercentileFunction);
+            context.add_function(RangeFunction);
+        }
+
+        if self.has_pack(Pack::NumberTheory) {
+            context.add_function(GcdFunction);
+            context.add_function(LcmFunction);
+            context.add_function(NcrFunction);
+            context.add_function(ChooseFunction);
+            context.add_function(NprFunction);
+            context.add_function(IsPrimeFunction);
+            context.add_function(NextPrimeFunction);
+            context.add_function(FactorCountFunction);
+            context.add_function(DivisorCountFunction);
+            context.add_function(MinFactorFunction);
+            context.add_function(FibFunction);
+            context.add_function(ModPowFunction);
+            context.add_function(ModInvFunction);
+        }
+
+        context
+    }
+
+    /// Builds a context with checked functions for an integer type,
+    /// which also includes the `Bitwise` pack if selected.
+    ///
+    /// # Example
+    /// ```
+    /// use math_engine::context::builder::{ContextBuilder, Pack};
+    /// use math_engine::evaluator::Evaluator;
+    ///
+    /// let context = ContextBuilder::new()
+    ///     .with_pack(Pack::Bitwise)
+    ///     .build_checked_integer::<i64>();
+    /// let evaluator = Evaluator::with_context(context);
+    /// assert_eq!(Ok(24), evaluator.eval("1 + 2 << 3"));
+    /// assert_eq!(Ok(10), evaluator.eval("6 & 3 | 8"));
+    /// ```
+    pub fn build_checked_integer<'a, N: CheckedNum + PrimInt>(self) -> DefaultContext<'a, N> {
+        let bitwise = self.has_pack(Pack::Bitwise);
+        let mut context = self.build_checked();
+        if bitwise {
+            add_bitwise_functions(&mut context);
+        }
+        context
+    }
+
+    /// Builds a context with unchecked functions for an integer type,
+    /// which also includes the `Bitwise` pack if selected.
+    pub fn build_unchecked_integer<'a, N: UncheckedNum + PrimInt>(self) -> DefaultContext<'a, N> {
+        let bitwise = self.has_pack(Pack::Bitwise);
+        let mut context = self.build_unchecked();
+        if bitwise {
+            add_bitwise_functions(&mut context);
+        }
+        context
+    }
+
+    /// Adds the functions of `ops::math` shared by the checked and unchecked contexts.
+    fn add_math_functions<N>(&self, context: &mut DefaultContext<'_, N>)
+    where
+        N: ToPrimitive + FromPrimitive + Clone,
+    {
+        if self.has_pack(Pack::Trigonometry) {
+            let unit = self.config.angle_unit.unwrap_or(AngleUnit::Degrees);
+            context.add_function(ToRadiansFunction);
+            context.add_function(ToDegreesFunction);
+            context.add_function(SinFunction::with_unit(unit));
+            context.add_function(CosFunction::with_unit(unit));
+            context.add_function(TanFunction::with_unit(unit));
+            context.add_function(CscFunction::with_unit(unit));
+            context.add_function(SecFunction::with_unit(unit));
+            context.add_function(CotFunction::with_unit(unit));
+            context.add_function(ASinFunction::with_unit(unit));
+            context.add_function(ACosFunction::with_unit(unit));
+            context.add_function(ATanFunction::with_unit(unit));
+            context.add_function(ACscFunction::with_unit(unit));
+            context.add_function(ASecFunction::with_unit(unit));
+            context.add_function(ACotFunction::with_unit(unit));
+            context.add_unary_function(DegreeOperator::with_unit(unit));
+        }
+
+        if self.has_pack(Pack::Hyperbolic) {
+            context.add_function(SinhFunction);
+            context.add_function(CoshFunction);
+            context.add_function(TanhFunction);
+            context.add_function(CschFunction);
+            context.add_function(SechFunction);
+            context.add_function(CothFunction);
+            context.add_function(ASinhFunction);
+            context.add_function(ACoshFunction);
+            context.add_function(ATanhFunction);
+            context.add_function(ACschFunction);
+            context.add_function(ASechFunction);
+            context.add_function(ACothFunction);
+        }
+
+        if self.has_pack(Pack::Logarithms) {
+            context.add_function(LnFunction);
+            context.add_function(LogFunction);
+            context.add_function(ExpFunction);
+        }
+
+        if self.has_pack(Pack::Rounding) {
+            context.add_function(FloorFunction);
+            context.add_function(CeilFunction);
+            context.add_function(TruncateFunction);
+            context.add_function(RoundFunction);
+            context.add_function(SignFunction);
+        }
+
+        if self.has_pack(Pack::Statistics) {
+            context.add_function(CountFunction);
+            context.add_function(VarianceFunction);
+            context.add_function(PopVarianceFunction);
+            context.add_function(StdDevFunction);
+            context.add_function(PopStdDevFunction);
+            context.add_function(GeoMeanFunction);
+            context.add_function(HarMeanFunction);
+        }
+
+        if self.has_pack(Pack::Random) {
+            context.add_function(RandFunction::with_rng(context.rng().clone()));
+        }
+
+        if self.has_pack(Pack::Sampling) {
+            let rng = context.rng().clone();
+            context.add_function(RandIntFunction::with_rng(rng.clone()));
+            context.add_function(RandNormalFunction::with_rng(rng.clone()));
+            context.add_function(RandExpFunction::with_rng(rng.clone()));
+            context.add_function(RandPoissonFunction::with_rng(rng.clone()));
+            context.add_function(RandBinomialFunction::with_rng(rng.clone()));
+            context.add_function(ChoiceFunction::with_rng(rng));
+        }
+
+        if self.has_pack(Pack::Distributions) {
+            add_distribution_functions(context);
+        }
+
+        if self.has_pack(Pack::Special) {
+            add_special_functions(context);
+        }
+    }
+}
+
+/// Adds the functions of the `Distributions` pack.
+pub(crate) fn add_distribution_functions<N>(context: &mut DefaultContext<'_, N>)
+where
+    N: ToPrimitive + FromPrimitive,
+{
+    context.add_function(NormPdfFunction);
+    context.add_function(NormCdfFunction);
+    context.add_function(NormInvFunction);
+    context.add_function(BinomPdfFunction);
+    context.add_function(BinomCdfFunction);
+    context.add_function(PoissonPdfFunction);
+    context.add_function(PoissonCdfFunction);
+    context.add_function(TPdfFunction);
+    context.add_function(TCdfFunction);
+    context.add_function(Chi2PdfFunction);
+    context.add_function(Chi2CdfFunction);
+    context.add_function(ExpPdfFunction);
+    context.add_function(ExpCdfFunction);
+}
+
+/// Adds the functions of the `Special` pack.
+pub(crate) fn add_special_functions<N>(context: &mut DefaultContext<'_, N>)
+where
+    N: ToPrimitive + FromPrimitive,
+{
+    context.add_function(GammaFunction);
+    context.add_function(LnGammaFunction);
+    context.add_function(DigammaFunction);
+    context.add_function(BetaFunction);
+    context.add_function(ErfFunction);
+    context.add_function(ErfcFunction);
+    context.add_function(ErfInvFunction);
+    context.add_function(ZetaFunction);
+    context.add_function(BesselJFunction);
+    context.add_function(BesselYFunction);
+    context.add_function(LambertWFunction);
+}
+
+/// Adds the operators and functions of the `Bitwise` pack.
+///
+/// Unsigned integers are not `CheckedNum`, this can be used to add the bitwise
+/// operators to a context for those types.
+///
+/// # Example
+/// ```
+/// use math_engine::context::{Context, DefaultContext};
+/// use math_engine::context::builder::add_bitwise_functions;
+/// use math_engine::evaluator::Evaluator;
+/// use math_engine::ops::checked::AddOperator;
+///
+/// let mut context: DefaultContext<u32> = DefaultContext::empty();
+/// context.add_binary_function(AddOperator);
+/// add_bitwise_functions(&mut context);
+///
+/// let evaluator = Evaluator::with_context(context);
+/// assert_eq!(Ok(0xFFFF_FFF0), evaluator.eval("~15"));
+/// assert_eq!(Ok(16_384), evaluator.eval("1 << 2 + 12"));
+/// ```
+pub fn add_bitwise_functions<N: PrimInt>(context: &mut DefaultContext<'_, N>) {
+    context.add_binary_function(BitAndOperator);
+    context.add_binary_function(BitOrOperator);
+    context.add_binary_function(BitXorOperator);
+    context.add_binary_function(ShlOperator);
+    context.add_binary_function(ShrOperator);
+    context.add_unary_function(BitNotOperator);
+    context.add_unary_function(NotOperator);
+    context.add_function(PopCountFunction);
+    context.add_function(BitFunction);
+    context.add_function(RotateLeftFunction);
+    context.add_function(RotateRightFunction);
+}
+
+impl Default for ContextBuilder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{Error, ErrorKind};
+    use crate::evaluator::Evaluator;
+
+    #[test]
+    fn build_all_test() {
+        let context: DefaultContext<f64> = ContextBuilder::empty().with_packs(Pack::all()).build_checked();
+        for name in &["sin", "sinh", "ln", "floor", "sum", "median", "random", "abs", "randint", "gcd"] {
+            assert!(context.is_function(name), "{} not found", name);
+        }
+
+        let context: DefaultContext<f64> = ContextBuilder::empty().with_packs(Pack::all()).build_unchecked();
+        for name in &["cos", "cosh", "log", "round", "max", "stddev", "random", "sqrt", "choice", "ncr"] {
+            assert!(context.is_function(name), "{} not found", name);
+        }
+    }
+
+    #[test]
+    fn build_defaults_test() {
+        fn names<N>(context: &DefaultContext<N>) -> Vec<String> {
+            let mut names = context.function_names().map(str::to_lowercase).collect::<Vec<String>>();
+            names.sort();
+            names
+        }
+
+        let checked = [
+            "abs", "acos", "acosh", "acot", "acoth", "acsc", "acsch", "asec", "asech", "asin",
+            "asinh", "atan", "atanh", "avg", "ceil", "cos", "cosh", "cot", "coth", "csc", "csch",
+            "exp", "floor", "ln", "log", "max", "min", "product", "random", "root", "round", "sec", "sech",
+            "sign", "sin", "sinh", "sqrt", "sum", "tan", "tanh", "todegrees", "toradians", "truncate",
+        ];
+
+        assert_eq!(checked.to_vec(), names(&DefaultContext::<f64>::new_checked()));
+        assert_eq!(checked.to_vec(), names(&ContextBuilder::new().build_checked::<f64>()));
+
+        // The unchecked context doesn't include the `abs` and rounding functions
+        let unchecked = checked
+            .iter()
+            .copied()
+            .filter(|name| !["abs", "ceil", "floor", "round", "sign", "truncate"].contains(name))
+            .collect::<Vec<&str>>();
+
+        assert_eq!(unchecked, names(&DefaultContext::<f64>::new_unchecked()));
+    }
+
+    #[test]
+    fn build_packs_test() {
+        let context: DefaultContext<i64> = ContextBuilder::empty()
+            .with_packs(&[Pack::Arithmetic, Pack::Aggregates])
+            .build_checked();
+
+        assert!(context.is_binary_function("+"));
+        assert!(context.is_function("sum"));
+        assert!(context.is_constant("PI"));
+        assert!(!context.is_function("sin"));
+        assert!(!context.is_function("random"));
+
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(13), evaluator.eval("Sum(1, 2, 3) + 7"));
+        assert_eq!(Ok(3), evaluator.eval("Root(27, 3)"));
+        assert_eq!(Ok(4052555153018976267), evaluator.eval("3^39"));
+        assert_eq!(Err(Error::from(ErrorKind::Overflow)), evaluator.eval("25!"));
+        assert_eq!(ErrorKind::NegativeValue, evaluator.eval("2^-1").unwrap_err().kind());
+        assert!(evaluator.eval("Median(1, 2, 3)").is_err());
+        assert!(evaluator.eval("Sin(1)").is_err());
+
+        let context: DefaultContext<f64> = ContextBuilder::empty().build_unchecked();
+        assert!(!context.is_binary_function("+"));
+        assert_eq!(0, context.function_names().count());
+    }
+
+    #[test]
+    fn build_number_theory_test() {
+        let context: DefaultContext<i64> = ContextBuilder::new().build_checked();
+        assert!(!context.is_function("gcd"));
+
+        let context: DefaultContext<i64> = ContextBuilder::new()
+            .with_pack(Pack::NumberTheory)
+            .build_checked();
+        let evaluator = Evaluator::with_context(context);
+
+        assert_eq!(Ok(6), evaluator.eval("Gcd(12, 18) + Lcm(0, 5)"));
+        assert_eq!(Ok(252), evaluator.eval("Ncr(10, 5)"));
+        assert_eq!(Ok(7540113804746346429), evaluator.eval("Fib(92)"));
+        assert!(evaluator.eval("Fib(93)").is_err());
+        assert_eq!(Ok(1), evaluator.eval("IsPrime(2147483647)"));
+
+        let context: DefaultContext<f64> = ContextBuilder::new()
+            .with_pack(Pack::NumberTheory)
+            .build_unchecked();
+        let evaluator = Evaluator::with_context(context);
+
+        assert_eq!(Ok(4.0), evaluator.eval("ModInv(3, 11)"));
+        assert_eq!(Ok(9.0), evaluator.eval("DivisorCount(36)"));
+        assert!(evaluator.eval("Gcd(1.5, 3)").is_err());
+    }
+
+    #[test]
+    fn build_distributions_test() {
+        let context: DefaultContext<f64> = ContextBuilder::new().build_checked();
+        assert!(!context.is_function("normcdf"));
+
+        let context: DefaultContext<f64> = ContextBuilder::new()
+            .with_pack(Pack::Distributions)
+            .build_unchecked();
+
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(0.5), evaluator.eval("NormCdf(100, 100, 15)"));
+        assert_eq!(Ok(1.0), evaluator.eval("BinomCdf(2, 0.5, 2)"));
+        assert!(evaluator.eval("NormInv(2)").is_err());
+    }
+
+    #[test]
+    fn build_special_test() {
+        let context: DefaultContext<f64> = ContextBuilder::new().build_checked();
+        assert!(!context.is_function("gamma"));
+
+        let context: DefaultContext<f64> = ContextBuilder::new()
+            .with_pack(Pack::Special)
+            .build_checked();
+
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(24.0), evaluator.eval("Gamma(5)"));
+        assert_eq!(Ok(0.0), evaluator.eval("erf(0) + lambertw(0)"));
+        assert!(evaluator.eval("zeta(1)").is_err());
+        assert!(evaluator.eval("besselj(1.5, 2)").is_err());
+    }
+
+    #[test]
+    fn build_bitwise_test() {
+        let context: DefaultContext<i64> = ContextBuilder::new().build_checked();
+        assert!(!context.is_binary_function("<<"));
+
+        let context: DefaultContext<i64> = ContextBuilder::new().build_checked_integer();
+        assert!(!context.is_binary_function("<<"));
+
+        let context: DefaultContext<i64> = ContextBuilder::new()
+            .with_pack(Pack::Bitwise)
+            .build_checked_integer();
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(24), evaluator.eval("1 + 2 << 3"));
+        assert_eq!(Ok(10), evaluator.eval("6 & 3 | 8"));
+        assert_eq!(Ok(10), evaluator.eval("8 | 6 & 3"));
+        assert_eq!(Ok(2), evaluator.eval("6 & 3 << 1 xor 4"));
+        assert_eq!(Ok(5), evaluator.eval("1 | 2 xor 6"));
+        assert_eq!(Ok(-8), evaluator.eval("~7"));
+        assert_eq!(Ok(-8), evaluator.eval("not 7"));
+        assert_eq!(Ok(3), evaluator.eval("popcount(7) * bit(4, 2) + 0"));
+        assert_eq!(Ok(1), evaluator.eval("rotl(1 << 63, 1)"));
+        assert!(evaluator.eval("1 << 64").is_err());
+
+        let context: DefaultContext<i32> = ContextBuilder::empty()
+            .with_packs(&[Pack::Arithmetic, Pack::Bitwise])
+            .build_unchecked_integer();
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(-4), evaluator.eval("-16 >> 2"));
+        assert_eq!(Ok(6), evaluator.eval("2 * 3 & 7"));
+
+        let mut context: DefaultContext<u32> = DefaultContext::empty();
+        context.add_binary_function(crate::ops::checked::AddOperator);
+        add_bitwise_functions(&mut context);
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(0xFFFF_FFF8), evaluator.eval("~7"));
+        assert_eq!(Ok(0x8000_0000), evaluator.eval("rotr(1, 1)"));
+        assert_eq!(Ok(1024), evaluator.eval("1 << 2 + 8"));
+    }
+
+    #[test]
+    fn build_angle_unit_test() {
+        use crate::context::AngleUnit;
+
+        fn eval(unit: AngleUnit, expr: &str) -> f64 {
+            let config = Config::new().with_angle_unit(unit);
+            let context = ContextBuilder::new().with_config(config).build_checked::<f64>();
+            Evaluator::with_context(context).eval(expr).unwrap()
+        }
+
+        let cases = [
+            (AngleUnit::Degrees, "sin(30)", 0.5),
+            (AngleUnit::Degrees, "sin(30°)", 0.5),
+            (AngleUnit::Degrees, "atan(1)", 45.0),
+            (AngleUnit::Radians, "sin(PI / 2)", 1.0),
+            (AngleUnit::Radians, "sin(30°)", 0.5),
+            (AngleUnit::Radians, "tan(45°)", 1.0),
+            (AngleUnit::Radians, "asin(1)", std::f64::consts::FRAC_PI_2),
+            (AngleUnit::Gradians, "cos(200)", -1.0),
+            (AngleUnit::Gradians, "90°", 100.0),
+            (AngleUnit::Gradians, "atan(1, 1)", 50.0),
+        ];
+
+        for (unit, expr, expected) in &cases {
+            let result = eval(*unit, expr);
+            assert!((result - expected).abs() < 1e-12, "{:?}: {} = {}", unit, expr, result);
+        }
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn build_decimal_angle_unit_test() {
+        use crate::context::AngleUnit;
+
+        let eval = |unit: AngleUnit, expr: &str| {
+            let config = Config::new().with_angle_unit(unit);
+            let evaluator = Evaluator::with_context(ContextBuilder::new().with_config(config).build_decimal());
+            evaluator.eval(expr).map(|n| n.round_dp(10).normalize().to_string())
+        };
+
+        assert_eq!(Ok("0.5".to_owned()), eval(AngleUnit::Degrees, "sin(30)"));
+        assert_eq!(Ok("90".to_owned()), eval(AngleUnit::Degrees, "asin(1)"));
+        assert_eq!(Ok("45".to_owned()), eval(AngleUnit::Degrees, "acot(1)"));
+        assert_eq!(Ok("1".to_owned()), eval(AngleUnit::Degrees, "cot(45)"));
+        assert_eq!(Ok("0.5".to_owned()), eval(AngleUnit::Radians, "sin(30°)"));
+        assert_eq!(Ok("1.5707963268".to_owned()), eval(AngleUnit::Radians, "asin(1)"));
+        assert_eq!(Ok("1".to_owned()), eval(AngleUnit::Gradians, "sin(100)"));
+        assert_eq!(Ok("50".to_owned()), eval(AngleUnit::Gradians, "atan(1)"));
+        assert_eq!(Ok("100".to_owned()), eval(AngleUnit::Gradians, "90°"));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn build_decimal_test() {
+        let context = DefaultContext::new_decimal();
+        assert!(!context.is_function("median"));
+        assert!(!context.is_function("gcd"));
+        assert!(!context.is_function("randint"));
+
+        let context = ContextBuilder::new()
+            .with_packs(&[Pack::Statistics, Pack::NumberTheory])
+            .without_pack(Pack::Trigonometry)
+            .build_decimal();
+
+        assert!(context.is_function("cbrt"));
+        assert!(context.is_function("round"));
+        assert!(!context.is_function("sin"));
+
+        let evaluator = Evaluator::with_context(context);
+        let eval = |expr: &str| evaluator.eval(expr).map(|n| n.round_dp(20).normalize().to_string());
+        assert_eq!(Ok("2.5".to_owned()), eval("Median(4, 1, 3, 2)"));
+        assert_eq!(Ok("3".to_owned()), eval("Mode(3, 1, 3)"));
+        assert_eq!(Ok("15".to_owned()), eval("Percentile(25, 10, 20, 30)"));
+        assert_eq!(Ok("4".to_owned()), eval("Range(5, 1, 3)"));
+        assert_eq!(Ok("4".to_owned()), eval("PVariance(2, 4, 4, 4, 5, 5, 7, 9)"));
+        assert_eq!(Ok("2".to_owned()), eval("PStdDev(2, 4, 4, 4, 5, 5, 7, 9)"));
+        assert_eq!(Ok("2".to_owned()), eval("HarMean(1, 4, 4)"));
+        assert_eq!(Ok("8".to_owned()), eval("Count(1, 2, 3, 4, 5, 6, 7, 8)"));
+        assert!(eval("Variance(1)").is_err());
+        assert!(eval("GeoMean(1, -2)").is_err());
+        assert_eq!(Ok("8".to_owned()), eval("Gcd(24, 40)"));
+        assert_eq!(Ok("118264581564861424".to_owned()), eval("Ncr(60, 30)"));
+        assert!(eval("Gcd(2.5, 5)").is_err());
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn build_decimal_special_test() {
+        let context = ContextBuilder::new()
+            .with_pack(Pack::Special)
+            .build_decimal();
+
+        let evaluator = Evaluator::with_context(context);
+        let eval = |expr: &str| evaluator.eval(expr).map(|n| n.round_dp(20).normalize().to_string());
+        assert_eq!(Ok("39916800".to_owned()), eval("Gamma(12)"));
+        assert_eq!(Ok("0.84270079294971486934".to_owned()), eval("erf(1)"));
+        assert_eq!(Ok("0.15729920705028513066".to_owned()), eval("erfc(1)"));
+        assert_eq!(Ok("1.5".to_owned()), eval("zeta(0) + lambertw(0) + 2"));
+        assert!(eval("gamma(-2)").is_err());
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn build_complex_test() {
+        let context = ContextBuilder::empty()
+            .with_pack(Pack::Arithmetic)
+            .build_complex::<f64>();
+
+        assert!(context.config().complex_number);
+        assert!(context.is_constant("i"));
+        assert!(context.is_binary_function("^"));
+        assert!(!context.is_function("sum"));
+    }
+
+    #[cfg(feature = "complex")]
+    #[test]
+    fn build_complex_angle_unit_test() {
+        use crate::complex::Complex;
+        use crate::context::AngleUnit;
+
+        let approx = |a: Complex<f64>, b: Complex<f64>| (a - b).norm() < 1e-12;
+
+        let config = Config::new().with_angle_unit(AngleUnit::Degrees);
+        let context = ContextBuilder::new().with_config(config).build_complex::<f64>();
+        let evaluator = Evaluator::with_context(context);
+        assert!(approx(Complex::new(0.5, 0.0), evaluator.eval("sin(30)").unwrap()));
+        assert!(approx(Complex::new(90.0, 0.0), evaluator.eval("acos(0)").unwrap()));
+
+        // Radians by default
+        let evaluator = Evaluator::with_context(ContextBuilder::new().build_complex::<f64>());
+        assert!(approx(Complex::new(0.5, 0.0), evaluator.eval("sin(30°)").unwrap()));
+        assert!(approx(Complex::new(1.0, 0.0).sin(), evaluator.eval("sin(1)").unwrap()));
+        assert!(approx(Complex::new(1.0, 0.0), evaluator.eval("sin(PI / 2)").unwrap()));
+    }
+}
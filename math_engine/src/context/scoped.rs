@@ -0,0 +1,436 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+use crate::context::validate::{self, OrPanic, TokenKind};
+use crate::context::{check_binary_function_name, conflict, duplicated, Config, Context, Names};
+use crate::function::{BinaryFunction, Function, UnaryFunction};
+use crate::utils::ignore_case_string::IgnoreCaseString;
+use crate::{Result, Shared};
+
+/// A `Context` which references a parent `Context` and only stores its own variables,
+/// constants and functions, the lookups fall back to the parent.
+///
+/// This allows to evaluate with local variables without clone the parent, as
+/// binding the params of a function.
+///
+/// The `remove_*` and `clear_variables` methods only affect the local entries,
+/// the parent is never modified.
+///
+/// # Example
+/// ```
+/// use math_engine::context::{Context, DefaultContext};
+/// use math_engine::context::scoped::ScopedContext;
+/// use math_engine::evaluator::rpn_eval;
+/// use math_engine::tokenizer::{Tokenize, Tokenizer};
+///
+/// let global : DefaultContext<f64> = DefaultContext::new_checked();
+///
+/// let mut scope = ScopedContext::new(&global);
+/// scope.set_variable("x", 2.0);
+///
+/// let tokens = Tokenizer::with_context(&scope).tokenize("x * 10 + Max(x, 1)").unwrap();
+/// assert_eq!(Ok(22.0), rpn_eval(&tokens, &scope));
+/// assert!(!global.is_variable("x"));
+/// ```
+pub struct ScopedContext<'a, 's, N, C> {
+    /// The parent context.
+    parent: &'s C,
+    /// The local variables.
+    variables: HashMap<IgnoreCaseString, N>,
+    /// The local constants.
+    constants: HashMap<IgnoreCaseString, N>,
+    /// The local functions.
+    functions: HashMap<IgnoreCaseString, Shared<dyn Function<N> + 's>>,
+    /// The local unary functions.
+    unary_functions: HashMap<IgnoreCaseString, Shared<dyn UnaryFunction<N> + 's>>,
+    /// The local binary functions.
+    binary_functions: HashMap<IgnoreCaseString, Shared<dyn BinaryFunction<N> + 's>>,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl<'a: 's, 's, N, C> ScopedContext<'a, 's, N, C>
+where
+    C: Context<'a, N>,
+{
+    /// Constructs a new `ScopedContext` with the given parent.
+    #[inline]
+    pub fn new(parent: &'s C) -> Self {
+        ScopedContext {
+            parent,
+            variables: Default::default(),
+            constants: Default::default(),
+            functions: Default::default(),
+            unary_functions: Default::default(),
+            binary_functions: Default::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the parent context.
+    #[inline]
+    pub fn parent(&self) -> &'s C {
+        self.parent
+    }
+
+    /// Gets a reference to the local variables of this context.
+    #[inline]
+    pub fn local_variables(&self) -> &HashMap<IgnoreCaseString, N> {
+        &self.variables
+    }
+
+    /// Gets a reference to the local constants of this context.
+    #[inline]
+    pub fn local_constants(&self) -> &HashMap<IgnoreCaseString, N> {
+        &self.constants
+    }
+
+    /// Adds the specified function to this scope using its name.
+    ///
+    /// Returns an error if the name is invalid or a local function with the same name exists.
+    pub fn try_add_function<F: Function<N> + 's>(&mut self, func: F) -> Result<()> {
+        let name = func.name();
+        validate::check_token_name(TokenKind::Function, name)?;
+
+        let function_name = IgnoreCaseString::from(name);
+        if self.functions.contains_key(&function_name) {
+            return Err(duplicated("function", name));
+        }
+
+        self.functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds the specified unary function to this scope using its name.
+    ///
+    /// Returns an error if the name is invalid or a local unary function with the same name exists.
+    pub fn try_add_unary_function<F: UnaryFunction<N> + 's>(&mut self, func: F) -> Result<()> {
+        let name = func.name();
+        validate::check_token_name(TokenKind::Operator, name)?;
+
+        let function_name = IgnoreCaseString::from(name);
+        if self.unary_functions.contains_key(&function_name) {
+            return Err(duplicated("unary function", name));
+        }
+
+        self.unary_functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds the specified binary function to this scope using its name.
+    ///
+    /// Returns an error if the name is invalid or a local binary function with the same name exists.
+    ///
+    /// # Examples
+    /// ```
+    /// use math_engine::context::DefaultContext;
+    /// use math_engine::context::scoped::ScopedContext;
+    /// use math_engine::error::ErrorKind;
+    /// use math_engine::ops::unchecked::AddOperator;
+    ///
+    /// let global : DefaultContext<f64> = DefaultContext::empty();
+    /// let mut scope = ScopedContext::new(&global);
+    /// assert!(scope.try_add_binary_function(AddOperator).is_ok());
+    /// assert_eq!(ErrorKind::DuplicateName, scope.try_add_binary_function(AddOperator).unwrap_err().kind());
+    /// ```
+    pub fn try_add_binary_function<F: BinaryFunction<N> + 's>(&mut self, func: F) -> Result<()> {
+        let name = func.name();
+        check_binary_function_name(name)?;
+
+        let function_name = IgnoreCaseString::from(name);
+        if self.binary_functions.contains_key(&function_name) {
+            return Err(duplicated("binary function", name));
+        }
+
+        self.binary_functions.insert(function_name, Shared::new(func));
+        Ok(())
+    }
+
+    /// Adds a constant to this scope.
+    ///
+    /// Returns an error if the name is invalid or a variable with the same name exists in this scope or the parent.
+    pub fn try_add_constant(&mut self, name: &str, value: N) -> Result<()> {
+        validate::check_token_name(TokenKind::Constant, name)?;
+
+        if self.is_variable(name) {
+            return Err(conflict("constant", "variable", name));
+        }
+
+        self.constants.insert(IgnoreCaseString::from(name), value);
+        Ok(())
+    }
+
+    /// Adds or set the value of a local variable, returning the previous local value if any.
+    ///
+    /// Returns an error if the name is invalid or a constant with the same name exists in this scope or the parent.
+    pub fn try_set_variable(&mut self, name: &str, value: N) -> Result<Option<N>> {
+        validate::check_token_name(TokenKind::Variable, name)?;
+
+        if self.is_constant(name) {
+            return Err(conflict("variable", "constant", name));
+        }
+
+        Ok(self.variables.insert(IgnoreCaseString::from(name), value))
+    }
+}
+
+impl<'a: 's, 's, N, C> Context<'s, N> for ScopedContext<'a, 's, N, C>
+where
+    C: Context<'a, N>,
+{
+    #[inline]
+    fn config(&self) -> &Config {
+        self.parent.config()
+    }
+
+    #[inline]
+    fn add_function<F: Function<N> + 's>(&mut self, func: F) {
+        self.try_add_function(func).or_panic()
+    }
+
+    #[inline]
+    fn add_unary_function<F: UnaryFunction<N> + 's>(&mut self, func: F) {
+        self.try_add_unary_function(func).or_panic()
+    }
+
+    #[inline]
+    fn add_binary_function<F: BinaryFunction<N> + 's>(&mut self, func: F) {
+        self.try_add_binary_function(func).or_panic()
+    }
+
+    #[inline]
+    fn add_constant(&mut self, name: &str, value: N) {
+        self.try_add_constant(name, value).or_panic()
+    }
+
+    #[inline]
+    fn set_variable(&mut self, name: &str, value: N) -> Option<N> {
+        self.try_set_variable(name, value).or_panic()
+    }
+
+    fn get_variable(&self, name: &str) -> Option<&N> {
+        match self.variables.get(IgnoreCaseString::from(name).borrow()) {
+            Some(n) => Some(n),
+            None => self.parent.get_variable(name),
+        }
+    }
+
+    #[inline]
+    fn get_constant(&self, name: &str) -> Option<&N> {
+        match self.constants.get(IgnoreCaseString::from(name).borrow()) {
+            Some(n) => Some(n),
+            None => self.parent.get_constant(name),
+        }
+    }
+
+    #[inline]
+    fn get_function(&self, name: &str) -> Option<&Shared<dyn Function<N> + 's>> {
+        match self.functions.get(IgnoreCaseString::from(name).borrow()) {
+            Some(f) => Some(f),
+            None => self.parent.get_function(name),
+        }
+    }
+
+    #[inline]
+    fn get_unary_function(&self, name: &str) -> Option<&Shared<dyn UnaryFunction<N> + 's>> {
+        match self.unary_functions.get(IgnoreCaseString::from(name).borrow()) {
+            Some(f) => Some(f),
+            None => self.parent.get_unary_function(name),
+        }
+    }
+
+    #[inline]
+    fn get_binary_function(&self, name: &str) -> Option<&Shared<dyn BinaryFunction<N> + 's>> {
+        match self.binary_functions.get(IgnoreCaseString::from(name).borrow()) {
+            Some(f) => Some(f),
+            None => self.parent.get_binary_function(name),
+        }
+    }
+
+    #[inline]
+    fn remove_variable(&mut self, name: &str) -> Option<N> {
+        self.variables.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_constant(&mut self, name: &str) -> Option<N> {
+        self.constants.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_function(&mut self, name: &str) -> Option<Shared<dyn Function<N> + 's>> {
+        self.functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_unary_function(&mut self, name: &str) -> Option<Shared<dyn UnaryFunction<N> + 's>> {
+        self.unary_functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn remove_binary_function(&mut self, name: &str) -> Option<Shared<dyn BinaryFunction<N> + 's>> {
+        self.binary_functions.remove(&IgnoreCaseString::from(name))
+    }
+
+    #[inline]
+    fn variable_names(&self) -> Names<'_> {
+        names(&self.variables, self.parent.variable_names())
+    }
+
+    #[inline]
+    fn constant_names(&self) -> Names<'_> {
+        names(&self.constants, self.parent.constant_names())
+    }
+
+    #[inline]
+    fn function_names(&self) -> Names<'_> {
+        names(&self.functions, self.parent.function_names())
+    }
+
+    #[inline]
+    fn unary_function_names(&self) -> Names<'_> {
+        names(&self.unary_functions, self.parent.unary_function_names())
+    }
+
+    #[inline]
+    fn binary_function_names(&self) -> Names<'_> {
+        names(&self.binary_functions, self.parent.binary_function_names())
+    }
+
+    #[inline]
+    fn clear_variables(&mut self) {
+        self.variables.clear();
+    }
+}
+
+/// Chains the local names with the names of the parent which are not shadowed.
+fn names<'c, T>(local: &'c HashMap<IgnoreCaseString, T>, parent: Names<'c>) -> Names<'c> {
+    let parent = parent.filter(move |name| !local.contains_key(&IgnoreCaseString::from(*name)));
+    Box::new(local.keys().map(IgnoreCaseString::as_raw_str).chain(parent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::DefaultContext;
+    use crate::evaluator::rpn_eval;
+    use crate::error::ErrorKind;
+    use crate::function::{Associativity, Precedence};
+    use crate::tokenizer::{Tokenize, Tokenizer};
+
+    fn eval<'a, C: Context<'a, i64>>(context: &'a C, expression: &str) -> Result<i64> {
+        let tokens = Tokenizer::with_context(context).tokenize(expression)?;
+        rpn_eval(&tokens, context)
+    }
+
+    struct Double;
+    impl Function<i64> for Double {
+        fn name(&self) -> &str {
+            "double"
+        }
+
+        fn call(&self, args: &[i64]) -> Result<i64> {
+            Ok(args[0] * 2)
+        }
+    }
+
+    #[test]
+    fn scoped_context_test() {
+        let mut global: DefaultContext<i64> = DefaultContext::new_checked();
+        global.set_variable("x", 1);
+        global.set_variable("y", 2);
+
+        let mut scope = ScopedContext::new(&global);
+        scope.set_variable("x", 10);
+        scope.add_constant("TEN", 10);
+        scope.add_function(Double);
+
+        assert_eq!(Ok(28), eval(&scope, "double(x + y) + TEN - Abs(-2) * 3"));
+        assert_eq!(Some(&10), scope.get_variable("X"));
+        assert_eq!(1, scope.local_variables().len());
+
+        // The parent is not modified
+        assert_eq!(Some(&1), global.get_variable("x"));
+        assert!(!global.is_function("double"));
+        assert!(!global.is_constant("TEN"));
+    }
+
+    #[test]
+    fn nested_scoped_context_test() {
+        let global: DefaultContext<i64> = DefaultContext::new_checked();
+        let mut outer = ScopedContext::new(&global);
+        outer.set_variable("a", 3);
+
+        let mut inner = ScopedContext::new(&outer);
+        inner.set_variable("b", 4);
+        assert_eq!(Ok(25), eval(&inner, "a * a + b * b"));
+        assert!(eval(&outer, "b").is_err());
+    }
+
+    #[test]
+    fn scoped_context_remove_test() {
+        let mut global: DefaultContext<i64> = DefaultContext::new_checked();
+        global.set_variable("x", 1);
+        global.set_variable("y", 2);
+
+        let mut scope = ScopedContext::new(&global);
+        scope.set_variable("x", 10);
+        scope.set_variable("z", 3);
+
+        let mut names = scope.variable_names().collect::<Vec<&str>>();
+        names.sort();
+        assert_eq!(vec!["x", "y", "z"], names);
+
+        // Only the local variables are removed
+        assert_eq!(Some(10), scope.remove_variable("x"));
+        assert_eq!(Some(&1), scope.get_variable("x"));
+        assert_eq!(None, scope.remove_variable("y"));
+
+        scope.clear_variables();
+        assert!(!scope.is_variable("z"));
+        assert_eq!(2, scope.variable_names().count());
+    }
+
+    #[test]
+    #[should_panic]
+    fn scoped_context_constant_conflict_test() {
+        let global: DefaultContext<i64> = DefaultContext::new_checked();
+        let mut scope = ScopedContext::new(&global);
+        scope.set_variable("PI", 3);
+    }
+
+    #[test]
+    fn scoped_context_invalid_name_test() {
+        use crate::ops::unchecked::AddOperator;
+
+        let global: DefaultContext<i64> = DefaultContext::new_checked();
+        let mut scope = ScopedContext::new(&global);
+
+        assert_eq!(ErrorKind::InvalidName, scope.try_add_binary_function(Named("1x")).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidName, scope.try_add_binary_function(Named("+x")).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidName, scope.try_set_variable("x y", 1).unwrap_err().kind());
+        assert_eq!(ErrorKind::NameConflict, scope.try_set_variable("PI", 1).unwrap_err().kind());
+
+        assert!(scope.try_add_binary_function(AddOperator).is_ok());
+        assert_eq!(ErrorKind::DuplicateName, scope.try_add_binary_function(AddOperator).unwrap_err().kind());
+    }
+
+    struct Named(&'static str);
+    impl BinaryFunction<i64> for Named {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn precedence(&self) -> Precedence {
+            Precedence::LOW
+        }
+
+        fn associativity(&self) -> Associativity {
+            Associativity::Left
+        }
+
+        fn call(&self, left: i64, right: i64) -> Result<i64> {
+            Ok(left + right)
+        }
+    }
+}
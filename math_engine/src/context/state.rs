@@ -0,0 +1,329 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::context::{Config, DefaultContext};
+use crate::error::{Error, ErrorKind};
+use crate::utils::ignore_case_string::IgnoreCaseString;
+use crate::Result;
+
+/// The state of a `DefaultContext` which can be saved and restored.
+///
+/// The values are stored as text to keep its precision, the built-in functions and operators
+/// are stored by name and the functions defined by the user as its source,
+/// eg: `double(x) = x * 2`, see `Function::source`.
+///
+/// With the `serde` feature the state can be written to formats as `JSON` or `TOML`.
+///
+/// # Example
+/// ```
+/// use math_engine::context::{DefaultContext, Context};
+///
+/// let mut context : DefaultContext<f64> = DefaultContext::new_unchecked();
+/// context.set_variable("x", 10.5);
+///
+/// let state = context.save_state();
+/// let builtins = DefaultContext::new_unchecked();
+/// let restored = DefaultContext::load_state(&state, &builtins).unwrap();
+/// assert_eq!(Some(&10.5), restored.get_variable("x"));
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ContextState {
+    /// The source of the functions defined by the user.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub user_functions: Vec<String>,
+    /// The names of the built-in functions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub functions: Vec<String>,
+    /// The names of the built-in unary operators.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub unary_functions: Vec<String>,
+    /// The names of the built-in binary operators.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub binary_functions: Vec<String>,
+    /// The configuration of the context.
+    pub config: Config,
+    /// The variables and its values.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub variables: BTreeMap<String, String>,
+    /// The constants and its values.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub constants: BTreeMap<String, String>,
+}
+
+impl<'a, N> DefaultContext<'a, N> {
+    /// Gets the current state of this context.
+    pub fn save_state(&self) -> ContextState
+    where
+        N: Display,
+    {
+        let mut state = ContextState {
+            config: self.config.clone(),
+            variables: to_text_map(&self.variables),
+            constants: to_text_map(&self.constants),
+            ..Default::default()
+        };
+
+        let mut user_functions = Vec::new();
+        for (name, func) in &self.functions {
+            match func.source() {
+                Some(source) => user_functions.push((name.to_string(), source)),
+                None => state.functions.push(name.to_string()),
+            }
+        }
+
+        state.unary_functions = self.unary_functions.keys().map(|s| s.to_string()).collect();
+        state.binary_functions = self.binary_functions.keys().map(|s| s.to_string()).collect();
+
+        // Sorted for a predictable output, the user functions are sorted so each one
+        // is after the functions it calls, to be defined in that order when loaded
+        state.user_functions = sort_by_dependencies(user_functions);
+        state.functions.sort();
+        state.unary_functions.sort();
+        state.binary_functions.sort();
+        state
+    }
+
+    /// Constructs a `DefaultContext` from the given state, the functions and operators
+    /// are taken by name from `builtins`.
+    ///
+    /// # Remarks
+    /// The functions defined by the user are not added, those are in `ContextState::user_functions`
+    /// and should be parsed by the caller.
+    pub fn load_state(state: &ContextState, builtins: &DefaultContext<'a, N>) -> Result<Self>
+    where
+        N: FromStr,
+    {
+        let mut context = DefaultContext::empty_with_config(state.config.clone());
+
+        for name in &state.functions {
+            let key = IgnoreCaseString::from(name.as_str());
+            let func = builtins.functions.get(&key).ok_or_else(|| Error::not_found("Function", name))?;
+            context.functions.insert(key, func.clone());
+        }
+
+        for name in &state.unary_functions {
+            let key = IgnoreCaseString::from(name.as_str());
+            let func = builtins.unary_functions.get(&key).ok_or_else(|| Error::not_found("Unary operator", name))?;
+            context.unary_functions.insert(key, func.clone());
+        }
+
+        for name in &state.binary_functions {
+            let key = IgnoreCaseString::from(name.as_str());
+            let func = builtins.binary_functions.get(&key).ok_or_else(|| Error::not_found("Binary operator", name))?;
+            context.binary_functions.insert(key, func.clone());
+        }
+
+        for (name, value) in &state.constants {
+            context.try_add_constant(name, parse_value(value)?)?;
+        }
+
+        for (name, value) in &state.variables {
+            context.try_set_variable(name, parse_value(value)?)?;
+        }
+
+        Ok(context)
+    }
+}
+
+/// Sorts the sources of the given `(name, source)` functions alphabetically
+/// but placing each function after the functions it calls.
+fn sort_by_dependencies(mut functions: Vec<(String, String)>) -> Vec<String> {
+    fn visit(
+        index: usize,
+        functions: &[(String, String)],
+        visited: &mut [bool],
+        sorted: &mut Vec<String>,
+    ) {
+        if visited[index] {
+            return;
+        }
+
+        // Marked before visiting the dependencies to stop on recursive functions
+        visited[index] = true;
+
+        let (name, source) = &functions[index];
+        let words = source
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .filter(|word| !word.is_empty() && !word.eq_ignore_ascii_case(name));
+
+        for word in words {
+            let dependency = functions.iter().position(|(n, _)| n.eq_ignore_ascii_case(word));
+            if let Some(dependency) = dependency {
+                visit(dependency, functions, visited, sorted);
+            }
+        }
+
+        sorted.push(source.clone());
+    }
+
+    functions.sort_by(|a, b| a.1.cmp(&b.1));
+
+    let mut visited = vec![false; functions.len()];
+    let mut sorted = Vec::with_capacity(functions.len());
+    for index in 0..functions.len() {
+        visit(index, &functions, &mut visited, &mut sorted);
+    }
+
+    sorted
+}
+
+fn to_text_map<N: Display>(values: &HashMap<IgnoreCaseString, N>) -> BTreeMap<String, String> {
+    values
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect()
+}
+
+fn parse_value<N: FromStr>(value: &str) -> Result<N> {
+    N::from_str(value).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "failed to parse `{}` to `{}`.",
+                value,
+                std::any::type_name::<N>()
+            ),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::Context;
+    use crate::function::Function;
+
+    struct Double;
+    impl Function<i64> for Double {
+        fn name(&self) -> &str {
+            "double"
+        }
+
+        fn call(&self, args: &[i64]) -> Result<i64> {
+            Ok(args[0] * 2)
+        }
+
+        fn source(&self) -> Option<String> {
+            Some("double(x) = x * 2".to_string())
+        }
+    }
+
+    #[test]
+    fn save_state_test() {
+        let mut context = DefaultContext::empty();
+        context.add_function(crate::ops::math::MaxFunction);
+        context.add_function(Double);
+        context.add_binary_function(crate::ops::checked::AddOperator);
+        context.add_unary_function(crate::ops::checked::UnaryMinus);
+        context.add_constant("ONE", 1_i64);
+        context.set_variable("x", 10);
+
+        let state = context.save_state();
+        assert_eq!(vec!["double(x) = x * 2".to_string()], state.user_functions);
+        assert_eq!(vec!["max".to_string()], state.functions);
+        assert_eq!(vec!["+".to_string()], state.binary_functions);
+        assert_eq!(vec!["-".to_string()], state.unary_functions);
+        assert_eq!(Some(&"1".to_string()), state.constants.get("ONE"));
+        assert_eq!(Some(&"10".to_string()), state.variables.get("x"));
+        assert_eq!(&Config::new(), &state.config);
+    }
+
+    struct UserFunction(&'static str, &'static str);
+    impl Function<i64> for UserFunction {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn call(&self, _: &[i64]) -> Result<i64> {
+            Ok(0)
+        }
+
+        fn source(&self) -> Option<String> {
+            Some(self.1.to_string())
+        }
+    }
+
+    #[test]
+    fn save_state_user_functions_order_test() {
+        let mut context = DefaultContext::empty();
+        context.add_function(UserFunction("volume", "volume(r, h) = h * Area(r)"));
+        context.add_function(UserFunction("area", "area(r) = 3 * sq(r)"));
+        context.add_function(UserFunction("sq", "sq(x) = x * x"));
+        context.add_function(UserFunction("cube", "cube(x) = x * sq(x)"));
+        context.add_function(UserFunction("abs2", "abs2(x) = sq(x)"));
+
+        let state = context.save_state();
+        assert_eq!(
+            vec![
+                "sq(x) = x * x".to_string(),
+                "abs2(x) = sq(x)".to_string(),
+                "area(r) = 3 * sq(r)".to_string(),
+                "cube(x) = x * sq(x)".to_string(),
+                "volume(r, h) = h * Area(r)".to_string(),
+            ],
+            state.user_functions
+        );
+    }
+
+    #[test]
+    fn load_state_test() {
+        let mut context: DefaultContext<i64> = DefaultContext::new_checked();
+        context.add_function_as(crate::ops::math::MaxFunction, "maximum");
+        context.set_variable("x", -5);
+
+        let state = context.save_state();
+        let builtins = {
+            let mut builtins = DefaultContext::new_checked();
+            builtins.add_function_as(crate::ops::math::MaxFunction, "maximum");
+            builtins
+        };
+
+        let restored = DefaultContext::load_state(&state, &builtins).unwrap();
+        assert_eq!(Some(&-5), restored.get_variable("x"));
+        assert_eq!(context.get_constant("PI"), restored.get_constant("PI"));
+        assert!(restored.is_function("maximum"));
+        assert!(restored.is_binary_function("+"));
+        assert!(restored.is_unary_function("!"));
+        assert_eq!(state, restored.save_state());
+
+        // `maximum` is not in the built-in functions
+        assert!(DefaultContext::<i64>::load_state(&state, &DefaultContext::new_checked()).is_err());
+    }
+
+    #[test]
+    fn load_invalid_state_test() {
+        let mut state = ContextState::default();
+        state.variables.insert("x".to_string(), "abc".to_string());
+        assert!(DefaultContext::<i64>::load_state(&state, &DefaultContext::empty()).is_err());
+
+        let mut state = ContextState::default();
+        state.constants.insert("x".to_string(), "1".to_string());
+        state.variables.insert("x".to_string(), "2".to_string());
+        let error = DefaultContext::<i64>::load_state(&state, &DefaultContext::empty()).err();
+        assert_eq!(Some(ErrorKind::NameConflict), error.map(|e| e.kind()));
+
+        let mut state = ContextState::default();
+        state.variables.insert("1x".to_string(), "2".to_string());
+        let error = DefaultContext::<i64>::load_state(&state, &DefaultContext::empty()).err();
+        assert_eq!(Some(ErrorKind::InvalidName), error.map(|e| e.kind()));
+
+        let mut state = ContextState::default();
+        state.constants.insert("1x".to_string(), "2".to_string());
+        let error = DefaultContext::<i64>::load_state(&state, &DefaultContext::empty()).err();
+        assert_eq!(Some(ErrorKind::InvalidName), error.map(|e| e.kind()));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_state_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_unchecked();
+        context.set_variable("x", 0.25);
+
+        let state = context.save_state();
+        let json = serde_json::to_string(&state).unwrap();
+        let deserialized: ContextState = serde_json::from_str(&json).unwrap();
+        assert_eq!(state, deserialized);
+    }
+}
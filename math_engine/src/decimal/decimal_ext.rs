@@ -20,6 +20,9 @@ pub trait DecimalExt {
     fn checked_ln(self) -> Option<Decimal>;
     fn checked_exp(self) -> Option<Decimal>;
     fn checked_factorial(self) -> Option<Decimal>;
+    fn checked_gamma(self) -> Option<Decimal>;
+    fn erf(self) -> Decimal;
+    fn erfc(self) -> Decimal;
     fn sin(self) -> Decimal;
     fn cos(self) -> Decimal;
     fn tan(self) -> Option<Decimal>;
@@ -290,6 +293,52 @@ impl DecimalExt for Decimal {
         Some(result.round_dp(MAX_DECIMAL_PLACES))
     }
 
+    fn checked_gamma(self) -> Option<Decimal> {
+        if self.is_integer() {
+            // Not defined for zero and the negative integers
+            if self <= Decimal::zero() {
+                return None;
+            }
+
+            // Exact for the positive integers: gamma(n) = (n - 1)!
+            let mut result = Decimal::one();
+            let mut n = consts::TWO;
+
+            while n < self {
+                result = result.checked_mul(n)?;
+                n += Decimal::one();
+            }
+
+            return Some(result);
+        }
+
+        gamma(self)
+    }
+
+    fn erf(self) -> Decimal {
+        if self.is_sign_negative() {
+            return -Decimal::erf(-self);
+        }
+
+        if self < consts::THREE {
+            erf_series(self)
+        } else {
+            Decimal::one() - erfc_continued_fraction(self)
+        }
+    }
+
+    fn erfc(self) -> Decimal {
+        if self.is_sign_negative() {
+            return consts::TWO - Decimal::erfc(-self);
+        }
+
+        if self < consts::THREE {
+            Decimal::one() - erf_series(self)
+        } else {
+            erfc_continued_fraction(self)
+        }
+    }
+
     fn sin(self) -> Decimal {
         let radians: Decimal = self % consts::PI_2;
 
@@ -544,6 +593,42 @@ impl Approx for Decimal {
     }
 }
 
+/// Computes `erf(x)` for `x` in `[0, 3)` using the series
+/// `erf(x) = 2/✓π e^(-x²) Σ 2^n x^(2n + 1) / (1·3·5···(2n + 1))`, which has only positive terms.
+fn erf_series(x: Decimal) -> Decimal {
+    let x2 = x * x;
+    let mut term = x;
+    let mut sum = x;
+    let mut n = Decimal::one();
+
+    while !term.is_zero() {
+        term = term * consts::TWO * x2 / (consts::TWO * n + Decimal::one());
+        sum += term;
+        n += Decimal::one();
+    }
+
+    // `x` is small so `e^(-x²)` can't overflow
+    let exp = Decimal::checked_exp(-x2).unwrap();
+    consts::TWO / consts::SQRT_PI * exp * sum
+}
+
+/// Computes `erfc(x)` for `x >= 3` using the continued fraction
+/// `erfc(x) = e^(-x²) / ✓π / (x + (1/2) / (x + 1 / (x + (3/2) / (x + ...))))`.
+fn erfc_continued_fraction(x: Decimal) -> Decimal {
+    // erfc(9) ≈ 4e-37, which is smaller than any `Decimal`
+    if x > dec!(9) {
+        return Decimal::zero();
+    }
+
+    let mut fraction = x;
+    for k in (1..=consts::TAYLOR_SERIES_ITERATIONS).rev() {
+        fraction = x + Decimal::from(k) / consts::TWO / fraction;
+    }
+
+    let exp = Decimal::checked_exp(-x * x).unwrap();
+    exp / (consts::SQRT_PI * fraction)
+}
+
 fn gamma(mut x: Decimal) -> Option<Decimal> {
     //Using Coefficients from: https://mrob.com/pub/ries/lanczos-gamma.html
     const G: Decimal = dec!(4.7421875);
@@ -569,7 +654,9 @@ fn gamma(mut x: Decimal) -> Option<Decimal> {
     // ~10^-13 precision
     // See: https://en.wikipedia.org/wiki/Lanczos_approximation
     if x < consts::HALF {
-        Some(consts::PI / Decimal::sin(consts::PI * x) * gamma(Decimal::one() - x)?)
+        // Reflection formula: gamma(x) = π / (sin(πx) gamma(1 - x))
+        let sin = Decimal::sin(consts::PI * x);
+        consts::PI.checked_div(sin.checked_mul(gamma(Decimal::one() - x)?)?)
     } else {
         // Lanczos solve gamma for (x + 1)
         x -= Decimal::one();
@@ -739,6 +826,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn checked_gamma_test() {
+        assert_eq!(Decimal::checked_gamma(dec!(1)), Some(dec!(1)));
+        assert_eq!(Decimal::checked_gamma(dec!(11)), Some(dec!(3628800)));
+        assert_eq!(Decimal::checked_gamma(dec!(0)), None);
+        assert_eq!(Decimal::checked_gamma(dec!(-3)), None);
+
+        assert_almost_eq_by(
+            Decimal::checked_gamma(dec!(0.5)).unwrap(),
+            dec!(1.7724538509055160272981674833),
+            12,
+        );
+        assert_almost_eq_by(
+            Decimal::checked_gamma(dec!(5.5)).unwrap(),
+            dec!(52.342777784553520181149008492418),
+            10,
+        );
+        assert_almost_eq_by(
+            Decimal::checked_gamma(dec!(-1.5)).unwrap(),
+            dec!(2.3632718012073547030642233111215),
+            12,
+        );
+    }
+
+    #[test]
+    fn erf_test() {
+        assert_eq!(Decimal::erf(dec!(0)), dec!(0));
+        assert_almost_eq(
+            Decimal::erf(dec!(0.001)),
+            dec!(0.0011283787909692363799484776569),
+        );
+        assert_almost_eq(
+            Decimal::erf(dec!(0.5)),
+            dec!(0.52049987781304653768274665389196),
+        );
+        assert_almost_eq(
+            Decimal::erf(dec!(1)),
+            dec!(0.84270079294971486934122063508261),
+        );
+        assert_almost_eq(
+            Decimal::erf(dec!(-1.5)),
+            dec!(-0.96610514647531072706697626164595),
+        );
+        assert_almost_eq(
+            Decimal::erf(dec!(2.5)),
+            dec!(0.99959304798255504106043578426003),
+        );
+        assert_almost_eq(
+            Decimal::erf(dec!(4.5)),
+            dec!(0.99999999980338395584571125237208),
+        );
+        assert_almost_eq(Decimal::erf(dec!(10)), dec!(1));
+    }
+
+    #[test]
+    fn erfc_test() {
+        assert_almost_eq(
+            Decimal::erfc(dec!(1)),
+            dec!(0.15729920705028513065877936491739),
+        );
+        assert_almost_eq(
+            Decimal::erfc(dec!(-2)),
+            dec!(1.9953222650189527341620692563673),
+        );
+        assert_almost_eq_by(
+            Decimal::erfc(dec!(3)),
+            dec!(0.00002209049699858544137277612958232),
+            25,
+        );
+        assert_almost_eq_by(
+            Decimal::erfc(dec!(5)),
+            dec!(0.000000000001537459794428034850188343485),
+            25,
+        );
+        assert_eq!(Decimal::erfc(dec!(20)), dec!(0));
+    }
+
     #[test]
     fn sin_test() {
         assert_almost_eq(Decimal::sin(dec!(180).to_radians()), Decimal::zero());
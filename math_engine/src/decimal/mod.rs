@@ -46,6 +46,8 @@ pub mod consts {
     pub const LN_10_INV: Decimal = dec!(0.4342944819032518276511289189);
     /// ✓2
     pub const SQRT_2: Decimal = dec!(1.4142135623730950488016887242097);
+    /// ✓π
+    pub const SQRT_PI: Decimal = dec!(1.7724538509055160272981674833);
     /// 0.5
     pub const HALF: Decimal = dec!(0.5);
     /// 1/3
@@ -65,15 +67,18 @@ pub mod consts {
 }
 
 pub mod ops {
-    use num_traits::FromPrimitive;
+    use num_traits::{FromPrimitive, ToPrimitive, Zero};
     use rust_decimal::Decimal;
 
+    use crate::context::AngleUnit;
     use crate::decimal::consts;
     use crate::decimal::decimal_ext::DecimalExt;
     use crate::error::*;
     use crate::function::{
         Associativity, BinaryFunction, Function, Notation, Precedence, UnaryFunction,
     };
+    use crate::ops::math::{angle_functions, sorted_args, WithAngleUnit};
+    use crate::utils::approx::Approx;
     use crate::Result;
 
     pub struct AddOperator;
@@ -327,6 +332,193 @@ pub mod ops {
         }
     }
 
+    pub struct MedianFunction;
+    impl Function<Decimal> for MedianFunction {
+        fn name(&self) -> &str {
+            "median"
+        }
+
+        fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+            let sorted = sorted_args(args)?;
+            let mid = sorted.len() / 2;
+
+            if sorted.len() % 2 == 1 {
+                Ok(sorted[mid])
+            } else {
+                let (a, b) = (sorted[mid - 1], sorted[mid]);
+                b.checked_sub(a)
+                    .map(|n| n / Decimal::from(2))
+                    .and_then(|n| a.checked_add(n))
+                    .ok_or(Error::from(ErrorKind::Overflow))
+            }
+        }
+    }
+
+    /// Gets the percentile `p` in `[0, 100]` of the values: `percentile(p, ...)`,
+    /// interpolating linearly between the closest ranks.
+    pub struct PercentileFunction;
+    impl Function<Decimal> for PercentileFunction {
+        fn name(&self) -> &str {
+            "percentile"
+        }
+
+        fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+            if args.len() < 2 {
+                return Err(Error::from(ErrorKind::InvalidArgumentCount));
+            }
+
+            let p = args[0];
+            if p < Decimal::zero() || p > Decimal::from(100) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("`percentile` expects a value in [0, 100] but was {}", p),
+                ));
+            }
+
+            let sorted = sorted_args(&args[1..])?;
+            let rank = Decimal::from_usize(sorted.len() - 1).unwrap() * p / Decimal::from(100);
+            let index = rank.floor();
+            let fraction = rank - index;
+            let index = index.to_usize().unwrap();
+
+            if fraction.is_zero() {
+                return Ok(sorted[index]);
+            }
+
+            let (a, b) = (sorted[index], sorted[index + 1]);
+            b.checked_sub(a)
+                .and_then(|n| n.checked_mul(fraction))
+                .and_then(|n| a.checked_add(n))
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Gets the difference between the max and min values.
+    pub struct RangeFunction;
+    impl Function<Decimal> for RangeFunction {
+        fn name(&self) -> &str {
+            "range"
+        }
+
+        fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+            let sorted = sorted_args(args)?;
+            sorted[sorted.len() - 1]
+                .checked_sub(sorted[0])
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    macro_rules! impl_variance {
+        ($struct_name:ident, $name:expr, $ddof:expr, $sqrt:expr) => {
+            pub struct $struct_name;
+            impl Function<Decimal> for $struct_name {
+                #[inline]
+                fn name(&self) -> &str {
+                    $name
+                }
+
+                #[inline]
+                fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+                    let variance = welford_variance(args, $ddof)?;
+                    if $sqrt {
+                        variance
+                            .checked_sqrt()
+                            .ok_or(Error::from(ErrorKind::Overflow))
+                    } else {
+                        Ok(variance)
+                    }
+                }
+            }
+        };
+    }
+
+    impl_variance!(VarianceFunction, "variance", 1, false);
+    impl_variance!(PopVarianceFunction, "pvariance", 0, false);
+    impl_variance!(StdDevFunction, "stddev", 1, true);
+    impl_variance!(PopStdDevFunction, "pstddev", 0, true);
+
+    /// Gets the geometric mean of positive values.
+    pub struct GeoMeanFunction;
+    impl Function<Decimal> for GeoMeanFunction {
+        fn name(&self) -> &str {
+            "geomean"
+        }
+
+        fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+            check_positive(args, "geomean")?;
+
+            let mut sum = Decimal::zero();
+            for n in args {
+                let ln = n.checked_ln().ok_or(Error::from(ErrorKind::Overflow))?;
+                sum = sum.checked_add(ln).ok_or(Error::from(ErrorKind::Overflow))?;
+            }
+
+            (sum / Decimal::from_usize(args.len()).unwrap())
+                .checked_exp()
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Gets the harmonic mean of positive values.
+    pub struct HarMeanFunction;
+    impl Function<Decimal> for HarMeanFunction {
+        fn name(&self) -> &str {
+            "harmean"
+        }
+
+        fn call(&self, args: &[Decimal]) -> Result<Decimal> {
+            check_positive(args, "harmean")?;
+
+            let mut sum = Decimal::zero();
+            for n in args {
+                let inv = n.checked_inv().ok_or(Error::from(ErrorKind::Overflow))?;
+                sum = sum.checked_add(inv).ok_or(Error::from(ErrorKind::Overflow))?;
+            }
+
+            Decimal::from_usize(args.len())
+                .unwrap()
+                .checked_div(sum)
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Computes the variance of the values using the Welford's algorithm,
+    /// `ddof` is subtracted from the number of values in the divisor.
+    fn welford_variance(args: &[Decimal], ddof: usize) -> Result<Decimal> {
+        if args.len() <= ddof {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let mut mean = Decimal::zero();
+        let mut m2 = Decimal::zero();
+
+        for (i, n) in args.iter().enumerate() {
+            let delta = n.checked_sub(mean).ok_or(Error::from(ErrorKind::Overflow))?;
+            mean += delta / Decimal::from_usize(i + 1).unwrap();
+            m2 = (n - mean)
+                .checked_mul(delta)
+                .and_then(|d| m2.checked_add(d))
+                .ok_or(Error::from(ErrorKind::Overflow))?;
+        }
+
+        Ok(m2 / Decimal::from_usize(args.len() - ddof).unwrap())
+    }
+
+    fn check_positive(args: &[Decimal], name: &str) -> Result<()> {
+        if args.is_empty() {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        if args.iter().any(|n| *n <= Decimal::zero()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{}` expects positive values", name),
+            ));
+        }
+
+        Ok(())
+    }
+
     macro_rules! forward_checked_func_impl {
         ($struct_name:ident, $method_name:ident, $name:ident) => {
             impl Function<Decimal> for $struct_name {
@@ -468,6 +660,15 @@ pub mod ops {
         }
     }
 
+    pub struct GammaFunction;
+    forward_checked_func_impl!(GammaFunction, checked_gamma, gamma);
+
+    pub struct ErfFunction;
+    forward_func_impl!(ErfFunction, erf);
+
+    pub struct ErfcFunction;
+    forward_func_impl!(ErfcFunction, erfc);
+
     pub struct ToRadiansFunction;
     forward_func_impl!(ToRadiansFunction, to_radians, toRadians);
 
@@ -475,85 +676,42 @@ pub mod ops {
     forward_func_impl!(ToDegreesFunction, to_degrees, toDegrees);
 
     //////////////////// Trigonometric ////////////////////
-    macro_rules! impl_checked_trig {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
-                #[inline]
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
-
-                #[inline]
-                fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => {
-                            args[0].to_radians()
-                                .$method_name()
-                                .ok_or(Error::from(ErrorKind::Overflow))
-                        },
-                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
-                    }
-                }
-            }
-        };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_checked_trig!($struct_name, $method_name, $method_name);
-        };
+    angle_functions!(
+        AngleUnit::Degrees;
+        SinFunction, CosFunction, TanFunction, CscFunction, SecFunction, CotFunction,
+        ASinFunction, ACosFunction, ATanFunction, ACscFunction, ASecFunction, ACotFunction,
+        DegreeOperator,
+    );
+
+    /// Gets the number of radians of one unit of the given `AngleUnit`.
+    #[inline]
+    fn radians_per_unit(unit: AngleUnit) -> Decimal {
+        match unit {
+            AngleUnit::Radians => consts::ONE,
+            AngleUnit::Degrees => consts::PI / Decimal::from(180),
+            AngleUnit::Gradians => consts::PI / Decimal::from(200),
+        }
     }
 
-    macro_rules! impl_checked_trig_rec {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
-                #[inline]
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
-
-                #[inline]
-                fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => args[0].to_radians()
-                            .$method_name()
-                            //.map(Decimal::checked_inv)
-                            .ok_or(Error::from(ErrorKind::Overflow)),
-                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
-                    }
-                }
-            }
-        };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_checked_trig_rec!($struct_name, $method_name, $method_name);
-        };
+    #[inline]
+    fn to_radians(unit: AngleUnit, value: Decimal) -> Option<Decimal> {
+        match unit {
+            AngleUnit::Radians => Some(value),
+            _ => value.checked_mul(radians_per_unit(unit)).map(|n| n.approx()),
+        }
     }
 
-    macro_rules! impl_trig {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
-                #[inline]
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
-
-                #[inline]
-                fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => Ok(args[0].to_radians().$method_name()),
-                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
-                    }
-                }
-            }
-        };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_trig!($struct_name, $method_name, $method_name);
-        };
+    #[inline]
+    fn from_radians(unit: AngleUnit, value: Decimal) -> Option<Decimal> {
+        match unit {
+            AngleUnit::Radians => Some(value),
+            _ => value.checked_div(radians_per_unit(unit)).map(|n| n.approx()),
+        }
     }
 
-    macro_rules! impl_trig_rec {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
+    macro_rules! impl_trig {
+        ($struct_name:ident, $name:ident, |$n:ident| $body:expr) => {
+            impl Function<Decimal> for WithAngleUnit<$struct_name> {
                 #[inline]
                 fn name(&self) -> &str {
                     stringify!($name)
@@ -561,64 +719,53 @@ pub mod ops {
 
                 #[inline]
                 fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => Ok(args[0].to_radians().$method_name().inv()),
+                    match args {
+                        [x] => to_radians(self.unit, *x)
+                            .and_then(|$n| $body)
+                            .ok_or(Error::from(ErrorKind::Overflow)),
                         _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
                     }
                 }
             }
         };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_trig_rec!($struct_name, $method_name, $method_name);
-        };
     }
 
-    pub struct SinFunction;
-    impl_trig!(SinFunction, sin);
+    impl_trig!(SinFunction, sin, |n| Some(n.sin()));
+    impl_trig!(CosFunction, cos, |n| Some(n.cos()));
+    impl_trig!(TanFunction, tan, |n| n.tan());
+    impl_trig!(CscFunction, csc, |n| n.sin().checked_inv());
+    impl_trig!(SecFunction, sec, |n| n.cos().checked_inv());
+    impl_trig!(CotFunction, cot, |n| n.tan());
 
-    pub struct CosFunction;
-    impl_trig!(CosFunction, cos);
-
-    pub struct TanFunction;
-    impl_checked_trig!(TanFunction, tan);
-
-    pub struct CscFunction;
-    impl_trig_rec!(CscFunction, sin, csc);
-
-    pub struct SecFunction;
-    impl_trig_rec!(SecFunction, cos, sec);
-
-    pub struct CotFunction;
-    impl_checked_trig_rec!(CotFunction, tan, cot);
+    /// Converts an angle in degrees to the `AngleUnit` of the context: `90°`.
+    impl UnaryFunction<Decimal> for WithAngleUnit<DegreeOperator> {
+        #[inline]
+        fn name(&self) -> &str {
+            "°"
+        }
 
-    //////////////////// Inverse Trigonometric ////////////////////
-    macro_rules! impl_arc_trig_rec {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
-                #[inline]
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
+        #[inline]
+        fn notation(&self) -> Notation {
+            Notation::Postfix
+        }
 
-                #[inline]
-                fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => Ok(args[0].$method_name()),
-                        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
-                    }
-                }
+        #[inline]
+        fn call(&self, value: Decimal) -> Result<Decimal> {
+            match self.unit {
+                AngleUnit::Degrees => Some(value),
+                AngleUnit::Radians => to_radians(AngleUnit::Degrees, value),
+                AngleUnit::Gradians => value
+                    .checked_mul(Decimal::from(10))
+                    .and_then(|n| n.checked_div(Decimal::from(9))),
             }
-        };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_arc_trig_rec!($struct_name, $method_name, $method_name);
-        };
+            .ok_or(Error::from(ErrorKind::Overflow))
+        }
     }
 
-    macro_rules! impl_checked_arc_trig_rec {
-        ($struct_name:ident, $method_name:ident, $name:ident) => {
-            impl Function<Decimal> for $struct_name {
+    //////////////////// Inverse Trigonometric ////////////////////
+    macro_rules! impl_arc_trig {
+        ($struct_name:ident, $name:ident, |$n:ident| $body:expr) => {
+            impl Function<Decimal> for WithAngleUnit<$struct_name> {
                 #[inline]
                 fn name(&self) -> &str {
                     stringify!($name)
@@ -626,30 +773,24 @@ pub mod ops {
 
                 #[inline]
                 fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-                    match args.len() {
-                        1 => args[0]
-                            .inv()
-                            .$method_name()
+                    match args {
+                        [$n] => $body
+                            .and_then(|n| from_radians(self.unit, n))
                             .ok_or(Error::from(ErrorKind::Overflow)),
                         _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
                     }
                 }
             }
         };
-
-        ($struct_name:ident, $method_name:ident) => {
-            impl_checked_arc_trig_rec!($struct_name, $method_name, $method_name);
-        };
     }
 
-    pub struct ASinFunction;
-    impl_checked_trig!(ASinFunction, asin);
-
-    pub struct ACosFunction;
-    impl_checked_trig!(ACosFunction, acos);
+    impl_arc_trig!(ASinFunction, asin, |n| n.asin());
+    impl_arc_trig!(ACosFunction, acos, |n| n.acos());
+    impl_arc_trig!(ACscFunction, acsc, |n| n.checked_inv().and_then(DecimalExt::asin));
+    impl_arc_trig!(ASecFunction, asec, |n| n.checked_inv().and_then(DecimalExt::acos));
+    impl_arc_trig!(ACotFunction, acot, |n| Some(n.atan()));
 
-    pub struct ATanFunction;
-    impl Function<Decimal> for ATanFunction {
+    impl Function<Decimal> for WithAngleUnit<ATanFunction> {
         #[inline]
         fn name(&self) -> &str {
             stringify!(atan)
@@ -657,23 +798,16 @@ pub mod ops {
 
         #[inline]
         fn call(&self, args: &[Decimal]) -> Result<Decimal> {
-            match args.len() {
-                1 => Ok(args[0].atan()),
-                2 => Ok(args[0].atan2(args[1])),
-                _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
-            }
+            let radians = match args {
+                [x] => x.atan(),
+                [y, x] => y.atan2(*x),
+                _ => return Err(Error::from(ErrorKind::InvalidArgumentCount)),
+            };
+
+            from_radians(self.unit, radians).ok_or(Error::from(ErrorKind::Overflow))
         }
     }
 
-    pub struct ACscFunction;
-    impl_checked_arc_trig_rec!(ACscFunction, asin, acsc);
-
-    pub struct ASecFunction;
-    impl_checked_arc_trig_rec!(ASecFunction, acos, asec);
-
-    pub struct ACotFunction;
-    impl_arc_trig_rec!(ACotFunction, atan, acot);
-
     //////////////////// Hyperbolic Trigonometric ////////////////////
     pub struct SinhFunction;
     forward_checked_func_impl!(SinhFunction, sinh);
@@ -713,11 +847,49 @@ pub mod ops {
     forward_checked_func_inv_impl!(ACothFunction, atanh, acoth);
 }
 
+impl crate::ops::integer::IntegerOps<Decimal> for crate::ops::integer::Checked {
+    #[inline]
+    fn add(a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_add(*b)
+    }
+
+    #[inline]
+    fn sub(a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_sub(*b)
+    }
+
+    #[inline]
+    fn mul(a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_mul(*b)
+    }
+
+    #[inline]
+    fn div(a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_div(*b)
+    }
+
+    #[inline]
+    fn rem(a: &Decimal, b: &Decimal) -> Option<Decimal> {
+        a.checked_rem(*b)
+    }
+}
+
 pub mod context {
-    use crate::context::{Config, Context, DefaultContext};
+    use crate::context::builder::{add_distribution_functions, ContextBuilder, Pack};
+    use crate::context::{AngleUnit, Config, Context, DefaultContext};
     use crate::decimal::consts;
     use crate::decimal::ops::*;
-    use crate::ops::math::{MaxFunction, MinFunction, RandFunction, UnaryPlus};
+    use crate::ops::checked::{
+        ChooseFunction, DivisorCountFunction, FactorCountFunction, FibFunction, GcdFunction,
+        IsPrimeFunction, LcmFunction, MinFactorFunction, ModInvFunction, ModPowFunction,
+        NcrFunction, NextPrimeFunction, NprFunction,
+    };
+    use crate::ops::math::{CountFunction, MaxFunction, MinFunction, ModeFunction, RandFunction, RootFunction, UnaryPlus};
+    use crate::ops::random::*;
+    use crate::ops::special::{
+        BesselJFunction, BesselYFunction, BetaFunction, DigammaFunction, ErfInvFunction,
+        LambertWFunction, LnGammaFunction, ZetaFunction,
+    };
     use rust_decimal::Decimal;
 
     impl<'a> DefaultContext<'a, Decimal> {
@@ -727,59 +899,150 @@ pub mod context {
         }
 
         pub fn new_decimal_with_config(config: Config) -> Self {
-            let mut context = Self::empty_with_config(config);
+            ContextBuilder::new().with_config(config).build_decimal()
+        }
+    }
+
+    impl ContextBuilder {
+        /// Builds a context with `Decimal` functions, see `DefaultContext::new_decimal`.
+        pub fn build_decimal<'a>(self) -> DefaultContext<'a, Decimal> {
+            let mut context = DefaultContext::empty_with_config(self.config().clone());
             context.add_constant("PI", consts::PI);
             context.add_constant("E", consts::E);
-            context.add_binary_function(AddOperator);
-            context.add_binary_function(SubOperator);
-            context.add_binary_function(MulOperator);
-            context.add_binary_function(DivOperator);
-            context.add_binary_function(PowOperator);
-            context.add_binary_function(ModOperator);
-            context.add_unary_function(UnaryPlus);
-            context.add_unary_function(UnaryMinus);
-            context.add_unary_function(Factorial);
-            context.add_function(SumFunction);
-            context.add_function(AvgFunction);
-            context.add_function(ProdFunction);
-            context.add_function(MaxFunction);
-            context.add_function(MinFunction);
-            context.add_function(CbrtFunction);
-            context.add_function(SqrtFunction);
-            context.add_function(LnFunction);
-            context.add_function(LogFunction);
-            context.add_function(RandFunction);
-            context.add_function(CeilFunction);
-            context.add_function(FloorFunction);
-            context.add_function(TruncateFunction);
-            context.add_function(RoundFunction);
-            context.add_function(ExpFunction);
-            context.add_function(ToRadiansFunction);
-            context.add_function(ToDegreesFunction);
-            context.add_function(SinFunction);
-            context.add_function(CosFunction);
-            context.add_function(TanFunction);
-            context.add_function(CscFunction);
-            context.add_function(SecFunction);
-            context.add_function(CotFunction);
-            context.add_function(ASinFunction);
-            context.add_function(ACosFunction);
-            context.add_function(ATanFunction);
-            context.add_function(ACscFunction);
-            context.add_function(ASecFunction);
-            context.add_function(ACotFunction);
-            context.add_function(SinhFunction);
-            context.add_function(CoshFunction);
-            context.add_function(TanhFunction);
-            context.add_function(CschFunction);
-            context.add_function(SechFunction);
-            context.add_function(CothFunction);
-            context.add_function(ASinhFunction);
-            context.add_function(ACoshFunction);
-            context.add_function(ATanhFunction);
-            context.add_function(ACschFunction);
-            context.add_function(ASechFunction);
-            context.add_function(ACothFunction);
+
+            if self.has_pack(Pack::Arithmetic) {
+                context.add_binary_function(AddOperator);
+                context.add_binary_function(SubOperator);
+                context.add_binary_function(MulOperator);
+                context.add_binary_function(DivOperator);
+                context.add_binary_function(PowOperator);
+                context.add_binary_function(ModOperator);
+                context.add_unary_function(UnaryPlus);
+                context.add_unary_function(UnaryMinus);
+                context.add_unary_function(Factorial);
+                context.add_function(CbrtFunction);
+                context.add_function(SqrtFunction);
+                context.add_function(RootFunction);
+            }
+
+            if self.has_pack(Pack::Trigonometry) {
+                let unit = self.config().angle_unit.unwrap_or(AngleUnit::Degrees);
+                context.add_function(ToRadiansFunction);
+                context.add_function(ToDegreesFunction);
+                context.add_function(SinFunction::with_unit(unit));
+                context.add_function(CosFunction::with_unit(unit));
+                context.add_function(TanFunction::with_unit(unit));
+                context.add_function(CscFunction::with_unit(unit));
+                context.add_function(SecFunction::with_unit(unit));
+                context.add_function(CotFunction::with_unit(unit));
+                context.add_function(ASinFunction::with_unit(unit));
+                context.add_function(ACosFunction::with_unit(unit));
+                context.add_function(ATanFunction::with_unit(unit));
+                context.add_function(ACscFunction::with_unit(unit));
+                context.add_function(ASecFunction::with_unit(unit));
+                context.add_function(ACotFunction::with_unit(unit));
+                context.add_unary_function(DegreeOperator::with_unit(unit));
+            }
+
+            if self.has_pack(Pack::Hyperbolic) {
+                context.add_function(SinhFunction);
+                context.add_function(CoshFunction);
+                context.add_function(TanhFunction);
+                context.add_function(CschFunction);
+                context.add_function(SechFunction);
+                context.add_function(CothFunction);
+                context.add_function(ASinhFunction);
+                context.add_function(ACoshFunction);
+                context.add_function(ATanhFunction);
+                context.add_function(ACschFunction);
+                context.add_function(ASechFunction);
+                context.add_function(ACothFunction);
+            }
+
+            if self.has_pack(Pack::Logarithms) {
+                context.add_function(LnFunction);
+                context.add_function(LogFunction);
+                context.add_function(ExpFunction);
+            }
+
+            if self.has_pack(Pack::Rounding) {
+                context.add_function(CeilFunction);
+                context.add_function(FloorFunction);
+                context.add_function(TruncateFunction);
+                context.add_function(RoundFunction);
+            }
+
+            if self.has_pack(Pack::Aggregates) {
+                context.add_function(SumFunction);
+                context.add_function(AvgFunction);
+                context.add_function(ProdFunction);
+                context.add_function(MaxFunction);
+                context.add_function(MinFunction);
+            }
+
+            if self.has_pack(Pack::Statistics) {
+                context.add_function(CountFunction);
+                context.add_function(MedianFunction);
+                context.add_function(ModeFunction);
+                context.add_function(PercentileFunction);
+                context.add_function(RangeFunction);
+                context.add_function(VarianceFunction);
+                context.add_function(PopVarianceFunction);
+                context.add_function(StdDevFunction);
+                context.add_function(PopStdDevFunction);
+                context.add_function(GeoMeanFunction);
+                context.add_function(HarMeanFunction);
+            }
+
+            if self.has_pack(Pack::NumberTheory) {
+                context.add_function(GcdFunction);
+                context.add_function(LcmFunction);
+                context.add_function(NcrFunction);
+                context.add_function(ChooseFunction);
+                context.add_function(NprFunction);
+                context.add_function(IsPrimeFunction);
+                context.add_function(NextPrimeFunction);
+                context.add_function(FactorCountFunction);
+                context.add_function(DivisorCountFunction);
+                context.add_function(MinFactorFunction);
+                context.add_function(FibFunction);
+                context.add_function(ModPowFunction);
+                context.add_function(ModInvFunction);
+            }
+
+            if self.has_pack(Pack::Random) {
+                context.add_function(RandFunction::with_rng(context.rng().clone()));
+            }
+
+            if self.has_pack(Pack::Sampling) {
+                let rng = context.rng().clone();
+                context.add_function(RandIntFunction::with_rng(rng.clone()));
+                context.add_function(RandNormalFunction::with_rng(rng.clone()));
+                context.add_function(RandExpFunction::with_rng(rng.clone()));
+                context.add_function(RandPoissonFunction::with_rng(rng.clone()));
+                context.add_function(RandBinomialFunction::with_rng(rng.clone()));
+                context.add_function(ChoiceFunction::with_rng(rng));
+            }
+
+            if self.has_pack(Pack::Distributions) {
+                add_distribution_functions(&mut context);
+            }
+
+            if self.has_pack(Pack::Special) {
+                // `gamma`, `erf` and `erfc` use `Decimal` precision, the others are computed as `f64`
+                context.add_function(GammaFunction);
+                context.add_function(LnGammaFunction);
+                context.add_function(DigammaFunction);
+                context.add_function(BetaFunction);
+                context.add_function(ErfFunction);
+                context.add_function(ErfcFunction);
+                context.add_function(ErfInvFunction);
+                context.add_function(ZetaFunction);
+                context.add_function(BesselJFunction);
+                context.add_function(BesselYFunction);
+                context.add_function(LambertWFunction);
+            }
+
             context
         }
     }
@@ -26,6 +26,7 @@ pub struct Custom {
 
 /// A list of general errors.
 #[derive(Clone, Copy, Debug, Hash, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorKind {
     /// The value overflow.
     Overflow,
@@ -47,6 +48,22 @@ pub enum ErrorKind {
     InvalidExpression,
     /// The expression is empty.
     Empty,
+    /// The name of a variable, constant, function or operator is invalid.
+    InvalidName,
+    /// The name is already used by other item of the same kind.
+    DuplicateName,
+    /// The name is already used by other item of a different kind, as a variable and a constant.
+    NameConflict,
+    /// The expression is longer than the limit in the `Config`.
+    ExpressionTooLong,
+    /// The expression has more tokens than the limit in the `Config`.
+    TooManyTokens,
+    /// The grouping symbols are nested deeper than the limit in the `Config`.
+    NestingTooDeep,
+    /// The evaluation took more steps than the limit in the `Config`.
+    StepLimitExceeded,
+    /// The nested evaluations, as functions defined by the user, exceeded the limit in the `Config`.
+    RecursionLimitExceeded,
     /// Other type of error.
     Other,
 }
@@ -64,6 +81,14 @@ impl ErrorKind {
             ErrorKind::NegativeValue => "Value is negative",
             ErrorKind::PositiveValue => "Value is positive",
             ErrorKind::Empty => "Empty input",
+            ErrorKind::InvalidName => "Invalid name",
+            ErrorKind::DuplicateName => "Name already exists",
+            ErrorKind::NameConflict => "Name is used by other item",
+            ErrorKind::ExpressionTooLong => "Expression is too long",
+            ErrorKind::TooManyTokens => "Too many tokens",
+            ErrorKind::NestingTooDeep => "Nesting is too deep",
+            ErrorKind::StepLimitExceeded => "Evaluation step limit exceeded",
+            ErrorKind::RecursionLimitExceeded => "Recursion limit exceeded",
             ErrorKind::InvalidExpression => "Invalid expression",
             ErrorKind::Other => "Other error",
         }
@@ -125,6 +150,13 @@ impl Error {
         Self::new(ErrorKind::Other, msg)
     }
 
+    /// Creates an `ErrorKind::InvalidInput` error for a missing item of a `Context`,
+    /// `kind` is the type of item, eg: `Function`.
+    #[inline]
+    pub(crate) fn not_found(kind: &str, name: &str) -> Error {
+        Self::new(ErrorKind::InvalidInput, format!("{} `{}` not found", kind, name))
+    }
+
     /// Gets the `ErrorKind` of this error.
     ///
     /// # Example
@@ -254,4 +286,12 @@ mod tests {
             unreachable!()
         }
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_error_kind_test() {
+        let json = serde_json::to_string(&ErrorKind::DivisionByZero).unwrap();
+        assert_eq!("\"DivisionByZero\"", json);
+        assert_eq!(ErrorKind::DivisionByZero, serde_json::from_str(&json).unwrap());
+    }
 }
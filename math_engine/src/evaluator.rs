@@ -10,6 +10,11 @@ use crate::token::Token::*;
 use crate::tokenizer::{Tokenize, Tokenizer};
 use crate::Result;
 
+pub mod batch;
+pub mod bytecode;
+pub mod closure;
+mod resolve;
+
 /// A trait for evaluate an expression of `Token`.
 pub trait Evaluate<N> {
     /// The result of the evaluation.
@@ -116,6 +121,9 @@ where
     N: Debug + Clone,
     C: Context<'a, N>,
 {
+    let limits = &context.config().limits;
+    // Tracks the nested evaluations, as functions defined by the user.
+    let mut guard = limits::EvalGuard::enter(limits)?;
     // Converts the array of tokens to RPN.
     let rpn = shunting_yard::infix_to_rpn(tokens, context)?;
     // Stores the resulting values
@@ -124,6 +132,15 @@ where
     let mut arg_count: Option<usize> = None;
 
     for token in &rpn {
+        // A function is charged an step for each argument, so calls with many arguments
+        // as `sum(1, 2, 3)` are not counted as a single operation.
+        let steps = match token {
+            Function(_) => 1 + arg_count.unwrap_or(0),
+            _ => 1,
+        };
+
+        guard.step(steps, limits)?;
+
         match token {
             Number(n) => values.push(n.clone()),
             Variable(name) => {
@@ -246,6 +263,103 @@ where
     }
 }
 
+pub(crate) mod limits {
+    use std::cell::Cell;
+
+    use crate::context::Limits;
+    use crate::error::{Error, ErrorKind};
+    use crate::Result;
+
+    thread_local! {
+        /// The depth of the nested evaluations and the steps performed since the outer evaluation started.
+        static EVAL_STATE: Cell<(usize, usize)> = const { Cell::new((0, 0)) };
+        /// The `max_eval_steps` of the outer evaluation, used to charge the work done inside functions.
+        static MAX_EVAL_STEPS: Cell<Option<usize>> = const { Cell::new(None) };
+    }
+
+    /// Tracks an evaluation checking the `max_eval_steps` and `max_recursion_depth` limits,
+    /// the depth is restored when dropped even if a function `panic`s.
+    pub struct EvalGuard;
+
+    impl EvalGuard {
+        /// Enters in a new evaluation, which may be nested in other.
+        pub fn enter(limits: &Limits) -> Result<EvalGuard> {
+            let (depth, steps) = EVAL_STATE.with(|state| state.get());
+            // The outer evaluation don't count as recursion
+            if let Some(max) = limits.max_recursion_depth {
+                if depth > max {
+                    return Err(Error::new(
+                        ErrorKind::RecursionLimitExceeded,
+                        format!("Nested evaluations exceeded the max depth of {}", max),
+                    ));
+                }
+            }
+
+            let steps = if depth == 0 {
+                MAX_EVAL_STEPS.with(|max| max.set(limits.max_eval_steps));
+                0
+            } else {
+                steps
+            };
+
+            EVAL_STATE.with(|state| state.set((depth + 1, steps)));
+            Ok(EvalGuard)
+        }
+
+        /// Enters in a new evaluation and counts all its steps at once,
+        /// used by the compiled expressions where the number of steps is known before evaluate.
+        pub fn enter_with_steps(limits: &Limits, count: usize) -> Result<EvalGuard> {
+            let mut guard = EvalGuard::enter(limits)?;
+            guard.step(count, limits)?;
+            Ok(guard)
+        }
+
+        /// Counts the given number of steps of the evaluation.
+        pub fn step(&mut self, count: usize, limits: &Limits) -> Result<()> {
+            add_steps(count, limits.max_eval_steps)
+        }
+    }
+
+    /// Charges the given number of steps to the current evaluation, used by the functions
+    /// which work grows with its operands, as the factorial.
+    ///
+    /// Does nothing when called outside an evaluation.
+    pub fn charge(count: usize) -> Result<()> {
+        let (depth, _) = EVAL_STATE.with(|state| state.get());
+        if depth == 0 {
+            return Ok(());
+        }
+
+        add_steps(count, MAX_EVAL_STEPS.with(|max| max.get()))
+    }
+
+    fn add_steps(count: usize, max_eval_steps: Option<usize>) -> Result<()> {
+        let steps = EVAL_STATE.with(|state| {
+            let (depth, steps) = state.get();
+            let steps = steps.saturating_add(count);
+            state.set((depth, steps));
+            steps
+        });
+
+        match max_eval_steps {
+            Some(max) if steps > max => Err(Error::new(
+                ErrorKind::StepLimitExceeded,
+                format!("Evaluation exceeded the max of {} steps", max),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    impl Drop for EvalGuard {
+        fn drop(&mut self) {
+            EVAL_STATE.with(|state| {
+                let (depth, steps) = state.get();
+                state.set((depth - 1, steps));
+            });
+        }
+    }
+}
+
 /// Converts the given array of tokens to reverse polish notation.
 ///
 /// # Arguments
@@ -300,6 +414,9 @@ mod shunting_yard {
         let mut operators = Vec::new();
         let mut arg_count: Vec<usize> = Vec::new();
         let mut grouping_count: Vec<usize> = Vec::new();
+        // The current depth of nested grouping symbols.
+        let mut depth: usize = 0;
+        let max_depth = context.config().limits.max_depth;
 
         let mut token_iterator = tokens.iter().enumerate().peekable();
         while let Some((pos, token)) = token_iterator.next() {
@@ -338,13 +455,37 @@ mod shunting_yard {
                     operators.push(token.clone());
                 }
                 Token::GroupingOpen(_) => {
+                    depth += 1;
+                    if let Some(max) = max_depth {
+                        if depth > max {
+                            return Err(Error::new(
+                                ErrorKind::NestingTooDeep,
+                                format!("Grouping symbols are nested deeper than {}", max),
+                            ));
+                        }
+                    }
+
                     operators.push(token.clone());
                     if !arg_count.is_empty() {
                         grouping_count.push(pos);
                     }
                 }
                 Token::GroupingClose(c) => {
-                    push_grouping_close(context, *c, &mut output, &mut operators, &mut arg_count)?;
+                    depth = depth.saturating_sub(1);
+
+                    // Checking for function calls without arguments: eg: `Random()`
+                    let is_empty_call = pos > 1
+                        && tokens[pos - 1].is_grouping_open()
+                        && tokens[pos - 2].is_function();
+
+                    push_grouping_close(
+                        context,
+                        *c,
+                        is_empty_call,
+                        &mut output,
+                        &mut operators,
+                        &mut arg_count,
+                    )?;
 
                     // Checking for empty grouping symbols: eg: `Random(())`, `()+2`
                     if pos > 1 {
@@ -566,6 +707,7 @@ mod shunting_yard {
     fn push_grouping_close<'a, N: Clone + Debug>(
         context: &impl Context<'a, N>,
         group_close: char,
+        is_empty_call: bool,
         output: &mut Vec<Token<N>>,
         operators: &mut Vec<Token<N>>,
         arg_count: &mut Vec<usize>,
@@ -586,8 +728,12 @@ mod shunting_yard {
                             if !arg_count.is_empty() {
                                 if let Some(top) = operators.last() {
                                     if let Token::Function(_) = top {
-                                        let count = arg_count.pop().unwrap() + 1;
-                                        output.push(Token::ArgCount(count));
+                                        let count = arg_count.pop().unwrap();
+                                        if is_empty_call {
+                                            output.push(Token::ArgCount(0));
+                                        } else {
+                                            output.push(Token::ArgCount(count + 1));
+                                        }
                                         output.push(operators.pop().unwrap());
                                     }
                                 }
@@ -923,6 +1069,7 @@ mod tests {
         assert!(evaluator.eval("5 * Sin(40)").is_ok());
         assert!(evaluator.eval("Sin(30) * 5").is_ok());
         assert!(evaluator.eval("Cos(30) * Sin(30)").is_ok());
+        assert!(evaluator.eval("2 + Random()").is_ok());
 
         assert!(evaluator.eval("((20) + 2").is_err());
         assert!(evaluator.eval("(1,23) + 1").is_err());
@@ -1024,4 +1171,143 @@ mod tests {
 
         assert_eq!(evaluator.eval("x + 2").unwrap(), 12);
     }
+
+    #[test]
+    fn eval_angle_group_symbol_test() {
+        use crate::context::builder::{ContextBuilder, Pack};
+
+        // `>>` closes two groups if the shift operators are not defined
+        let config = Config::new()
+            .with_group_symbol('<', '>')
+            .with_custom_function_call(true);
+        let evaluator: Evaluator<i64> =
+            Evaluator::with_context(DefaultContext::new_checked_with_config(config.clone()));
+        assert_eq!(Ok(5), evaluator.eval("Max<1, Sum<2, 3>>"));
+
+        let context = ContextBuilder::new()
+            .with_config(config)
+            .with_pack(Pack::Bitwise)
+            .build_checked_integer::<i64>();
+        let evaluator = Evaluator::with_context(context);
+        assert_eq!(Ok(5), evaluator.eval("Max<1, Sum<2, 3> >"));
+        assert_eq!(Ok(8), evaluator.eval("Max<1, 1 << 3>"));
+    }
+
+    #[test]
+    fn eval_limits_test() {
+        use crate::context::{DefaultContext, Limits};
+
+        fn eval_with(limits: Limits, expression: &str) -> Result<i64> {
+            let config = Config::new().with_limits(limits);
+            let evaluator = Evaluator::with_context(DefaultContext::new_checked_with_config(config));
+            evaluator.eval(expression)
+        }
+
+        fn error_kind(result: Result<i64>) -> ErrorKind {
+            result.unwrap_err().kind()
+        }
+
+        let limits = Limits::new().with_max_expression_len(5);
+        assert_eq!(Ok(3), eval_with(limits, "1 + 2"));
+        assert_eq!(ErrorKind::ExpressionTooLong, error_kind(eval_with(limits, "1 + 23")));
+
+        let limits = Limits::new().with_max_tokens(3);
+        assert_eq!(Ok(3), eval_with(limits, "1 + 2"));
+        assert_eq!(ErrorKind::TooManyTokens, error_kind(eval_with(limits, "1 + 2 + 3")));
+
+        let limits = Limits::new().with_max_depth(2);
+        assert_eq!(Ok(6), eval_with(limits, "((1 + 2)) * (Max(1, 2))"));
+        assert_eq!(ErrorKind::NestingTooDeep, error_kind(eval_with(limits, "(((1)))")));
+        assert_eq!(ErrorKind::NestingTooDeep, error_kind(eval_with(limits, "(Max((1), 2))")));
+
+        let limits = Limits::new().with_max_eval_steps(5);
+        assert_eq!(Ok(6), eval_with(limits, "1 + 2 + 3"));
+        assert_eq!(ErrorKind::StepLimitExceeded, error_kind(eval_with(limits, "1 + 2 + 3 + 4")));
+
+        // 3 numbers, the argument count and the function with its 3 arguments
+        let limits = Limits::new().with_max_eval_steps(8);
+        assert_eq!(Ok(3), eval_with(limits, "Max(1, 2, 3)"));
+        assert_eq!(ErrorKind::StepLimitExceeded, error_kind(eval_with(limits, "Max(1, 2, 3, 4)")));
+    }
+
+    #[test]
+    fn eval_steps_of_operator_test() {
+        use std::str::FromStr;
+        use bigdecimal::BigDecimal;
+        use crate::context::{DefaultContext, Limits};
+
+        // A factorial is charged an step for each multiplication, so `n!` is `n + 2` steps
+        let config = Config::new().with_limits(Limits::new().with_max_eval_steps(12));
+        let evaluator = Evaluator::with_context(DefaultContext::<BigDecimal>::new_unchecked_with_config(config));
+        assert_eq!(Ok(BigDecimal::from(3_628_800)), evaluator.eval("10!"));
+        assert_eq!(ErrorKind::StepLimitExceeded, evaluator.eval("11!").unwrap_err().kind());
+        assert_eq!(ErrorKind::StepLimitExceeded, evaluator.eval("99999!").unwrap_err().kind());
+        assert_eq!(ErrorKind::StepLimitExceeded, evaluator.eval(&format!("{}!", BigDecimal::from_str("1e100").unwrap())).unwrap_err().kind());
+
+        // An integer power is charged an step for each bit of the exponent
+        let config = Config::new().with_limits(Limits::new().with_max_eval_steps(7));
+        let evaluator = Evaluator::with_context(DefaultContext::<i64>::new_checked_with_config(config));
+        assert_eq!(Ok(1 << 15), evaluator.eval("2 ^ 15"));
+        assert_eq!(ErrorKind::StepLimitExceeded, evaluator.eval("2 ^ 16").unwrap_err().kind());
+    }
+
+    #[test]
+    fn eval_recursion_limit_test() {
+        use crate::context::{DefaultContext, Limits};
+        use crate::function::Function;
+
+        /// Evaluates `recurse(n - 1) + 1` until `n` is 0.
+        struct Recurse(Limits);
+
+        impl Function<i64> for Recurse {
+            fn name(&self) -> &str {
+                "recurse"
+            }
+
+            fn call(&self, args: &[i64]) -> Result<i64> {
+                if args[0] == 0 {
+                    return Ok(0);
+                }
+
+                let config = Config::new().with_limits(self.0);
+                let mut evaluator = Evaluator::with_context(DefaultContext::new_checked_with_config(config));
+                evaluator.mut_context().add_function(Recurse(self.0));
+                evaluator.mut_context().set_variable("n", args[0]);
+                evaluator.eval("recurse(n - 1) + 1")
+            }
+        }
+
+        let recurse = Recurse(Limits::new().with_max_recursion_depth(5));
+        // The outer evaluation and 5 nested
+        assert_eq!(Ok(6), recurse.call(&[6]));
+        assert_eq!(ErrorKind::RecursionLimitExceeded, recurse.call(&[7]).unwrap_err().kind());
+        // The depth is restored after an error
+        assert_eq!(Ok(3), recurse.call(&[3]));
+
+        let recurse = Recurse(Limits::new().with_max_eval_steps(25));
+        assert_eq!(Ok(3), recurse.call(&[3]));
+        assert_eq!(ErrorKind::StepLimitExceeded, recurse.call(&[10]).unwrap_err().kind());
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn eval_shared_between_threads_test() {
+        use crate::context::DefaultContext;
+        use std::sync::Arc;
+
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<DefaultContext<'static, f64>>();
+        assert_send_sync::<Evaluator<'static, f64>>();
+
+        let evaluator: Arc<Evaluator<f64>> = Arc::new(Evaluator::with_context(DefaultContext::new_unchecked()));
+        let handles = (0..4)
+            .map(|n| {
+                let evaluator = Arc::clone(&evaluator);
+                std::thread::spawn(move || evaluator.eval(&format!("{} * 2 + max(1, 3)", n)).unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        let results = handles.into_iter().map(|h| h.join().unwrap()).collect::<Vec<_>>();
+        assert_eq!(vec![3.0, 5.0, 7.0, 9.0], results);
+    }
 }
@@ -0,0 +1,507 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::context::scoped::ScopedContext;
+use crate::context::{Config, Context, Limits, Names};
+use crate::error::{Error, ErrorKind};
+use crate::evaluator::infix_to_rpn;
+use crate::evaluator::limits::EvalGuard;
+use crate::evaluator::resolve::{resolve_rpn, Resolved};
+use crate::function::{BinaryFunction, Function, UnaryFunction};
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::utils::ignore_case_str::IgnoreCaseStr;
+use crate::{Result, Shared};
+
+/// An expression compiled for evaluate it over columns of values.
+///
+/// The variables named as columns take its value from the column slices for each row,
+/// other variables and constants take its value from the `Context` when the expression is compiled
+/// and the functions and operators are resolved once, so the context is not needed for evaluate.
+///
+/// The `max_eval_steps` and `max_recursion_depth` limits of the `Context` are checked for each row.
+///
+/// # Example
+/// ```
+/// use math_engine::context::DefaultContext;
+/// use math_engine::evaluator::batch::BatchExpr;
+///
+/// let context : DefaultContext<f64> = DefaultContext::new_unchecked();
+/// let expr = BatchExpr::compile("price * qty + 1", &["price", "qty"], &context).unwrap();
+///
+/// let price = [2.0, 3.5, 10.0];
+/// let qty = [1.0, 2.0, 0.5];
+/// let results = expr.eval(&[("price", &price), ("qty", &qty)]).unwrap();
+/// assert_eq!(vec![Ok(3.0), Ok(8.0), Ok(6.0)], results);
+/// ```
+#[derive(Clone)]
+pub struct BatchExpr<'a, N> {
+    /// The operations in `Reverse Polish Notation`.
+    ops: Vec<Op<'a, N>>,
+    /// The names of the columns, the position is the index used by `Op::Column`.
+    columns: Vec<String>,
+    /// The limits of the `Context` used for compile, checked for each row.
+    limits: Limits,
+    /// The number of evaluation steps of each row.
+    steps: usize,
+}
+
+/// An operation of a `BatchExpr`.
+#[derive(Clone)]
+enum Op<'a, N> {
+    Number(N),
+    Column(usize),
+    Function(Shared<dyn Function<N> + 'a>, usize),
+    Unary(Shared<dyn UnaryFunction<N> + 'a>),
+    Binary(Shared<dyn BinaryFunction<N> + 'a>),
+}
+
+impl<'a, N> BatchExpr<'a, N>
+where
+    N: Clone,
+{
+    /// Compiles the given expression, the variables in `columns` will be taken from the column
+    /// slices passed to `eval`.
+    pub fn compile<C>(expression: &str, columns: &[&str], context: &C) -> Result<Self>
+    where
+        C: Context<'a, N>,
+        N: FromStr + Debug,
+    {
+        let column_context = ColumnContext::new(context, columns);
+
+        let tokens = Tokenizer::with_context(&column_context).tokenize(expression)?;
+        let rpn = infix_to_rpn(&tokens, &column_context)?;
+        Self::from_rpn(&rpn, columns, context)
+    }
+
+    /// Compiles the given tokens in `Reverse Polish Notation`, the variables in `columns` will be
+    /// taken from the column slices passed to `eval`.
+    pub fn from_rpn<C>(rpn: &[Token<N>], columns: &[&str], context: &C) -> Result<Self>
+    where
+        C: Context<'a, N>,
+        N: Debug,
+    {
+        let resolved = resolve_rpn(rpn, columns, context)?;
+        let ops = resolved
+            .tokens
+            .into_iter()
+            .map(|token| match token {
+                Resolved::Value(n) => Op::Number(n),
+                Resolved::Column(index) => Op::Column(index),
+                Resolved::Function(_, func, n) => Op::Function(func, n),
+                Resolved::Unary(_, func) => Op::Unary(func),
+                Resolved::Binary(_, func) => Op::Binary(func),
+            })
+            .collect();
+
+        Ok(BatchExpr {
+            ops,
+            columns: columns.iter().map(|s| s.to_string()).collect(),
+            limits: context.config().limits,
+            steps: resolved.steps,
+        })
+    }
+
+    /// Gets the names of the columns used by this expression.
+    #[inline]
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Evaluates the expression for each row of the given columns, keyed by variable name.
+    ///
+    /// Returns an error if a column is missing or the columns have different lengths,
+    /// otherwise the result of each row.
+    pub fn eval(&self, columns: &[(&str, &[N])]) -> Result<Vec<Result<N>>> {
+        let (slices, rows) = self.bind(columns)?;
+        let mut stack = Vec::new();
+
+        Ok((0..rows)
+            .map(|row| self.eval_row(&slices, row, &mut stack))
+            .collect())
+    }
+
+    /// Evaluates the expression for each row of the given columns, keyed by variable name,
+    /// and writes the results to `output`.
+    ///
+    /// Returns the first error found, `output` must have the same length than the columns.
+    pub fn eval_into(&self, columns: &[(&str, &[N])], output: &mut [N]) -> Result<()> {
+        let (slices, rows) = self.bind(columns)?;
+        check_output_len(rows, output.len())?;
+
+        let mut stack = Vec::new();
+        for (row, result) in output.iter_mut().enumerate() {
+            *result = self.eval_row(&slices, row, &mut stack)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets the column slices in the order used by `Op::Column` and the number of rows.
+    fn bind<'b>(&self, columns: &[(&str, &'b [N])]) -> Result<(Vec<&'b [N]>, usize)> {
+        let mut rows: Option<usize> = None;
+        for (name, values) in columns {
+            match rows {
+                Some(len) if len != values.len() => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Column `{}` has {} rows but {} was expected",
+                            name,
+                            values.len(),
+                            len
+                        ),
+                    ));
+                }
+                _ => rows = Some(values.len()),
+            }
+        }
+
+        let slices = self
+            .columns
+            .iter()
+            .map(|name| {
+                columns
+                    .iter()
+                    .find(|(s, _)| IgnoreCaseStr::new(s) == IgnoreCaseStr::new(name))
+                    .map(|(_, values)| *values)
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidInput,
+                            format!("Column `{}` not found", name),
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok((slices, rows.unwrap_or(0)))
+    }
+
+    /// Evaluates the row at the given index, `stack` is reused between rows.
+    fn eval_row(&self, columns: &[&[N]], row: usize, stack: &mut Vec<N>) -> Result<N> {
+        let _guard = EvalGuard::enter_with_steps(&self.limits, self.steps)?;
+        stack.clear();
+
+        for op in &self.ops {
+            let value = match op {
+                Op::Number(n) => n.clone(),
+                Op::Column(index) => columns[*index][row].clone(),
+                Op::Function(func, n) => {
+                    // The arguments are the last `n` values
+                    let start = stack.len() - n;
+                    let result = func.call(&stack[start..])?;
+                    stack.truncate(start);
+                    result
+                }
+                Op::Unary(func) => {
+                    let value = pop(stack)?;
+                    func.call(value)?
+                }
+                Op::Binary(func) => {
+                    let right = pop(stack)?;
+                    let left = pop(stack)?;
+                    func.call(left, right)?
+                }
+            };
+
+            stack.push(value);
+        }
+
+        pop(stack)
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<'a, N> BatchExpr<'a, N>
+where
+    N: Clone + Send + Sync,
+{
+    /// Evaluates the expression for each row of the given columns in parallel.
+    ///
+    /// See `BatchExpr::eval`.
+    pub fn par_eval(&self, columns: &[(&str, &[N])]) -> Result<Vec<Result<N>>> {
+        use rayon::prelude::*;
+
+        let (slices, rows) = self.bind(columns)?;
+        Ok((0..rows)
+            .into_par_iter()
+            .map_init(Vec::new, |stack, row| self.eval_row(&slices, row, stack))
+            .collect())
+    }
+
+    /// Evaluates the expression for each row of the given columns in parallel
+    /// and writes the results to `output`.
+    ///
+    /// See `BatchExpr::eval_into`.
+    pub fn par_eval_into(&self, columns: &[(&str, &[N])], output: &mut [N]) -> Result<()> {
+        use rayon::prelude::*;
+
+        let (slices, rows) = self.bind(columns)?;
+        check_output_len(rows, output.len())?;
+
+        output
+            .par_iter_mut()
+            .enumerate()
+            .try_for_each_init(Vec::new, |stack, (row, result)| {
+                *result = self.eval_row(&slices, row, stack)?;
+                Ok(())
+            })
+    }
+}
+
+#[inline]
+fn pop<N>(stack: &mut Vec<N>) -> Result<N> {
+    stack
+        .pop()
+        .ok_or_else(|| Error::from(ErrorKind::InvalidExpression))
+}
+
+#[inline]
+pub(crate) fn column_index(columns: &[&str], name: &str) -> Option<usize> {
+    columns
+        .iter()
+        .position(|s| IgnoreCaseStr::new(s) == IgnoreCaseStr::new(name))
+}
+
+fn check_output_len(rows: usize, len: usize) -> Result<()> {
+    if rows != len {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Output has {} rows but {} was expected", len, rows),
+        ));
+    }
+
+    Ok(())
+}
+
+/// A `Context` used for tokenize an expression where the columns are variables.
+///
+/// The lookups fall back to the given context, items added or removed only affect a local scope.
+pub(crate) struct ColumnContext<'a, 'c, N, C> {
+    scope: ScopedContext<'a, 'c, N, C>,
+    columns: &'c [&'c str],
+}
+
+impl<'a: 'c, 'c, N, C> ColumnContext<'a, 'c, N, C>
+where
+    C: Context<'a, N>,
+{
+    /// Constructs a `ColumnContext` over the given context where `columns` are variables.
+    #[inline]
+    pub(crate) fn new(context: &'c C, columns: &'c [&'c str]) -> Self {
+        ColumnContext {
+            scope: ScopedContext::new(context),
+            columns,
+        }
+    }
+}
+
+impl<'a: 'c, 'c, N, C> Context<'c, N> for ColumnContext<'a, 'c, N, C>
+where
+    C: Context<'a, N>,
+{
+    #[inline]
+    fn config(&self) -> &Config {
+        self.scope.config()
+    }
+
+    #[inline]
+    fn add_function<F: Function<N> + 'c>(&mut self, func: F) {
+        self.scope.add_function(func)
+    }
+
+    #[inline]
+    fn add_unary_function<F: UnaryFunction<N> + 'c>(&mut self, func: F) {
+        self.scope.add_unary_function(func)
+    }
+
+    #[inline]
+    fn add_binary_function<F: BinaryFunction<N> + 'c>(&mut self, func: F) {
+        self.scope.add_binary_function(func)
+    }
+
+    #[inline]
+    fn add_constant(&mut self, name: &str, value: N) {
+        self.scope.add_constant(name, value)
+    }
+
+    #[inline]
+    fn set_variable(&mut self, name: &str, value: N) -> Option<N> {
+        self.scope.set_variable(name, value)
+    }
+
+    #[inline]
+    fn get_variable(&self, name: &str) -> Option<&N> {
+        self.scope.get_variable(name)
+    }
+
+    #[inline]
+    fn get_constant(&self, name: &str) -> Option<&N> {
+        self.scope.get_constant(name)
+    }
+
+    #[inline]
+    fn get_function(&self, name: &str) -> Option<&Shared<dyn Function<N> + 'c>> {
+        self.scope.get_function(name)
+    }
+
+    #[inline]
+    fn get_unary_function(&self, name: &str) -> Option<&Shared<dyn UnaryFunction<N> + 'c>> {
+        self.scope.get_unary_function(name)
+    }
+
+    #[inline]
+    fn get_binary_function(&self, name: &str) -> Option<&Shared<dyn BinaryFunction<N> + 'c>> {
+        self.scope.get_binary_function(name)
+    }
+
+    #[inline]
+    fn remove_variable(&mut self, name: &str) -> Option<N> {
+        self.scope.remove_variable(name)
+    }
+
+    #[inline]
+    fn remove_constant(&mut self, name: &str) -> Option<N> {
+        self.scope.remove_constant(name)
+    }
+
+    #[inline]
+    fn remove_function(&mut self, name: &str) -> Option<Shared<dyn Function<N> + 'c>> {
+        self.scope.remove_function(name)
+    }
+
+    #[inline]
+    fn remove_unary_function(&mut self, name: &str) -> Option<Shared<dyn UnaryFunction<N> + 'c>> {
+        self.scope.remove_unary_function(name)
+    }
+
+    #[inline]
+    fn remove_binary_function(&mut self, name: &str) -> Option<Shared<dyn BinaryFunction<N> + 'c>> {
+        self.scope.remove_binary_function(name)
+    }
+
+    #[inline]
+    fn variable_names(&self) -> Names<'_> {
+        let columns = self.columns;
+        let variables = self
+            .scope
+            .variable_names()
+            .filter(move |name| column_index(columns, name).is_none());
+
+        Box::new(columns.iter().copied().chain(variables))
+    }
+
+    #[inline]
+    fn constant_names(&self) -> Names<'_> {
+        self.scope.constant_names()
+    }
+
+    #[inline]
+    fn function_names(&self) -> Names<'_> {
+        self.scope.function_names()
+    }
+
+    #[inline]
+    fn unary_function_names(&self) -> Names<'_> {
+        self.scope.unary_function_names()
+    }
+
+    #[inline]
+    fn binary_function_names(&self) -> Names<'_> {
+        self.scope.binary_function_names()
+    }
+
+    #[inline]
+    fn clear_variables(&mut self) {
+        self.scope.clear_variables()
+    }
+
+    #[inline]
+    fn is_variable(&self, name: &str) -> bool {
+        column_index(self.columns, name).is_some() || self.scope.is_variable(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::DefaultContext;
+
+    #[test]
+    fn batch_eval_test() {
+        let mut context: DefaultContext<i64> = DefaultContext::new_checked();
+        context.set_variable("offset", 100);
+
+        let expr = BatchExpr::compile("Max(x, y) * 2 + offset - 2 * x", &["x", "y"], &context).unwrap();
+        assert_eq!(&["x".to_string(), "y".to_string()], expr.columns());
+
+        let x = [1, 5, -3];
+        let y = [4, 2, -1];
+        let results = expr.eval(&[("y", &y), ("X", &x)]).unwrap();
+        assert_eq!(vec![Ok(106), Ok(100), Ok(104)], results);
+
+        let mut output = [0; 3];
+        expr.eval_into(&[("x", &x), ("y", &y)], &mut output).unwrap();
+        assert_eq!([106, 100, 104], output);
+    }
+
+    #[test]
+    fn batch_eval_error_test() {
+        let context: DefaultContext<i64> = DefaultContext::new_checked();
+        let expr = BatchExpr::compile("10 / x", &["x"], &context).unwrap();
+
+        let x = [2, 0, 5];
+        let results = expr.eval(&[("x", &x)]).unwrap();
+        assert_eq!(Ok(5), results[0]);
+        assert_eq!(ErrorKind::DivisionByZero, results[1].as_ref().unwrap_err().kind());
+        assert_eq!(Ok(2), results[2]);
+
+        let mut output = [0; 3];
+        assert!(expr.eval_into(&[("x", &x)], &mut output).is_err());
+        assert!(expr.eval_into(&[("x", &x)], &mut [0; 2]).is_err());
+
+        // Missing column and different lengths
+        assert!(expr.eval(&[("y", &x)]).is_err());
+        assert!(expr.eval(&[("x", &x), ("y", &[1, 2])]).is_err());
+
+        // Not a column or variable
+        assert!(BatchExpr::compile("x + y", &["x"], &context).is_err());
+        assert!(BatchExpr::compile("2 +", &["x"], &context).is_err());
+
+        let rpn = [Token::Number(2), Token::BinaryOperator("+".to_string())];
+        let error = BatchExpr::from_rpn(&rpn, &["x"], &context).err().unwrap();
+        assert_eq!("Missing operands for binary operator `+`", error.to_string());
+    }
+
+    #[test]
+    fn batch_eval_limits_test() {
+        use crate::context::Limits;
+
+        let config = Config::new().with_limits(Limits::new().with_max_eval_steps(5));
+        let context: DefaultContext<i64> = DefaultContext::new_checked_with_config(config);
+
+        // The steps are counted for each row
+        let expr = BatchExpr::compile("x + 2 * 3", &["x"], &context).unwrap();
+        assert_eq!(vec![Ok(7), Ok(8)], expr.eval(&[("x", &[1, 2])]).unwrap());
+
+        let expr = BatchExpr::compile("x + 2 * 3 - 1", &["x"], &context).unwrap();
+        let results = expr.eval(&[("x", &[1])]).unwrap();
+        assert_eq!(ErrorKind::StepLimitExceeded, results[0].as_ref().unwrap_err().kind());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn batch_par_eval_test() {
+        let context: DefaultContext<f64> = DefaultContext::new_unchecked();
+        let expr = BatchExpr::compile("x ^ 2 + Sqrt(y)", &["x", "y"], &context).unwrap();
+
+        let x = (0..10_000).map(|n| n as f64).collect::<Vec<_>>();
+        let y = vec![4.0; x.len()];
+        let expected = expr.eval(&[("x", &x), ("y", &y)]).unwrap();
+        assert_eq!(expected, expr.par_eval(&[("x", &x), ("y", &y)]).unwrap());
+
+        let mut output = vec![0.0; x.len()];
+        expr.par_eval_into(&[("x", &x), ("y", &y)], &mut output).unwrap();
+        assert_eq!(expected.into_iter().map(|r| r.unwrap()).collect::<Vec<_>>(), output);
+    }
+}
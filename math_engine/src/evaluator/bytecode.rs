@@ -0,0 +1,339 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::context::{Context, Limits};
+use crate::error::{Error, ErrorKind};
+use crate::evaluator::batch::ColumnContext;
+use crate::evaluator::infix_to_rpn;
+use crate::evaluator::limits::EvalGuard;
+use crate::evaluator::resolve::{resolve_rpn, Resolved};
+use crate::function::{BinaryFunction, Function, UnaryFunction};
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::{Result, Shared};
+
+/// An instruction of a `Program`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Instruction {
+    /// Pushes the constant at the given index.
+    PushConst(usize),
+    /// Pushes the value of the variable at the given slot.
+    LoadVar(usize),
+    /// Calls the function at the given index with the given number of arguments.
+    Call(usize, usize),
+    /// Calls the unary operator at the given index.
+    Unary(usize),
+    /// Calls the binary operator at the given index.
+    Binary(usize),
+}
+
+/// An expression compiled to bytecode, executed by a `Vm`.
+///
+/// The variables, functions and operators are resolved to indices when compiled,
+/// so no names are looked up during the evaluation.
+///
+/// The `max_eval_steps` and `max_recursion_depth` limits of the `Context` are checked for each run.
+///
+/// # Example
+/// ```
+/// use math_engine::context::DefaultContext;
+/// use math_engine::evaluator::bytecode::{Program, Vm};
+///
+/// let context : DefaultContext<i64> = DefaultContext::new_checked();
+/// let program = Program::compile("x * (y + 2)", &["x", "y"], &context).unwrap();
+///
+/// let mut vm = Vm::new();
+/// assert_eq!(Ok(15), vm.run(&program, &[3, 3]));
+/// assert_eq!(Ok(-8), vm.run(&program, &[-2, 2]));
+/// ```
+#[derive(Clone)]
+pub struct Program<'a, N> {
+    instructions: Vec<Instruction>,
+    constants: Vec<N>,
+    variables: Vec<String>,
+    functions: Vec<Shared<dyn Function<N> + 'a>>,
+    unary_functions: Vec<Shared<dyn UnaryFunction<N> + 'a>>,
+    binary_functions: Vec<Shared<dyn BinaryFunction<N> + 'a>>,
+    /// The max number of values in the stack during the execution.
+    stack_size: usize,
+    /// The limits of the `Context` used for compile, checked for each run.
+    limits: Limits,
+    /// The number of evaluation steps of each run.
+    steps: usize,
+}
+
+impl<'a, N> Program<'a, N>
+where
+    N: Clone,
+{
+    /// Compiles the given expression, the variables in `variables` are loaded from
+    /// the slots passed to `Vm::run` in the same order.
+    ///
+    /// Other variables and constants take its value from the `Context` when compiled.
+    pub fn compile<C>(expression: &str, variables: &[&str], context: &C) -> Result<Self>
+    where
+        C: Context<'a, N>,
+        N: FromStr + Debug,
+    {
+        let column_context = ColumnContext::new(context, variables);
+        let tokens = Tokenizer::with_context(&column_context).tokenize(expression)?;
+        let rpn = infix_to_rpn(&tokens, &column_context)?;
+        Self::from_rpn(&rpn, variables, context)
+    }
+
+    /// Compiles the given tokens in `Reverse Polish Notation`, see `Program::compile`.
+    pub fn from_rpn<C>(rpn: &[Token<N>], variables: &[&str], context: &C) -> Result<Self>
+    where
+        C: Context<'a, N>,
+        N: Debug,
+    {
+        /// Gets the index of the function with the given name, adding it if not exists.
+        fn index_of<F: ?Sized>(
+            names: &mut Vec<String>,
+            items: &mut Vec<Shared<F>>,
+            name: &str,
+            item: &Shared<F>,
+        ) -> usize {
+            match names.iter().position(|s| s == name) {
+                Some(index) => index,
+                None => {
+                    names.push(name.to_string());
+                    items.push(item.clone());
+                    items.len() - 1
+                }
+            }
+        }
+
+        let resolved = resolve_rpn(rpn, variables, context)?;
+
+        let mut program = Program {
+            instructions: Vec::with_capacity(resolved.tokens.len()),
+            constants: Vec::new(),
+            variables: variables.iter().map(|s| s.to_string()).collect(),
+            functions: Vec::new(),
+            unary_functions: Vec::new(),
+            binary_functions: Vec::new(),
+            stack_size: resolved.stack_size,
+            limits: context.config().limits,
+            steps: resolved.steps,
+        };
+
+        let mut function_names = Vec::new();
+        let mut unary_names = Vec::new();
+        let mut binary_names = Vec::new();
+
+        for token in resolved.tokens {
+            let instruction = match token {
+                Resolved::Value(n) => program.push_const(n),
+                Resolved::Column(slot) => Instruction::LoadVar(slot),
+                Resolved::Function(name, func, n) => {
+                    let index = index_of(&mut function_names, &mut program.functions, name, &func);
+                    Instruction::Call(index, n)
+                }
+                Resolved::Unary(name, func) => {
+                    let index = index_of(&mut unary_names, &mut program.unary_functions, name, &func);
+                    Instruction::Unary(index)
+                }
+                Resolved::Binary(name, func) => {
+                    let index = index_of(&mut binary_names, &mut program.binary_functions, name, &func);
+                    Instruction::Binary(index)
+                }
+            };
+
+            program.instructions.push(instruction);
+        }
+
+        Ok(program)
+    }
+
+    /// Gets the instructions of this program.
+    #[inline]
+    pub fn instructions(&self) -> &[Instruction] {
+        &self.instructions
+    }
+
+    /// Gets the constants used by `Instruction::PushConst`.
+    #[inline]
+    pub fn constants(&self) -> &[N] {
+        &self.constants
+    }
+
+    /// Gets the names of the variables in slot order.
+    #[inline]
+    pub fn variables(&self) -> &[String] {
+        &self.variables
+    }
+
+    /// Gets the max number of values in the stack during the execution.
+    #[inline]
+    pub fn stack_size(&self) -> usize {
+        self.stack_size
+    }
+
+    /// Evaluates this program with the given variable values using a new `Vm`.
+    #[inline]
+    pub fn eval(&self, variables: &[N]) -> Result<N> {
+        Vm::new().run(self, variables)
+    }
+
+    fn push_const(&mut self, value: N) -> Instruction {
+        self.constants.push(value);
+        Instruction::PushConst(self.constants.len() - 1)
+    }
+}
+
+/// A virtual machine which executes the bytecode of a `Program`.
+///
+/// The stack is kept between runs, so reusing the `Vm` avoids allocations.
+#[derive(Debug, Clone)]
+pub struct Vm<N> {
+    stack: Vec<N>,
+}
+
+impl<N> Vm<N> {
+    /// Constructs a new `Vm`.
+    #[inline]
+    pub fn new() -> Self {
+        Vm { stack: Vec::new() }
+    }
+
+    /// Constructs a new `Vm` with a stack of the given capacity.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Vm {
+            stack: Vec::with_capacity(capacity),
+        }
+    }
+}
+
+impl<N> Default for Vm<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Clone> Vm<N> {
+    /// Executes the given program with the values of its variables in slot order.
+    pub fn run(&mut self, program: &Program<N>, variables: &[N]) -> Result<N> {
+        if variables.len() < program.variables.len() {
+            return Err(Error::new(
+                ErrorKind::InvalidArgumentCount,
+                format!(
+                    "expected {} variables but {} was get",
+                    program.variables.len(),
+                    variables.len()
+                ),
+            ));
+        }
+
+        let _guard = EvalGuard::enter_with_steps(&program.limits, program.steps)?;
+        let stack = &mut self.stack;
+        stack.clear();
+        stack.reserve(program.stack_size);
+
+        // The program was validated when compiled, so the stack always have enough values.
+        for instruction in &program.instructions {
+            let value = match *instruction {
+                Instruction::PushConst(index) => program.constants[index].clone(),
+                Instruction::LoadVar(slot) => variables[slot].clone(),
+                Instruction::Call(index, argc) => {
+                    let start = stack.len() - argc;
+                    let result = program.functions[index].call(&stack[start..])?;
+                    stack.truncate(start);
+                    result
+                }
+                Instruction::Unary(index) => {
+                    let value = stack.pop().unwrap();
+                    program.unary_functions[index].call(value)?
+                }
+                Instruction::Binary(index) => {
+                    let right = stack.pop().unwrap();
+                    let left = stack.pop().unwrap();
+                    program.binary_functions[index].call(left, right)?
+                }
+            };
+
+            stack.push(value);
+        }
+
+        Ok(stack.pop().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::DefaultContext;
+    use crate::evaluator::Evaluator;
+
+    #[test]
+    fn compile_test() {
+        let context: DefaultContext<i64> = DefaultContext::new_checked();
+        let program = Program::compile("Max(x, 2) + -x * x", &["x"], &context).unwrap();
+
+        assert_eq!(
+            &[
+                Instruction::LoadVar(0),
+                Instruction::PushConst(0),
+                Instruction::Call(0, 2),
+                Instruction::LoadVar(0),
+                Instruction::Unary(0),
+                Instruction::LoadVar(0),
+                Instruction::Binary(0),
+                Instruction::Binary(1),
+            ],
+            program.instructions()
+        );
+        assert_eq!(&[2], program.constants());
+        assert_eq!(3, program.stack_size());
+        assert_eq!(Ok(-6), program.eval(&[3]));
+    }
+
+    #[test]
+    fn run_test() {
+        let mut context: DefaultContext<f64> = DefaultContext::new_unchecked();
+        context.set_variable("offset", 0.5);
+
+        let expression = "Sqrt(a ^ 2 + b ^ 2) * 2 - Max(a, b, PI) / 2 + offset";
+        let program = Program::compile(expression, &["a", "b"], &context).unwrap();
+
+        let mut vm = Vm::with_capacity(program.stack_size());
+        for (a, b) in &[(3.0, 4.0), (6.0, 8.0), (0.0, 0.0)] {
+            let mut evaluator = Evaluator::with_context(context.clone());
+            evaluator.mut_context().set_variable("a", *a);
+            evaluator.mut_context().set_variable("b", *b);
+
+            assert_eq!(evaluator.eval(expression), vm.run(&program, &[*a, *b]));
+        }
+    }
+
+    #[test]
+    fn run_error_test() {
+        let context: DefaultContext<i64> = DefaultContext::new_checked();
+        let program = Program::compile("10 / (x - y)", &["x", "y"], &context).unwrap();
+
+        let mut vm = Vm::new();
+        assert_eq!(ErrorKind::DivisionByZero, vm.run(&program, &[1, 1]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidArgumentCount, vm.run(&program, &[1]).unwrap_err().kind());
+        assert_eq!(Ok(5), vm.run(&program, &[3, 1]));
+
+        assert!(Program::compile("x + y", &["x"], &context).is_err());
+        assert!(Program::compile("(2 +)", &[], &context).is_err());
+    }
+
+    #[test]
+    fn run_limits_test() {
+        use crate::context::{Config, Limits};
+
+        let config = Config::new().with_limits(Limits::new().with_max_eval_steps(5));
+        let context: DefaultContext<i64> = DefaultContext::new_checked_with_config(config);
+        let mut vm = Vm::new();
+
+        let program = Program::compile("x * 2 + 1", &["x"], &context).unwrap();
+        assert_eq!(Ok(7), vm.run(&program, &[3]));
+
+        let program = Program::compile("x * 2 + 1 - y", &["x", "y"], &context).unwrap();
+        assert_eq!(ErrorKind::StepLimitExceeded, vm.run(&program, &[3, 1]).unwrap_err().kind());
+    }
+}
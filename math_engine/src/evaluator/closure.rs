@@ -0,0 +1,196 @@
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use crate::ast::Expr;
+use crate::context::Context;
+use crate::error::{Error, ErrorKind};
+use crate::evaluator::batch::{column_index, ColumnContext};
+use crate::evaluator::limits::EvalGuard;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::Result;
+
+/// An expression compiled to a closure, which takes the values of the variables
+/// in the order used for compile it.
+pub type CompiledFn<'a, N> = Box<dyn Fn(&[N]) -> Result<N> + 'a>;
+
+/// Compiles the given expression to a closure built from nested closures,
+/// the functions and operators are resolved once so no lookups are done when it's called.
+///
+/// The variables in `variables` take its value from the slice passed to the closure, in the same order,
+/// other variables and constants take its value from the `Context` when the expression is compiled.
+///
+/// The `max_eval_steps` and `max_recursion_depth` limits of the `Context` are checked each time it's called.
+///
+/// # Example
+/// ```
+/// use math_engine::context::DefaultContext;
+/// use math_engine::evaluator::closure;
+///
+/// let context : DefaultContext<f64> = DefaultContext::new_unchecked();
+/// let f = closure::compile("x ^ 2 + y", &["x", "y"], &context).unwrap();
+///
+/// assert_eq!(Ok(10.0), f(&[3.0, 1.0]));
+/// assert_eq!(Ok(4.5), f(&[2.0, 0.5]));
+/// ```
+pub fn compile<'a, N, C>(expression: &str, variables: &[&str], context: &C) -> Result<CompiledFn<'a, N>>
+where
+    N: FromStr + Debug + Clone + 'a,
+    C: Context<'a, N>,
+{
+    let column_context = ColumnContext::new(context, variables);
+    let tokens = Tokenizer::with_context(&column_context).tokenize(expression)?;
+    let expr = Expr::from_infix(&tokens, &column_context)?;
+    compile_expr(&expr, variables, context)
+}
+
+/// Compiles the given expression tree to a closure, see `compile`.
+pub fn compile_expr<'a, N, C>(expr: &Expr<N>, variables: &[&str], context: &C) -> Result<CompiledFn<'a, N>>
+where
+    N: Clone + 'a,
+    C: Context<'a, N>,
+{
+    let limits = context.config().limits;
+    let steps = steps_of(expr);
+    let compiled = compile_node(expr, variables, context)?;
+
+    Ok(Box::new(move |values: &[N]| {
+        let _guard = EvalGuard::enter_with_steps(&limits, steps)?;
+        compiled(values)
+    }))
+}
+
+/// Gets the number of steps for evaluate the expression, counted as `rpn_eval` does.
+fn steps_of<N>(expr: &Expr<N>) -> usize {
+    match expr {
+        Expr::Number(_) | Expr::Variable(_) | Expr::Constant(_) => 1,
+        // The argument count, the function and one for each argument
+        Expr::Function(_, args) => 2 + args.len() + args.iter().map(steps_of).sum::<usize>(),
+        Expr::BinaryOperator(_, left, right) => 1 + steps_of(left) + steps_of(right),
+        Expr::UnaryOperator(_, operand) => 1 + steps_of(operand),
+    }
+}
+
+fn compile_node<'a, N, C>(expr: &Expr<N>, variables: &[&str], context: &C) -> Result<CompiledFn<'a, N>>
+where
+    N: Clone + 'a,
+    C: Context<'a, N>,
+{
+    fn value<'a, N: Clone + 'a>(n: N) -> CompiledFn<'a, N> {
+        Box::new(move |_| Ok(n.clone()))
+    }
+
+    let compiled: CompiledFn<'a, N> = match expr {
+        Expr::Number(n) => value(n.clone()),
+        Expr::Variable(name) => match column_index(variables, name) {
+            Some(index) => {
+                let name = name.clone();
+                Box::new(move |values: &[N]| {
+                    values.get(index).cloned().ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::InvalidArgumentCount,
+                            format!("No value for variable `{}`", name),
+                        )
+                    })
+                })
+            }
+            None => {
+                let n = context
+                    .get_variable(name)
+                    .ok_or_else(|| Error::not_found("Variable", name))?;
+                value(n.clone())
+            }
+        },
+        Expr::Constant(name) => {
+            let n = context
+                .get_constant(name)
+                .ok_or_else(|| Error::not_found("Constant", name))?;
+            value(n.clone())
+        }
+        Expr::Function(name, args) => {
+            let func = context
+                .get_function(name)
+                .ok_or_else(|| Error::not_found("Function", name))?
+                .clone();
+
+            let args = args
+                .iter()
+                .map(|arg| compile_node(arg, variables, context))
+                .collect::<Result<Vec<_>>>()?;
+
+            Box::new(move |values: &[N]| {
+                let args = args
+                    .iter()
+                    .map(|arg| arg(values))
+                    .collect::<Result<Vec<N>>>()?;
+                func.call(&args)
+            })
+        }
+        Expr::BinaryOperator(name, left, right) => {
+            let func = context
+                .get_binary_function(name)
+                .ok_or_else(|| Error::not_found("Binary operator", name))?
+                .clone();
+
+            let left = compile_node(left, variables, context)?;
+            let right = compile_node(right, variables, context)?;
+            Box::new(move |values: &[N]| func.call(left(values)?, right(values)?))
+        }
+        Expr::UnaryOperator(name, operand) => {
+            let func = context
+                .get_unary_function(name)
+                .ok_or_else(|| Error::not_found("Unary operator", name))?
+                .clone();
+
+            let operand = compile_node(operand, variables, context)?;
+            Box::new(move |values: &[N]| func.call(operand(values)?))
+        }
+    };
+
+    Ok(compiled)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::DefaultContext;
+    use crate::evaluator::Evaluator;
+
+    #[test]
+    fn compile_test() {
+        let mut context: DefaultContext<i64> = DefaultContext::new_checked();
+        context.set_variable("offset", 10);
+
+        let f = compile("Max(a, b, 3) * 2 - -a + offset + 5!", &["a", "b"], &context).unwrap();
+        assert_eq!(Ok(137), f(&[1, 0]));
+        assert_eq!(Ok(148), f(&[4, 7]));
+
+        let g = compile("2 ^ 3 ^ 2 - (10 - 4) / 2", &[], &context).unwrap();
+        assert_eq!(Evaluator::new().eval("2 ^ 3 ^ 2 - (10 - 4) / 2"), g(&[]));
+    }
+
+    #[test]
+    fn compile_error_test() {
+        let context: DefaultContext<i64> = DefaultContext::new_checked();
+        let f = compile("10 / x", &["x"], &context).unwrap();
+
+        assert_eq!(ErrorKind::DivisionByZero, f(&[0]).unwrap_err().kind());
+        assert_eq!(ErrorKind::InvalidArgumentCount, f(&[]).unwrap_err().kind());
+
+        assert!(compile("x + y", &["x"], &context).is_err());
+        assert!(compile("2 *", &[], &context).is_err());
+    }
+
+    #[test]
+    fn compile_limits_test() {
+        use crate::context::{Config, Limits};
+
+        let config = Config::new().with_limits(Limits::new().with_max_eval_steps(8));
+        let context: DefaultContext<i64> = DefaultContext::new_checked_with_config(config);
+
+        let f = compile("Max(x, 2, 3)", &["x"], &context).unwrap();
+        assert_eq!(Ok(4), f(&[4]));
+
+        let g = compile("Max(x, 2, 3, 4)", &["x"], &context).unwrap();
+        assert_eq!(ErrorKind::StepLimitExceeded, g(&[4]).unwrap_err().kind());
+    }
+}
@@ -0,0 +1,140 @@
+use crate::context::Context;
+use crate::error::{Error, ErrorKind};
+use crate::evaluator::batch::column_index;
+use crate::function::{BinaryFunction, Function, UnaryFunction};
+use crate::token::Token;
+use crate::{Result, Shared};
+use std::fmt::Debug;
+
+/// A token in `Reverse Polish Notation` resolved using a `Context`.
+pub(crate) enum Resolved<'a, 't, N> {
+    /// A number or the value of a variable or constant taken from the `Context`.
+    Value(N),
+    /// A variable which value is provided when evaluated, the index is its position in the columns.
+    Column(usize),
+    /// A function and the number of arguments it takes.
+    Function(&'t str, Shared<dyn Function<N> + 'a>, usize),
+    /// An unary operator.
+    Unary(&'t str, Shared<dyn UnaryFunction<N> + 'a>),
+    /// A binary operator.
+    Binary(&'t str, Shared<dyn BinaryFunction<N> + 'a>),
+}
+
+/// The resolved tokens of an expression in `Reverse Polish Notation`.
+pub(crate) struct ResolvedRpn<'a, 't, N> {
+    /// The tokens in the order they are evaluated.
+    pub tokens: Vec<Resolved<'a, 't, N>>,
+    /// The max number of values in the stack during the evaluation.
+    pub stack_size: usize,
+    /// The number of steps of the evaluation, counted as `rpn_eval` does.
+    pub steps: usize,
+}
+
+/// Resolves the variables, constants, functions and operators of the given tokens in
+/// `Reverse Polish Notation` using the `Context`, the variables in `columns` are resolved to its index.
+///
+/// Returns an error if a name is not found or the expression is not valid,
+/// so the resolved tokens always have enough values in the stack when evaluated.
+pub(crate) fn resolve_rpn<'a, 't, N, C>(
+    rpn: &'t [Token<N>],
+    columns: &[&str],
+    context: &C,
+) -> Result<ResolvedRpn<'a, 't, N>>
+where
+    N: Clone + Debug,
+    C: Context<'a, N>,
+{
+    let mut tokens = Vec::with_capacity(rpn.len());
+    // The number of values in the stack after each token, used for validate the expression.
+    let mut stack_len: usize = 0;
+    let mut stack_size: usize = 0;
+    let mut steps: usize = rpn.len();
+    let mut arg_count: Option<usize> = None;
+
+    for token in rpn {
+        let (resolved, pops) = match token {
+            Token::Number(n) => (Resolved::Value(n.clone()), 0),
+            Token::Variable(name) => match column_index(columns, name) {
+                Some(index) => (Resolved::Column(index), 0),
+                None => {
+                    let value = context
+                        .get_variable(name)
+                        .ok_or_else(|| Error::not_found("Variable", name))?;
+                    (Resolved::Value(value.clone()), 0)
+                }
+            },
+            Token::Constant(name) => {
+                let value = context
+                    .get_constant(name)
+                    .ok_or_else(|| Error::not_found("Constant", name))?;
+                (Resolved::Value(value.clone()), 0)
+            }
+            Token::ArgCount(n) => {
+                arg_count = Some(*n);
+                continue;
+            }
+            Token::Function(name) => {
+                let func = context
+                    .get_function(name)
+                    .ok_or_else(|| Error::not_found("Function", name))?;
+
+                let n = arg_count.take().ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        format!(
+                            "Cannot evaluate function `{}`, unknown number of arguments",
+                            name
+                        ),
+                    )
+                })?;
+
+                steps += n;
+                (Resolved::Function(name.as_str(), func.clone(), n), n)
+            }
+            Token::UnaryOperator(name) => {
+                let func = context
+                    .get_unary_function(name)
+                    .ok_or_else(|| Error::not_found("Unary operator", name))?;
+                (Resolved::Unary(name.as_str(), func.clone()), 1)
+            }
+            Token::BinaryOperator(name) => {
+                let func = context
+                    .get_binary_function(name)
+                    .ok_or_else(|| Error::not_found("Binary operator", name))?;
+                (Resolved::Binary(name.as_str(), func.clone()), 2)
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unknown token: `{:?}`", token),
+                ));
+            }
+        };
+
+        if stack_len < pops {
+            let message = match &resolved {
+                Resolved::Function(name, _, _) => format!("Missing arguments for function `{}`", name),
+                Resolved::Unary(name, _) => format!("Missing operand for unary operator `{}`", name),
+                Resolved::Binary(name, _) => format!("Missing operands for binary operator `{}`", name),
+                // Values don't take operands
+                Resolved::Value(_) | Resolved::Column(_) => String::from("Missing operands"),
+            };
+
+            return Err(Error::new(ErrorKind::InvalidExpression, message));
+        }
+
+        stack_len = stack_len - pops + 1;
+        stack_size = stack_size.max(stack_len);
+        tokens.push(resolved);
+    }
+
+    if stack_len != 1 {
+        return Err(Error::from(ErrorKind::InvalidExpression));
+    }
+
+    Ok(ResolvedRpn {
+        tokens,
+        stack_size,
+        steps,
+    })
+}
@@ -0,0 +1,334 @@
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::ast::Expr;
+use crate::context::{Context, DefaultContext};
+use crate::error::{Error, ErrorKind};
+use crate::formatter::{
+    greek_letter, is_fraction, needs_parens_left, needs_parens_postfix, needs_parens_prefix,
+    needs_parens_right, unary_notation, Format,
+};
+use crate::function::Notation;
+use crate::num::checked::CheckedNum;
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::Result;
+
+/// Functions which have its own `LaTeX` command.
+pub(crate) const LATEX_FUNCTIONS: [&str; 16] = [
+    "sin", "cos", "tan", "csc", "sec", "cot", "sinh", "cosh", "tanh", "coth", "ln", "log", "exp",
+    "max", "min", "gcd",
+];
+
+/// Converts expressions to `LaTeX`.
+///
+/// Custom functions can provide its own `LaTeX` form by implementing `Function::to_latex`.
+///
+/// # Example
+/// ```
+/// use math_engine::formatter::latex::LatexFormatter;
+///
+/// let formatter : LatexFormatter<i64> = LatexFormatter::new();
+/// let latex = formatter.format_str("Sqrt(PI ^ 2) / 2").unwrap();
+/// assert_eq!(r"\frac{\sqrt{\pi^{2}}}{2}", latex);
+/// ```
+pub struct LatexFormatter<'a, N, C = DefaultContext<'a, N>>
+where
+    C: Context<'a, N>,
+{
+    /// The context which contains the operators and functions used
+    /// for format and expression.
+    context: &'a C,
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N> LatexFormatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    /// Constructs a new `LatexFormatter` using the default checked context.
+    #[inline]
+    pub fn new() -> Self {
+        LatexFormatter {
+            context: unsafe { DefaultContext::instance() },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, N> Default for LatexFormatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, N, C> LatexFormatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    /// Constructs a new `LatexFormatter` with the given `Context`.
+    #[inline]
+    pub fn with_context(context: &'a C) -> Self {
+        LatexFormatter {
+            context,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the `LaTeX` of the given tokens in `infix` notation.
+    pub fn format_tokens(&self, tokens: &[Token<N>]) -> Result<String>
+    where
+        N: Clone + Debug,
+    {
+        let expr = Expr::from_infix(tokens, self.context)?;
+        self.format(&expr)
+    }
+
+    /// Gets the `LaTeX` of the given expression.
+    pub fn format_str(&self, expression: &str) -> Result<String>
+    where
+        N: Clone + Debug + FromStr,
+    {
+        let tokens = Tokenizer::with_context(self.context).tokenize(expression)?;
+        self.format_tokens(&tokens)
+    }
+
+    fn write_expr(&self, expr: &Expr<N>, output: &mut String) -> Result<()> {
+        let context = self.context;
+
+        match expr {
+            Expr::Number(n) => output.push_str(&n.to_string()),
+            Expr::Variable(name) | Expr::Constant(name) => output.push_str(&latex_name(name)),
+            Expr::Function(name, args) => self.write_function(name, args, output)?,
+            Expr::BinaryOperator(name, left, right) => match name.as_str() {
+                "/" => {
+                    output.push_str(r"\frac{");
+                    self.write_expr(left, output)?;
+                    output.push_str("}{");
+                    self.write_expr(right, output)?;
+                    output.push('}');
+                }
+                "^" => {
+                    // The base is always grouped if is not a simple value: (2 + 3)^{2}, (2^{3})^{4}
+                    let parens = needs_parens_prefix(context, left);
+                    self.write_grouped(left, parens, output)?;
+                    output.push_str("^{");
+                    self.write_expr(right, output)?;
+                    output.push('}');
+                }
+                _ => {
+                    // Fractions don't need parentheses: 2 \cdot \frac{3}{4}
+                    let left_parens = !is_fraction(left) && needs_parens_left(context, name, left)?;
+                    let right_parens = !is_fraction(right) && needs_parens_right(context, name, right)?;
+
+                    self.write_grouped(left, left_parens, output)?;
+                    output.push(' ');
+                    output.push_str(&latex_operator(name));
+                    output.push(' ');
+                    self.write_grouped(right, right_parens, output)?;
+                }
+            },
+            Expr::UnaryOperator(name, operand) => match unary_notation(context, name)? {
+                Notation::Prefix => {
+                    let operator = latex_operator(name);
+                    output.push_str(&operator);
+                    if name.ends_with(char::is_alphanumeric)
+                        || operator.ends_with(char::is_alphabetic)
+                    {
+                        output.push(' ');
+                    }
+
+                    self.write_grouped(operand, needs_parens_prefix(context, operand), output)?;
+                }
+                Notation::Postfix => {
+                    self.write_grouped(operand, needs_parens_postfix(context, operand)?, output)?;
+                    output.push_str(&latex_operator(name));
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    fn write_function(&self, name: &str, args: &[Expr<N>], output: &mut String) -> Result<()> {
+        let func = self.context.get_function(name).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Function `{}` not found", name),
+            )
+        })?;
+
+        let mut latex_args = Vec::with_capacity(args.len());
+        for arg in args {
+            let mut latex = String::new();
+            self.write_expr(arg, &mut latex)?;
+            latex_args.push(latex);
+        }
+
+        if let Some(latex) = func.to_latex(&latex_args) {
+            output.push_str(&latex);
+            return Ok(());
+        }
+
+        let func_name = func.name().to_lowercase();
+        match (func_name.as_str(), latex_args.as_slice()) {
+            ("sqrt", [arg]) => output.push_str(&format!(r"\sqrt{{{}}}", arg)),
+            ("abs", [arg]) => output.push_str(&format!(r"\left|{}\right|", arg)),
+            ("floor", [arg]) => output.push_str(&format!(r"\left\lfloor {} \right\rfloor", arg)),
+            ("ceil", [arg]) => output.push_str(&format!(r"\left\lceil {} \right\rceil", arg)),
+            ("log", [arg, base]) => output.push_str(&format!(r"\log_{{{}}}\left({}\right)", base, arg)),
+            _ => {
+                let command = match func_name.as_str() {
+                    "asin" => r"\arcsin".to_string(),
+                    "acos" => r"\arccos".to_string(),
+                    "atan" => r"\arctan".to_string(),
+                    s if LATEX_FUNCTIONS.contains(&s) => format!(r"\{}", s),
+                    _ => format!(r"\operatorname{{{}}}", func.name()),
+                };
+
+                output.push_str(&command);
+                output.push_str(r"\left(");
+                output.push_str(&latex_args.join(", "));
+                output.push_str(r"\right)");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_grouped(&self, expr: &Expr<N>, parens: bool, output: &mut String) -> Result<()> {
+        if parens {
+            output.push_str(r"\left(");
+            self.write_expr(expr, output)?;
+            output.push_str(r"\right)");
+        } else {
+            self.write_expr(expr, output)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, N, C> Format<N> for LatexFormatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    fn format(&self, expr: &Expr<N>) -> Result<String> {
+        let mut output = String::new();
+        self.write_expr(expr, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// Gets the `LaTeX` of a variable or constant name.
+fn latex_name(name: &str) -> String {
+    let lowercase = name.to_lowercase();
+    if greek_letter(&lowercase).is_some() {
+        format!(r"\{}", lowercase)
+    } else if lowercase == "e" {
+        // Euler's number
+        lowercase
+    } else if name.chars().count() == 1 {
+        name.to_string()
+    } else {
+        format!(r"\mathrm{{{}}}", name)
+    }
+}
+
+/// Gets the `LaTeX` of an operator.
+fn latex_operator(name: &str) -> String {
+    match name {
+        "*" => r"\cdot".to_string(),
+        "mod" => r"\bmod".to_string(),
+        "&" => r"\mathbin{\&}".to_string(),
+        "<<" => r"\ll".to_string(),
+        ">>" => r"\gg".to_string(),
+        "~" => r"\lnot".to_string(),
+        "°" => r"^{\circ}".to_string(),
+        _ if name.chars().all(char::is_alphanumeric) => format!(r"\operatorname{{{}}}", name),
+        _ => name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::context::builder::{ContextBuilder, Pack};
+    use crate::function::Function;
+
+    fn format(expression: &str) -> String {
+        let formatter: LatexFormatter<i64> = LatexFormatter::new();
+        formatter.format_str(expression).unwrap()
+    }
+
+    #[test]
+    fn latex_test() {
+        assert_eq!(r"\frac{2 + 3}{4}", format("(2 + 3) / 4"));
+        assert_eq!(r"2 \cdot \frac{3}{4}", format("2 * (3 / 4)"));
+        assert_eq!(r"\left(2 + 3\right) \cdot 4", format("(2 + 3) * 4"));
+        assert_eq!(r"2^{2 + 1}", format("2 ^ (2 + 1)"));
+        assert_eq!(r"\left(2^{3}\right)^{4}", format("(2 ^ 3) ^ 4"));
+        assert_eq!(r"\sin\left(30^{\circ}\right)", format("sin(30°)"));
+        assert_eq!(r"-\left(2 + 3\right)", format("-(2 + 3)"));
+        assert_eq!(r"5! \bmod 2", format("5! mod 2"));
+        assert_eq!(r"\left(2 + 3\right)!", format("(2 + 3)!"));
+        assert_eq!(r"2 \cdot \pi + e", format("2 * PI + E"));
+    }
+
+    #[test]
+    fn latex_function_test() {
+        assert_eq!(r"\sin\left(30\right)", format("Sin(30)"));
+        assert_eq!(r"\arctan\left(1\right)", format("ATan(1)"));
+        assert_eq!(r"\sqrt{2}", format("sqrt(2)"));
+        assert_eq!(r"\left|-2\right|", format("abs(-2)"));
+        assert_eq!(r"\log_{2}\left(8\right)", format("log(8, 2)"));
+        assert_eq!(r"\max\left(1, 2, 3\right)", format("max(1, 2, 3)"));
+        assert_eq!(r"\operatorname{sum}\left(1, 2\right)", format("sum(1, 2)"));
+    }
+
+    #[test]
+    fn latex_custom_function_test() {
+        struct NthRoot;
+        impl Function<i64> for NthRoot {
+            fn name(&self) -> &str {
+                "nroot"
+            }
+
+            fn call(&self, _: &[i64]) -> Result<i64> {
+                Err(Error::new(ErrorKind::Other, "only used for formatting"))
+            }
+
+            fn to_latex(&self, args: &[String]) -> Option<String> {
+                Some(format!(r"\sqrt[{}]{{{}}}", args[1], args[0]))
+            }
+        }
+
+        let mut context = DefaultContext::new_checked();
+        context.add_function(NthRoot);
+
+        let formatter = LatexFormatter::with_context(&context);
+        assert_eq!(r"\sqrt[3]{27}", formatter.format_str("nroot(27, 3)").unwrap());
+    }
+
+    #[test]
+    fn latex_bitwise_test() {
+        let mut context = ContextBuilder::new()
+            .with_pack(Pack::Bitwise)
+            .build_checked_integer::<i64>();
+        context.set_variable("x", 5);
+        let formatter = LatexFormatter::with_context(&context);
+        assert_eq!(r"1 + 2 \ll 3", formatter.format_str("1 + 2 << 3").unwrap());
+        assert_eq!(r"6 \mathbin{\&} 3 | 8", formatter.format_str("6 & 3 | 8").unwrap());
+        assert_eq!(
+            r"\lnot x \operatorname{xor} \operatorname{not} 1",
+            formatter.format_str("~x xor not 1").unwrap()
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::ast::Expr;
+use crate::context::{Context, DefaultContext};
+use crate::formatter::{
+    function_name, greek_letter, is_fraction, needs_parens_left, needs_parens_postfix,
+    needs_parens_prefix, needs_parens_right, unary_notation, Format,
+};
+use crate::function::Notation;
+use crate::num::checked::CheckedNum;
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::Result;
+
+/// Converts expressions to presentation `MathML`.
+///
+/// The output is a `<math>` element in the `MathML` namespace, so it can be embedded in `HTML`
+/// or `XML` documents, operands are grouped using the `precedence`
+/// and `associativity` of the operators in the `Context`.
+///
+/// # Example
+/// ```
+/// use math_engine::formatter::mathml::MathmlFormatter;
+///
+/// let formatter : MathmlFormatter<i64> = MathmlFormatter::new();
+/// let mathml = formatter.format_str("1 / 2").unwrap();
+/// assert_eq!(
+///     r#"<math xmlns="http://www.w3.org/1998/Math/MathML"><mfrac><mn>1</mn><mn>2</mn></mfrac></math>"#,
+///     mathml
+/// );
+/// ```
+pub struct MathmlFormatter<'a, N, C = DefaultContext<'a, N>>
+where
+    C: Context<'a, N>,
+{
+    /// The context which contains the operators and functions used
+    /// for format and expression.
+    context: &'a C,
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N> MathmlFormatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    /// Constructs a new `MathmlFormatter` using the default checked context.
+    #[inline]
+    pub fn new() -> Self {
+        MathmlFormatter {
+            context: unsafe { DefaultContext::instance() },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, N> Default for MathmlFormatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, N, C> MathmlFormatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    /// Constructs a new `MathmlFormatter` with the given `Context`.
+    #[inline]
+    pub fn with_context(context: &'a C) -> Self {
+        MathmlFormatter {
+            context,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the `MathML` of the given tokens in `infix` notation.
+    pub fn format_tokens(&self, tokens: &[Token<N>]) -> Result<String>
+    where
+        N: Clone + Debug,
+    {
+        let expr = Expr::from_infix(tokens, self.context)?;
+        self.format(&expr)
+    }
+
+    /// Gets the `MathML` of the given expression.
+    pub fn format_str(&self, expression: &str) -> Result<String>
+    where
+        N: Clone + Debug + FromStr,
+    {
+        let tokens = Tokenizer::with_context(self.context).tokenize(expression)?;
+        self.format_tokens(&tokens)
+    }
+
+    /// Writes the expression as a single `MathML` element.
+    fn write_expr(&self, expr: &Expr<N>, output: &mut String) -> Result<()> {
+        let context = self.context;
+
+        match expr {
+            Expr::Number(n) => push_element(output, "mn", &n.to_string()),
+            Expr::Variable(name) | Expr::Constant(name) => push_element(output, "mi", &mathml_name(name)),
+            Expr::Function(name, args) => self.write_function(name, args, output)?,
+            Expr::BinaryOperator(name, left, right) => match name.as_str() {
+                "/" => {
+                    output.push_str("<mfrac>");
+                    self.write_expr(left, output)?;
+                    self.write_expr(right, output)?;
+                    output.push_str("</mfrac>");
+                }
+                "^" => {
+                    output.push_str("<msup>");
+                    self.write_grouped(left, needs_parens_prefix(context, left), output)?;
+                    self.write_expr(right, output)?;
+                    output.push_str("</msup>");
+                }
+                _ => {
+                    // Fractions don't need parentheses
+                    let left_parens = !is_fraction(left) && needs_parens_left(context, name, left)?;
+                    let right_parens = !is_fraction(right) && needs_parens_right(context, name, right)?;
+
+                    output.push_str("<mrow>");
+                    self.write_grouped(left, left_parens, output)?;
+                    push_element(output, "mo", mathml_operator(name));
+                    self.write_grouped(right, right_parens, output)?;
+                    output.push_str("</mrow>");
+                }
+            },
+            Expr::UnaryOperator(name, operand) => {
+                output.push_str("<mrow>");
+                match unary_notation(context, name)? {
+                    Notation::Prefix => {
+                        push_element(output, "mo", mathml_operator(name));
+                        self.write_grouped(operand, needs_parens_prefix(context, operand), output)?;
+                    }
+                    Notation::Postfix => {
+                        self.write_grouped(operand, needs_parens_postfix(context, operand)?, output)?;
+                        push_element(output, "mo", mathml_operator(name));
+                    }
+                }
+                output.push_str("</mrow>");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_function(&self, name: &str, args: &[Expr<N>], output: &mut String) -> Result<()> {
+        let func_name = function_name(self.context, name)?;
+
+        match (func_name.to_lowercase().as_str(), args) {
+            ("sqrt", [arg]) => {
+                output.push_str("<msqrt>");
+                self.write_expr(arg, output)?;
+                output.push_str("</msqrt>");
+            }
+            ("abs", [arg]) => {
+                output.push_str("<mrow><mo>|</mo>");
+                self.write_expr(arg, output)?;
+                output.push_str("<mo>|</mo></mrow>");
+            }
+            (lowercase, _) => {
+                let display_name = match lowercase {
+                    "asin" => "arcsin",
+                    "acos" => "arccos",
+                    "atan" => "arctan",
+                    _ => func_name.as_str(),
+                };
+
+                output.push_str("<mrow>");
+                push_element(output, "mi", display_name);
+                // Function application: U+2061
+                output.push_str("<mo>&#x2061;</mo><mrow><mo>(</mo>");
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        output.push_str("<mo>,</mo>");
+                    }
+                    self.write_expr(arg, output)?;
+                }
+                output.push_str("<mo>)</mo></mrow></mrow>");
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_grouped(&self, expr: &Expr<N>, parens: bool, output: &mut String) -> Result<()> {
+        if parens {
+            output.push_str("<mrow><mo>(</mo>");
+            self.write_expr(expr, output)?;
+            output.push_str("<mo>)</mo></mrow>");
+        } else {
+            self.write_expr(expr, output)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, N, C> Format<N> for MathmlFormatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    fn format(&self, expr: &Expr<N>) -> Result<String> {
+        let mut output = String::from(MATH_OPEN);
+        self.write_expr(expr, &mut output)?;
+        output.push_str("</math>");
+        Ok(output)
+    }
+}
+
+/// The root `<math>` element with the `MathML` namespace.
+const MATH_OPEN: &str = r#"<math xmlns="http://www.w3.org/1998/Math/MathML">"#;
+
+/// Pushes an element with the given text escaping the `XML` special characters.
+fn push_element(output: &mut String, tag: &str, text: &str) {
+    output.push_str(&format!("<{}>", tag));
+    for c in text.chars() {
+        match c {
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '&' => output.push_str("&amp;"),
+            '"' => output.push_str("&quot;"),
+            _ => output.push(c),
+        }
+    }
+    output.push_str(&format!("</{}>", tag));
+}
+
+/// Gets the `MathML` text of a variable or constant name.
+fn mathml_name(name: &str) -> String {
+    let lowercase = name.to_lowercase();
+    match greek_letter(&lowercase) {
+        Some(c) => c.to_string(),
+        // Euler's number
+        None if lowercase == "e" => lowercase,
+        None => name.to_string(),
+    }
+}
+
+/// Gets the `MathML` text of an operator.
+fn mathml_operator(name: &str) -> &str {
+    match name {
+        // Dot operator: U+22C5
+        "*" => "\u{22C5}",
+        // Minus sign: U+2212
+        "-" => "\u{2212}",
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(expression: &str) -> String {
+        let formatter: MathmlFormatter<i64> = MathmlFormatter::new();
+        let mathml = formatter.format_str(expression).unwrap();
+        assert!(mathml.starts_with(MATH_OPEN));
+        mathml[MATH_OPEN.len()..mathml.len() - "</math>".len()].to_string()
+    }
+
+    #[test]
+    fn mathml_test() {
+        assert_eq!("<mrow><mn>2</mn><mo>+</mo><mn>3</mn></mrow>", format("2 + 3"));
+        assert_eq!(
+            "<mrow><mrow><mo>(</mo><mrow><mn>2</mn><mo>+</mo><mn>3</mn></mrow><mo>)</mo></mrow><mo>⋅</mo><mn>4</mn></mrow>",
+            format("(2 + 3) * 4")
+        );
+        assert_eq!(
+            "<mfrac><mrow><mn>1</mn><mo>+</mo><mn>2</mn></mrow><mn>3</mn></mfrac>",
+            format("(1 + 2) / 3")
+        );
+        assert_eq!(
+            "<msup><mrow><mo>(</mo><mrow><mo>−</mo><mn>2</mn></mrow><mo>)</mo></mrow><mn>2</mn></msup>",
+            format("(-2) ^ 2")
+        );
+        assert_eq!("<msup><mi>π</mi><mn>2</mn></msup>", format("PI ^ 2"));
+        assert_eq!("<mrow><mn>5</mn><mo>!</mo></mrow>", format("5!"));
+    }
+
+    #[test]
+    fn mathml_function_test() {
+        assert_eq!("<msqrt><mn>4</mn></msqrt>", format("Sqrt(4)"));
+        assert_eq!(
+            "<mrow><mi>max</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mn>1</mn><mo>,</mo><mn>2</mn><mo>)</mo></mrow></mrow>",
+            format("MAX(1, 2)")
+        );
+        assert_eq!(
+            "<mrow><mi>arcsin</mi><mo>&#x2061;</mo><mrow><mo>(</mo><mn>1</mn><mo>)</mo></mrow></mrow>",
+            format("asin(1)")
+        );
+    }
+
+    #[test]
+    fn escape_test() {
+        let mut output = String::new();
+        push_element(&mut output, "mo", "<<");
+        assert_eq!("<mo>&lt;&lt;</mo>", output);
+    }
+}
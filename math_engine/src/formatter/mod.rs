@@ -0,0 +1,408 @@
+use std::fmt::{Debug, Display};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::ast::Expr;
+use crate::context::{Context, DefaultContext};
+use crate::error::{Error, ErrorKind};
+use crate::function::{Associativity, Notation, Precedence};
+use crate::num::checked::CheckedNum;
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::Result;
+
+pub mod latex;
+pub mod mathml;
+
+/// Provides a way to convert an expression to text.
+pub trait Format<N> {
+    /// Gets the text representation of the specified expression.
+    fn format(&self, expr: &Expr<N>) -> Result<String>;
+
+    /// Gets the text representation of the given tokens in `Reverse Polish Notation`.
+    fn format_rpn(&self, rpn: &[Token<N>]) -> Result<String>
+    where
+        N: Clone + Debug,
+    {
+        let expr = Expr::from_rpn(rpn)?;
+        self.format(&expr)
+    }
+}
+
+/// Converts expressions to its canonical `infix` form.
+///
+/// The output only contains the parentheses needed to keep the meaning of the expression,
+/// using the `precedence` and `associativity` of the operators in the `Context`,
+/// the whitespaces are normalized and the function names are written as they are declared.
+///
+/// # Example
+/// ```
+/// use math_engine::formatter::Formatter;
+///
+/// let formatter : Formatter<i64> = Formatter::new();
+/// let text = formatter.format_str("((2+3))*MAX( 4,5 )").unwrap();
+/// assert_eq!("(2 + 3) * max(4, 5)", text);
+/// ```
+pub struct Formatter<'a, N, C = DefaultContext<'a, N>>
+where
+    C: Context<'a, N>,
+{
+    /// The context which contains the operators and functions used
+    /// for format and expression.
+    context: &'a C,
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N> Formatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    /// Constructs a new `Formatter` using the default checked context.
+    #[inline]
+    pub fn new() -> Self {
+        Formatter {
+            context: unsafe { DefaultContext::instance() },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, N> Default for Formatter<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, N, C> Formatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    /// Constructs a new `Formatter` with the given `Context`.
+    #[inline]
+    pub fn with_context(context: &'a C) -> Self {
+        Formatter {
+            context,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Gets the canonical form of the given tokens in `infix` notation.
+    pub fn format_tokens(&self, tokens: &[Token<N>]) -> Result<String>
+    where
+        N: Clone + Debug,
+    {
+        let expr = Expr::from_infix(tokens, self.context)?;
+        self.format(&expr)
+    }
+
+    /// Gets the canonical form of the given expression.
+    pub fn format_str(&self, expression: &str) -> Result<String>
+    where
+        N: Clone + Debug + FromStr,
+    {
+        let tokens = Tokenizer::with_context(self.context).tokenize(expression)?;
+        self.format_tokens(&tokens)
+    }
+
+    fn write_expr(&self, expr: &Expr<N>, output: &mut String) -> Result<()> {
+        let context = self.context;
+
+        match expr {
+            Expr::Number(n) => output.push_str(&n.to_string()),
+            Expr::Variable(name) | Expr::Constant(name) => output.push_str(name),
+            Expr::Function(name, args) => {
+                output.push_str(&function_name(context, name)?);
+                output.push('(');
+                for (index, arg) in args.iter().enumerate() {
+                    if index > 0 {
+                        output.push_str(", ");
+                    }
+                    self.write_expr(arg, output)?;
+                }
+                output.push(')');
+            }
+            Expr::BinaryOperator(name, left, right) => {
+                self.write_grouped(left, needs_parens_left(context, name, left)?, output)?;
+                output.push(' ');
+                output.push_str(name);
+                output.push(' ');
+                self.write_grouped(right, needs_parens_right(context, name, right)?, output)?;
+            }
+            Expr::UnaryOperator(name, operand) => match unary_notation(context, name)? {
+                Notation::Prefix => {
+                    output.push_str(name);
+                    // Operators as `not` needs to be separated from its operand
+                    if name.ends_with(char::is_alphanumeric) {
+                        output.push(' ');
+                    }
+
+                    self.write_grouped(operand, needs_parens_prefix(context, operand), output)?;
+                }
+                Notation::Postfix => {
+                    self.write_grouped(operand, needs_parens_postfix(context, operand)?, output)?;
+                    output.push_str(name);
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    fn write_grouped(&self, expr: &Expr<N>, parens: bool, output: &mut String) -> Result<()> {
+        if parens {
+            output.push('(');
+            self.write_expr(expr, output)?;
+            output.push(')');
+        } else {
+            self.write_expr(expr, output)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a, N, C> Format<N> for Formatter<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: Display,
+{
+    fn format(&self, expr: &Expr<N>) -> Result<String> {
+        let mut output = String::new();
+        self.write_expr(expr, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// Greek letters and its unicode character, these are written as symbols
+/// when used as variable or constant names.
+const GREEK_LETTERS: [(&str, char); 24] = [
+    ("alpha", 'α'), ("beta", 'β'), ("gamma", 'γ'), ("delta", 'δ'), ("epsilon", 'ε'),
+    ("zeta", 'ζ'), ("eta", 'η'), ("theta", 'θ'), ("iota", 'ι'), ("kappa", 'κ'),
+    ("lambda", 'λ'), ("mu", 'μ'), ("nu", 'ν'), ("xi", 'ξ'), ("omicron", 'ο'),
+    ("pi", 'π'), ("rho", 'ρ'), ("sigma", 'σ'), ("tau", 'τ'), ("upsilon", 'υ'),
+    ("phi", 'φ'), ("chi", 'χ'), ("psi", 'ψ'), ("omega", 'ω'),
+];
+
+/// Gets the unicode character of the greek letter with the given lowercase name, eg: `pi`.
+pub(crate) fn greek_letter(name: &str) -> Option<char> {
+    GREEK_LETTERS
+        .iter()
+        .find(|(letter, _)| *letter == name)
+        .map(|(_, c)| *c)
+}
+
+/// Checks if the expression is a division, which is written as a fraction.
+#[inline]
+pub(crate) fn is_fraction<N>(expr: &Expr<N>) -> bool {
+    matches!(expr, Expr::BinaryOperator(name, _, _) if name == "/")
+}
+
+/// Checks if the left operand of the binary operator `op` must be within parentheses.
+///
+/// The left operand is grouped if have a lower precedence or if both have the same
+/// precedence but the left operand is right associative: `(2 ^ 3) ^ 4`.
+pub(crate) fn needs_parens_left<'a, N, C>(context: &C, op: &str, left: &Expr<N>) -> Result<bool>
+where
+    N: Display,
+    C: Context<'a, N>,
+{
+    let (precedence, _) = binary_operator_info(context, op)?;
+    match left {
+        Expr::BinaryOperator(name, _, _) => {
+            let (p, a) = binary_operator_info(context, name)?;
+            Ok(p < precedence || (p == precedence && a == Associativity::Right))
+        }
+        _ => Ok(needs_parens_as_operand(context, left)),
+    }
+}
+
+/// Checks if the right operand of the binary operator `op` must be within parentheses.
+///
+/// The right operand is grouped if have a lower precedence or if both have the same
+/// precedence and the operator is left associative: `10 - (2 - 3)`.
+pub(crate) fn needs_parens_right<'a, N, C>(context: &C, op: &str, right: &Expr<N>) -> Result<bool>
+where
+    N: Display,
+    C: Context<'a, N>,
+{
+    let (precedence, associativity) = binary_operator_info(context, op)?;
+    match right {
+        Expr::BinaryOperator(name, _, _) => {
+            let (p, _) = binary_operator_info(context, name)?;
+            Ok(precedence > p || (p == precedence && associativity == Associativity::Left))
+        }
+        _ => Ok(needs_parens_as_operand(context, right)),
+    }
+}
+
+/// Checks if the operand of a prefix unary operator must be within parentheses.
+pub(crate) fn needs_parens_prefix<'a, N, C>(context: &C, operand: &Expr<N>) -> bool
+where
+    N: Display,
+    C: Context<'a, N>,
+{
+    !operand.is_atom() || needs_parens_as_operand(context, operand)
+}
+
+/// Checks if the operand of a postfix unary operator must be within parentheses.
+pub(crate) fn needs_parens_postfix<'a, N, C>(context: &C, operand: &Expr<N>) -> Result<bool>
+where
+    N: Display,
+    C: Context<'a, N>,
+{
+    match operand {
+        Expr::Function(_, _) => Ok(false),
+        Expr::UnaryOperator(name, _) => Ok(unary_notation(context, name)? == Notation::Prefix),
+        _ => Ok(needs_parens_prefix(context, operand)),
+    }
+}
+
+/// Checks if an operand that is not a binary operation must be within parentheses.
+fn needs_parens_as_operand<'a, N, C>(context: &C, expr: &Expr<N>) -> bool
+where
+    N: Display,
+    C: Context<'a, N>,
+{
+    match expr {
+        // Numbers as `-2` or `3+2i` are grouped
+        Expr::Number(n) => !n
+            .to_string()
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.'),
+        // Prefix operators only bind to simple values as `-2` or `-x`
+        Expr::UnaryOperator(name, operand) => match unary_notation(context, name) {
+            Ok(Notation::Prefix) => needs_parens_prefix(context, operand),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+pub(crate) fn binary_operator_info<'a, N, C>(context: &C, name: &str) -> Result<(Precedence, Associativity)>
+where
+    C: Context<'a, N>,
+{
+    context
+        .get_binary_function(name)
+        .map(|f| (f.precedence(), f.associativity()))
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Binary operator `{}` not found", name),
+            )
+        })
+}
+
+pub(crate) fn unary_notation<'a, N, C>(context: &C, name: &str) -> Result<Notation>
+where
+    C: Context<'a, N>,
+{
+    context
+        .get_unary_function(name)
+        .map(|f| f.notation())
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                format!("Unary operator `{}` not found", name),
+            )
+        })
+}
+
+/// Gets the name of the function as is declared.
+pub(crate) fn function_name<'a, N, C>(context: &C, name: &str) -> Result<String>
+where
+    C: Context<'a, N>,
+{
+    match context.get_function(name) {
+        // Functions added with an alias keep the name used in the expression
+        Some(func) if func.name().eq_ignore_ascii_case(name) => Ok(func.name().to_string()),
+        Some(_) => Ok(name.to_string()),
+        None => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("Function `{}` not found", name),
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::Evaluator;
+
+    fn format(expression: &str) -> String {
+        let formatter: Formatter<i64> = Formatter::new();
+        formatter.format_str(expression).unwrap()
+    }
+
+    #[test]
+    fn format_test() {
+        assert_eq!("2 + 3 * 5", format("2+3*5"));
+        assert_eq!("(2 + 3) * 5", format("((2 + 3)) * 5"));
+        assert_eq!("2 ^ 3 ^ 4", format("2 ^ (3 ^ 4)"));
+        assert_eq!("(2 ^ 3) ^ 4", format("(2 ^ 3) ^ 4"));
+        assert_eq!("10 - 2 - 3", format("(10 - 2) - 3"));
+        assert_eq!("10 - (2 - 3)", format("10 - (2 - 3)"));
+        assert_eq!("-2 + 3", format("-2 + 3"));
+        assert_eq!("-(2 + 3)", format("-(2 + 3)"));
+        assert_eq!("(2 + 3)!", format("(2 + 3)!"));
+        assert_eq!("max(1, 2) mod 3", format("max(1,2) mod 3"));
+        assert_eq!("random()", format("Random()"));
+    }
+
+    #[test]
+    fn format_function_name_test() {
+        assert_eq!("sin(30)", format("SIN( 30 )"));
+        assert_eq!("max(1, -2, PI)", format("Max(1,-2,PI)"));
+    }
+
+    #[test]
+    fn format_rpn_test() {
+        let formatter: Formatter<i64> = Formatter::new();
+        let rpn = [
+            Token::Number(2),
+            Token::Number(3),
+            Token::BinaryOperator("+".to_string()),
+            Token::Number(4),
+            Token::BinaryOperator("*".to_string()),
+        ];
+
+        assert_eq!("(2 + 3) * 4", formatter.format_rpn(&rpn).unwrap());
+    }
+
+    #[test]
+    fn format_keeps_value_test() {
+        let expressions = [
+            "-(2 + 3) * 4",
+            "-(-(2))",
+            "(-2)! + 1",
+            "-(3!) * 2",
+            "2 ^ 3 ^ 2 - 10 mod 3",
+            "Max(2 - 3, -5, (1 + 2)!) / 2",
+        ];
+
+        let evaluator: Evaluator<i64> = Evaluator::new();
+        for expression in expressions.iter() {
+            let formatted = format(expression);
+            assert_eq!(
+                evaluator.eval(expression),
+                evaluator.eval(&formatted),
+                "`{}` formatted as `{}`",
+                expression,
+                formatted
+            );
+            assert_eq!(formatted, format(&formatted));
+        }
+    }
+
+    #[test]
+    fn format_error_test() {
+        let formatter: Formatter<i64> = Formatter::new();
+        let expr = Expr::Function("unknown".to_string(), vec![]);
+        assert!(formatter.format(&expr).is_err());
+    }
+}
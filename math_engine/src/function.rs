@@ -2,15 +2,25 @@ use crate::Result;
 use std::panic::RefUnwindSafe;
 
 /// A trait for a function that take a variable number of arguments.
-pub trait Function<N>: RefUnwindSafe {
+pub trait Function<N>: RefUnwindSafe + MaybeSync {
     /// Gets the name of the function.
     fn name(&self) -> &str;
     /// Calls this function with the given number of arguments.
     fn call(&self, args: &[N]) -> Result<N>;
+    /// Gets the `LaTeX` form of a call to this function, the given arguments are already
+    /// in `LaTeX`. Returns `None` to use the default form, by default `None`.
+    fn to_latex(&self, _args: &[String]) -> Option<String> {
+        None
+    }
+    /// Gets the source of a function defined by the user as: `name(params) = body`,
+    /// used to save the function as text. By default `None`.
+    fn source(&self) -> Option<String> {
+        None
+    }
 }
 
 /// A trait for a function that takes 2 arguments.
-pub trait BinaryFunction<N>: RefUnwindSafe {
+pub trait BinaryFunction<N>: RefUnwindSafe + MaybeSync {
     /// Gets the name of the function.
     fn name(&self) -> &str;
     /// Gets the `Precedence` of the function.
@@ -22,7 +32,7 @@ pub trait BinaryFunction<N>: RefUnwindSafe {
 }
 
 /// A trait a function that takes 1 argument.
-pub trait UnaryFunction<N>: RefUnwindSafe {
+pub trait UnaryFunction<N>: RefUnwindSafe + MaybeSync {
     /// Gets the name of the function.
     fn name(&self) -> &str;
     /// Gets the `Notation` of this function.
@@ -31,10 +41,27 @@ pub trait UnaryFunction<N>: RefUnwindSafe {
     fn call(&self, value: N) -> Result<N>;
 }
 
+/// Marker for the functions of a `Context`, which must be `Send + Sync` with the `sync` feature
+/// so the context can be shared between threads.
+#[cfg(feature = "sync")]
+pub trait MaybeSync: Send + Sync {}
+
+#[cfg(feature = "sync")]
+impl<T: Send + Sync + ?Sized> MaybeSync for T {}
+
+/// Marker for the functions of a `Context`, which must be `Send + Sync` with the `sync` feature
+/// so the context can be shared between threads.
+#[cfg(not(feature = "sync"))]
+pub trait MaybeSync {}
+
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> MaybeSync for T {}
+
 /// Represents the associativity of an operator.
 ///
 /// See: `https://en.wikipedia.org/wiki/Operator_associativity`
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Associativity {
     /// Left to right.
     Left,
@@ -46,6 +73,7 @@ pub enum Associativity {
 ///
 /// See: `https://en.wikipedia.org/wiki/Unary_operation`
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Notation {
     /// The operator is before the value, eg: -10.
     Prefix,
@@ -55,21 +83,25 @@ pub enum Notation {
 
 /// Represents the precedence of an operator.
 ///
+/// The constants are spaced by 10 so other precedences can be defined between them,
+/// eg: the bitwise operators are between `VERY_LOW` and `LOW`.
+///
 /// See: `https://en.wikipedia.org/wiki/Order_of_operations`
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Precedence(pub u32);
 
 impl Precedence {
     /// Very low precedence.
     pub const VERY_LOW: Precedence = Precedence::from(0);
     /// Low precedence, used for addition `(+)` and subtraction `(-)` operators.
-    pub const LOW: Precedence = Precedence::from(1);
+    pub const LOW: Precedence = Precedence::from(10);
     /// Medium precedence, used for multiplication `(*)` and division `(/)` operators.
-    pub const MEDIUM: Precedence = Precedence::from(2);
+    pub const MEDIUM: Precedence = Precedence::from(20);
     /// High precedence, used for power `(^)` operator.
-    pub const HIGH: Precedence = Precedence::from(3);
+    pub const HIGH: Precedence = Precedence::from(30);
     /// Very high precedence.
-    pub const VERY_HIGH: Precedence = Precedence::from(4);
+    pub const VERY_HIGH: Precedence = Precedence::from(40);
 
     /// Constructs a `Precedence` from the given value.
     #[inline]
@@ -84,3 +116,17 @@ impl Into<u32> for Precedence {
         self.0
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serde_test() {
+        let json = serde_json::to_string(&(Precedence::HIGH, Associativity::Right, Notation::Postfix)).unwrap();
+        assert_eq!("[30,\"Right\",\"Postfix\"]", json);
+
+        let value: (Precedence, Associativity, Notation) = serde_json::from_str(&json).unwrap();
+        assert_eq!((Precedence::HIGH, Associativity::Right, Notation::Postfix), value);
+    }
+}
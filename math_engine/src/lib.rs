@@ -1,6 +1,8 @@
+pub mod ast;
 pub mod context;
 pub mod error;
 pub mod evaluator;
+pub mod formatter;
 pub mod function;
 pub mod num;
 pub mod ops;
@@ -11,6 +13,16 @@ pub mod utils;
 /// An convenient result type used for returning the result of evaluations.
 pub type Result<T> = std::result::Result<T, error::Error>;
 
+/// The pointer used for share the functions of a `Context`,
+/// `Rc` by default or `Arc` with the `sync` feature.
+#[cfg(not(feature = "sync"))]
+pub type Shared<T> = std::rc::Rc<T>;
+
+/// The pointer used for share the functions of a `Context`,
+/// `Rc` by default or `Arc` with the `sync` feature.
+#[cfg(feature = "sync")]
+pub type Shared<T> = std::sync::Arc<T>;
+
 #[cfg(feature = "decimal")]
 pub mod decimal;
 
@@ -19,9 +31,13 @@ pub mod complex;
 
 /// Evaluates the specified math expression and gets the result as `Result<T>`.
 ///
+/// The expression is evaluated using the checked operations of `T`, so overflows and
+/// divisions by zero are returned as errors instead of `panic`.
+///
 /// # Remarks
 /// Type `T` must implement:
-/// - Basic numeric operations using: `Add`, `Sub`, `Mul`, `Div`, `Rem` and `Neg` traits.
+/// - Checked numeric operations using: `CheckedAdd`, `CheckedSub`, `CheckedMul`, `CheckedDiv`,
+///   `CheckedRem` and `CheckedNeg` traits.
 /// - Conversion from `0` and `1` with the traits `Zero` and `One`.
 /// - Conversion from and to primitive types by: `FromPrimitive` and `ToPrimitive`.
 /// - Ordering using: `PartialOrd`.
@@ -29,7 +45,8 @@ pub mod complex;
 /// - Provides display and debug using: `Debug` and `Display`
 /// - Provides cloning by: `Clone`
 ///
-/// Primitive types as: `i8`, `i16`, `i32`, `i64`, `i128`, `f32`, `f64` meet all those conditions.
+/// Primitive types as: `i8`, `i16`, `i32`, `i64`, `f32`, `f64` meet all those conditions,
+/// for types without checked operations as `BigDecimal` use [`eval_unchecked`].
 ///
 /// NOTE: Unsigned types don't implement `Neg`.
 ///
@@ -38,53 +55,92 @@ pub mod complex;
 ///
 /// # Example
 /// ```
+/// use math_engine::error::ErrorKind;
+///
 /// assert_eq!(Ok(17), math_engine::eval::<i32>("2 + 3 * 5"));
 /// assert_eq!(Ok(100_f32), math_engine::eval::<f32>("10^2"));
-/// assert!(math_engine::eval::<f64>("10/0").is_err())
+/// assert!(math_engine::eval::<f64>("10/0").is_err());
+/// assert_eq!(ErrorKind::Overflow, math_engine::eval::<i8>("100 + 100").unwrap_err().kind());
 /// ```
 ///
+/// [`eval_unchecked`]: fn.eval_unchecked.html
 /// [`DefaultContext`]: context/struct.DefaultContext.html
 /// [`Evaluator`]: evaluator/struct.Evaluator.html
 pub fn eval<T>(expression: &str) -> Result<T>
 where
-    T: num::unchecked::UncheckedNum + std::panic::RefUnwindSafe + std::panic::UnwindSafe + 'static,
+    T: num::checked::CheckedNum + 'static,
 {
-    use context::Config;
     use context::DefaultContext;
     use evaluator::Evaluator;
-    use std::panic::*;
     use crate::utils::static_store::StaticStore;
 
-    /// Allow to catch `panic`s without print error messages.
-    fn catch_panic<F: FnOnce() -> R + UnwindSafe, R>(
-        f: F,
-    ) -> std::result::Result<R, Box<dyn std::any::Any + Send + 'static>> {
-        let prev_hook = std::panic::take_hook();
-        set_hook(Box::new(|_| {}));
-        let result = std::panic::catch_unwind(f);
-        set_hook(prev_hook);
-        result
-    }
-
     // Holds the data of the evaluators
     static STATIC_EVALUATOR : StaticStore = StaticStore::new();
 
-    let result = catch_panic(move || {
-        let evaluator = STATIC_EVALUATOR.load(move || {
-            let config = Config::new()
-                .with_implicit_mul(true)
-                .with_group_symbol('[', ']');
+    let evaluator = STATIC_EVALUATOR.load(move || {
+        let context = DefaultContext::new_checked_with_config(eval_config());
+        let temp: Evaluator<T> = Evaluator::with_context(context);
+        temp
+    });
 
-            let context = DefaultContext::new_unchecked_with_config(config);
-            let temp: Evaluator<T> = Evaluator::with_context(context);
-            temp
-        });
+    evaluator.eval(expression)
+}
 
-        evaluator.eval(expression)
+/// Evaluates the specified math expression using the unchecked operations of `T`
+/// and gets the result as `Result<T>`.
+///
+/// Used for types which can't overflow and don't implement the checked operations,
+/// as `BigDecimal`. Divisions by zero are returned as errors, but an overflow will `panic`
+/// or wrap for primitive integers, so [`eval`] should be used for them.
+///
+/// # Example
+/// ```
+/// assert_eq!(Ok(17), math_engine::eval_unchecked::<i32>("2 + 3 * 5"));
+/// assert!(math_engine::eval_unchecked::<f64>("10/0").is_err());
+/// ```
+///
+/// [`eval`]: fn.eval.html
+pub fn eval_unchecked<T>(expression: &str) -> Result<T>
+where
+    T: num::unchecked::UncheckedNum + 'static,
+{
+    use context::DefaultContext;
+    use evaluator::Evaluator;
+    use crate::utils::static_store::StaticStore;
+
+    // Holds the data of the evaluators
+    static STATIC_EVALUATOR : StaticStore = StaticStore::new();
+
+    let evaluator = STATIC_EVALUATOR.load(move || {
+        let context = DefaultContext::new_unchecked_with_config(eval_config());
+        let temp: Evaluator<T> = Evaluator::with_context(context);
+        temp
     });
 
-    match result {
-        Ok(n) => n,
-        Err(_) => Err(error::Error::from(error::ErrorKind::Overflow)),
+    evaluator.eval(expression)
+}
+
+/// Gets the `Config` used by `eval` and `eval_unchecked`.
+fn eval_config() -> context::Config {
+    context::Config::new()
+        .with_implicit_mul(true)
+        .with_group_symbol('[', ']')
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ErrorKind;
+
+    #[test]
+    fn eval_overflow_test() {
+        fn overflow<T: crate::num::checked::CheckedNum + 'static>(expression: &str) {
+            assert_eq!(ErrorKind::Overflow, crate::eval::<T>(expression).unwrap_err().kind(), "{}", expression);
+        }
+
+        overflow::<i32>("2147483647 + 1");
+        overflow::<i8>("100 * 100");
+        overflow::<i64>("25!");
+        overflow::<i32>("(-2147483647 - 1) / -1");
+        overflow::<i64>("sum(9223372036854775807, 1)");
     }
 }
@@ -0,0 +1,232 @@
+//! Bitwise operators and functions, only available for primitive integer types.
+//!
+//! The bitwise operators have a lower precedence than the arithmetic operators, as in C:
+//! the shifts `<<` and `>>` are lower than `+`, then `&`, `xor` and `|`,
+//! so `1 + 2 << 3` is `(1 + 2) << 3` and `a | b xor c & d` is `a | (b xor (c & d))`.
+use num_traits::PrimInt;
+
+use crate::error::*;
+use crate::function::{
+    Associativity, BinaryFunction, Function, Notation, Precedence, UnaryFunction,
+};
+use crate::Result;
+
+macro_rules! impl_bitwise_operator {
+    ($struct_name:ident, $name:expr, $precedence:expr, |$left:ident, $right:ident| $body:expr) => {
+        pub struct $struct_name;
+        impl<N: PrimInt> BinaryFunction<N> for $struct_name {
+            #[inline]
+            fn name(&self) -> &str {
+                $name
+            }
+
+            #[inline]
+            fn precedence(&self) -> Precedence {
+                $precedence
+            }
+
+            #[inline]
+            fn associativity(&self) -> Associativity {
+                Associativity::Left
+            }
+
+            #[inline]
+            fn call(&self, $left: N, $right: N) -> Result<N> {
+                $body
+            }
+        }
+    };
+}
+
+/// The precedence of `<<` and `>>`, lower than `+`.
+const SHIFT_PRECEDENCE: Precedence = Precedence::from(8);
+/// The precedence of `&`, lower than the shifts.
+const AND_PRECEDENCE: Precedence = Precedence::from(7);
+/// The precedence of `xor`, lower than `&`.
+const XOR_PRECEDENCE: Precedence = Precedence::from(6);
+/// The precedence of `|`, lower than `xor`.
+const OR_PRECEDENCE: Precedence = Precedence::from(5);
+
+impl_bitwise_operator!(BitAndOperator, "&", AND_PRECEDENCE, |left, right| {
+    Ok(left & right)
+});
+impl_bitwise_operator!(BitOrOperator, "|", OR_PRECEDENCE, |left, right| {
+    Ok(left | right)
+});
+impl_bitwise_operator!(BitXorOperator, "xor", XOR_PRECEDENCE, |left, right| {
+    Ok(left ^ right)
+});
+impl_bitwise_operator!(ShlOperator, "<<", SHIFT_PRECEDENCE, |left, right| {
+    Ok(left << bit_index("<<", right)?)
+});
+// Signed integers use an arithmetic shift which keeps the sign
+impl_bitwise_operator!(ShrOperator, ">>", SHIFT_PRECEDENCE, |left, right| {
+    Ok(left >> bit_index(">>", right)?)
+});
+
+macro_rules! impl_not_operator {
+    ($struct_name:ident, $name:expr) => {
+        pub struct $struct_name;
+        impl<N: PrimInt> UnaryFunction<N> for $struct_name {
+            #[inline]
+            fn name(&self) -> &str {
+                $name
+            }
+
+            #[inline]
+            fn notation(&self) -> Notation {
+                Notation::Prefix
+            }
+
+            #[inline]
+            fn call(&self, value: N) -> Result<N> {
+                Ok(!value)
+            }
+        }
+    };
+}
+
+impl_not_operator!(BitNotOperator, "~");
+impl_not_operator!(NotOperator, "not");
+
+/// Gets the number of ones in the binary representation of the value: `popcount(x)`.
+pub struct PopCountFunction;
+impl<N: PrimInt> Function<N> for PopCountFunction {
+    fn name(&self) -> &str {
+        "popcount"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        match args {
+            [x] => N::from(x.count_ones()).ok_or(Error::from(ErrorKind::Overflow)),
+            _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        }
+    }
+}
+
+/// Gets the bit `n` of the value, where 0 is the least significant bit: `bit(x, n)`.
+pub struct BitFunction;
+impl<N: PrimInt> Function<N> for BitFunction {
+    fn name(&self) -> &str {
+        "bit"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        match args {
+            [x, n] => Ok((*x >> bit_index("bit", *n)?) & N::one()),
+            _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        }
+    }
+}
+
+/// Rotates the bits of the value `n` places to the left: `rotl(x, n)`.
+pub struct RotateLeftFunction;
+impl<N: PrimInt> Function<N> for RotateLeftFunction {
+    fn name(&self) -> &str {
+        "rotl"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        match args {
+            [x, n] => Ok(x.rotate_left(rotation("rotl", *n)?)),
+            _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        }
+    }
+}
+
+/// Rotates the bits of the value `n` places to the right: `rotr(x, n)`.
+pub struct RotateRightFunction;
+impl<N: PrimInt> Function<N> for RotateRightFunction {
+    fn name(&self) -> &str {
+        "rotr"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        match args {
+            [x, n] => Ok(x.rotate_right(rotation("rotr", *n)?)),
+            _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        }
+    }
+}
+
+/// Gets the number of bits of `N`.
+#[inline]
+fn bit_count<N: PrimInt>() -> u32 {
+    N::zero().count_zeros()
+}
+
+/// Gets the value as a bit position, which must be in `[0, bits)`.
+fn bit_index<N: PrimInt>(name: &str, n: N) -> Result<usize> {
+    let bits = bit_count::<N>();
+    match n.to_u32() {
+        Some(index) if index < bits => Ok(index as usize),
+        _ => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "`{}` expects a bit position in [0, {}) but was {}",
+                name,
+                bits,
+                n.to_i128().unwrap_or_default()
+            ),
+        )),
+    }
+}
+
+/// Gets the value as a number of places to rotate, which must be non-negative.
+fn rotation<N: PrimInt>(name: &str, n: N) -> Result<u32> {
+    if n < N::zero() {
+        return Err(Error::new(
+            ErrorKind::NegativeValue,
+            format!("`{}` expects a non-negative number of places", name),
+        ));
+    }
+
+    // Any rotation is equivalent to one in `[0, bits)`
+    let bits = N::from(bit_count::<N>()).ok_or(Error::from(ErrorKind::Overflow))?;
+    Ok((n % bits).to_u32().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitwise_operators_test() {
+        assert_eq!(Ok(0b1000), BitAndOperator.call(0b1100, 0b1010));
+        assert_eq!(Ok(0b1110), BitOrOperator.call(0b1100, 0b1010));
+        assert_eq!(Ok(0b0110), BitXorOperator.call(0b1100, 0b1010));
+        assert_eq!(Ok(-6_i64), BitNotOperator.call(5_i64));
+        assert_eq!(Ok(0xFFFF_FFFA_u32), NotOperator.call(5_u32));
+    }
+
+    #[test]
+    fn shift_test() {
+        assert_eq!(Ok(40), ShlOperator.call(5, 3));
+        assert_eq!(Ok(0x8000_0000_u32), ShlOperator.call(1_u32, 31));
+        assert_eq!(Ok(2), ShrOperator.call(17, 3));
+        assert_eq!(Ok(-2_i64), ShrOperator.call(-8_i64, 2));
+
+        assert!(ShlOperator.call(1_u32, 32).is_err());
+        assert!(ShlOperator.call(1_i64, 64).is_err());
+        assert!(ShrOperator.call(1_i64, -1).is_err());
+    }
+
+    #[test]
+    fn bit_functions_test() {
+        assert_eq!(Ok(3), PopCountFunction.call(&[0b1011_0000]));
+        assert_eq!(Ok(64), PopCountFunction.call(&[-1_i64]));
+        assert_eq!(Ok(1), BitFunction.call(&[0b100, 2]));
+        assert_eq!(Ok(0), BitFunction.call(&[0b100, 1]));
+        assert_eq!(Ok(1), BitFunction.call(&[-1_i64, 63]));
+        assert_eq!(
+            Ok(0x0000_0001_u32),
+            RotateLeftFunction.call(&[0x8000_0000_u32, 1])
+        );
+        assert_eq!(Ok(0x8000_0000_u32), RotateRightFunction.call(&[1_u32, 33]));
+        assert_eq!(Ok(0x1234_u32), RotateLeftFunction.call(&[0x1234_u32, 0]));
+
+        assert!(BitFunction.call(&[1_u32, 32]).is_err());
+        assert!(RotateLeftFunction.call(&[1, -1]).is_err());
+        assert!(PopCountFunction.call(&[1, 2]).is_err());
+    }
+}
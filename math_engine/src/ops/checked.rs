@@ -1,10 +1,16 @@
-use num_traits::{FromPrimitive, Zero};
+use std::fmt::Display;
+
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::error::*;
+use crate::evaluator::limits;
 use crate::function::{
     Associativity, BinaryFunction, Function, Notation, Precedence, UnaryFunction,
 };
 use crate::num::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedNeg, CheckedRem, CheckedSub};
+use crate::ops::integer::{integer_functions, Checked};
+use crate::ops::math::{percentile_rank, sorted_args};
+use crate::utils::gamma::gamma;
 use crate::Result;
 
 pub struct AddOperator;
@@ -115,6 +121,36 @@ impl<N: CheckedRem + Zero> BinaryFunction<N> for ModOperator {
     }
 }
 
+/// Raises a value to a power, integer types are exponentiated exactly by repeated squaring
+/// and other types using `f64::powf`.
+pub struct PowOperator;
+impl<N> BinaryFunction<N> for PowOperator
+where
+    N: CheckedMul + CheckedDiv + CheckedRem + CheckedSub + Zero + One + PartialOrd + ToPrimitive + FromPrimitive + Clone + Display,
+{
+    fn name(&self) -> &str {
+        "^"
+    }
+
+    fn precedence(&self) -> Precedence {
+        Precedence::HIGH
+    }
+
+    fn associativity(&self) -> Associativity {
+        Associativity::Right
+    }
+
+    fn call(&self, left: N, right: N) -> Result<N> {
+        if is_integer_type::<N>() {
+            checked_pow(left, right)
+        } else if let (Some(base), Some(exp)) = (left.to_f64(), right.to_f64()) {
+            N::from_f64(f64::powf(base, exp)).ok_or(Error::from(ErrorKind::Overflow))
+        } else {
+            Err(Error::from(ErrorKind::Overflow))
+        }
+    }
+}
+
 pub struct UnaryMinus;
 impl<N: CheckedNeg> UnaryFunction<N> for UnaryMinus {
     fn name(&self) -> &str {
@@ -217,7 +253,7 @@ impl<N: CheckedAdd + CheckedDiv + FromPrimitive + Clone> Function<N> for AvgFunc
         match sum {
             Some(n) => {
                 let result = n
-                    .checked_div(&N::from_usize(args.len()).unwrap())
+                    .checked_div(&N::from_usize(args.len()).ok_or(Error::from(ErrorKind::Overflow))?)
                     .ok_or(Error::from(ErrorKind::Overflow))?;
 
                 Ok(result)
@@ -227,6 +263,184 @@ impl<N: CheckedAdd + CheckedDiv + FromPrimitive + Clone> Function<N> for AvgFunc
     }
 }
 
+pub struct MedianFunction;
+impl<N> Function<N> for MedianFunction
+where
+    N: CheckedAdd + CheckedSub + CheckedDiv + FromPrimitive + PartialOrd + Clone,
+{
+    fn name(&self) -> &str {
+        "median"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        let sorted = sorted_args(args)?;
+        let mid = sorted.len() / 2;
+
+        if sorted.len() % 2 == 1 {
+            Ok(sorted[mid].clone())
+        } else {
+            // a + (b - a) / 2, with `a <= b` to avoid the overflow of `a + b`
+            let two = N::from_u8(2).ok_or(Error::from(ErrorKind::Overflow))?;
+            let (a, b) = (&sorted[mid - 1], &sorted[mid]);
+            b.checked_sub(a)
+                .and_then(|n| n.checked_div(&two))
+                .and_then(|n| a.checked_add(&n))
+                .ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+}
+
+/// Gets the percentile `p` in `[0, 100]` of the values: `percentile(p, ...)`,
+/// interpolating linearly between the closest ranks.
+pub struct PercentileFunction;
+impl<N> Function<N> for PercentileFunction
+where
+    N: CheckedAdd + CheckedSub + ToPrimitive + FromPrimitive + PartialOrd + Clone,
+{
+    fn name(&self) -> &str {
+        "percentile"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() < 2 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let sorted = sorted_args(&args[1..])?;
+        let (index, fraction) = percentile_rank(&args[0], sorted.len())?;
+
+        if fraction == 0.0 {
+            return Ok(sorted[index].clone());
+        }
+
+        let (a, b) = (&sorted[index], &sorted[index + 1]);
+        let delta = b.checked_sub(a)
+            .and_then(|n| n.to_f64())
+            .and_then(|n| N::from_f64(n * fraction))
+            .ok_or(Error::from(ErrorKind::Overflow))?;
+
+        a.checked_add(&delta).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+/// Gets the difference between the max and min values.
+pub struct RangeFunction;
+impl<N: CheckedSub + PartialOrd + Clone> Function<N> for RangeFunction {
+    fn name(&self) -> &str {
+        "range"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        let sorted = sorted_args(args)?;
+        sorted[sorted.len() - 1]
+            .checked_sub(&sorted[0])
+            .ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+pub struct Factorial;
+impl<N> UnaryFunction<N> for Factorial
+where
+    N: CheckedSub + CheckedMul + Zero + One + PartialOrd + ToPrimitive + FromPrimitive + Clone,
+{
+    fn name(&self) -> &str {
+        "!"
+    }
+
+    fn notation(&self) -> Notation {
+        Notation::Postfix
+    }
+
+    fn call(&self, value: N) -> Result<N> {
+        if value < N::zero() {
+            return Err(Error::from(ErrorKind::NegativeValue));
+        }
+
+        // Each multiplication is an step of the evaluation
+        limits::charge(value.to_usize().unwrap_or(usize::MAX))?;
+
+        let one = N::one();
+        let mut total = N::one();
+        let mut next = value;
+
+        while next >= one {
+            total = total.checked_mul(&next)
+                .filter(|n| !matches!(n.to_f64(), Some(x) if x.is_infinite()))
+                .ok_or(Error::from(ErrorKind::Overflow))?;
+            next = next.checked_sub(&one)
+                .ok_or(Error::from(ErrorKind::Overflow))?;
+        }
+
+        // If next value is non-zero, apply `Gamma function`.
+        if next.is_zero() {
+            Ok(total)
+        } else if let (Some(total_f64), Some(n)) = (total.to_f64(), next.to_f64()) {
+            N::from_f64(total_f64 * gamma(n + 1f64)).ok_or(Error::from(ErrorKind::Overflow))
+        } else {
+            Err(Error::from(ErrorKind::Overflow))
+        }
+    }
+}
+
+/// Checks if `N` is an integer type, where the division truncates.
+#[inline]
+fn is_integer_type<N: CheckedDiv + Zero + One + FromPrimitive>() -> bool {
+    match N::from_u8(2) {
+        Some(two) => matches!(N::one().checked_div(&two), Some(n) if n.is_zero()),
+        None => false,
+    }
+}
+
+/// Raises an integer to an integer power by repeated squaring, checking for overflow.
+fn checked_pow<N>(base: N, exponent: N) -> Result<N>
+where
+    N: CheckedMul + CheckedDiv + CheckedRem + CheckedSub + Zero + One + PartialOrd + FromPrimitive + Clone + Display,
+{
+    let one = N::one();
+    let two = N::from_u8(2).ok_or(Error::from(ErrorKind::Overflow))?;
+    let is_odd = |n: &N| matches!(n.checked_rem(&two), Some(r) if !r.is_zero());
+
+    if exponent < N::zero() {
+        // Only 1 and -1 have integer results with negative exponents
+        return if base.is_one() {
+            Ok(one)
+        } else if base.is_zero() {
+            Err(Error::from(ErrorKind::DivisionByZero))
+        } else if N::zero().checked_sub(&one) == Some(base.clone()) {
+            // (-1)^n is -1 for odd n and 1 for even n
+            if is_odd(&exponent) { Ok(base) } else { Ok(one) }
+        } else {
+            Err(Error::new(
+                ErrorKind::NegativeValue,
+                format!("Cannot raise {} to the negative exponent {}, the result is not an integer", base, exponent),
+            ))
+        };
+    }
+
+    let mut result = one;
+    let mut base = base;
+    let mut exponent = exponent;
+
+    while !exponent.is_zero() {
+        limits::charge(1)?;
+
+        if is_odd(&exponent) {
+            result = result.checked_mul(&base).ok_or(Error::from(ErrorKind::Overflow))?;
+        }
+
+        exponent = exponent.checked_div(&two).ok_or(Error::from(ErrorKind::Overflow))?;
+
+        // Avoids squaring the base after the last step, which may overflow
+        if !exponent.is_zero() {
+            base = base.checked_mul(&base).ok_or(Error::from(ErrorKind::Overflow))?;
+        }
+    }
+
+    Ok(result)
+}
+
+integer_functions!(Checked);
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -234,6 +448,23 @@ mod tests{
         vec![].into_boxed_slice()
     }
 
+    #[test]
+    fn factorial_test(){
+        let instance = Factorial;
+
+        assert_eq!(instance.call(0), Ok(1));
+        assert_eq!(instance.call(1), Ok(1));
+        assert_eq!(instance.call(5), Ok(120));
+        assert_eq!(instance.call(20_i64), Ok(2432902008176640000));
+        assert_eq!(instance.call(5_f64), Ok(120_f64));
+        assert!((instance.call(2.5_f64).unwrap() - 3.323350970447843).abs() < 0.000001);
+        assert_eq!(instance.call(21_i64), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(99999_i64), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(25_i64), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(171_f64), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(-1), Err(Error::from(ErrorKind::NegativeValue)));
+    }
+
     #[test]
     fn add_test(){
         let instance = AddOperator;
@@ -334,4 +565,147 @@ mod tests{
         assert!(instance.call(empty_array::<i64>().as_ref()).is_err());
         assert!(instance.call(&[i32::max_value(), 10, 20, 30]).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn median_test(){
+        let instance = MedianFunction;
+
+        assert_eq!(instance.call(&[3_f64, 1_f64, 2_f64]), Ok(2_f64));
+        assert_eq!(instance.call(&[4_f64, 1_f64, 2_f64, 3_f64]), Ok(2.5_f64));
+        assert_eq!(instance.call(&[i32::max_value(), i32::max_value() - 2]), Ok(i32::max_value() - 1));
+
+        assert!(instance.call(&[f64::NAN, 1_f64]).is_err());
+        assert!(instance.call(empty_array::<i64>().as_ref()).is_err());
+    }
+
+    #[test]
+    fn percentile_test(){
+        let instance = PercentileFunction;
+
+        assert_eq!(instance.call(&[50_f64, 4_f64, 1_f64, 3_f64, 2_f64]), Ok(2.5_f64));
+        assert_eq!(instance.call(&[0_f64, 4_f64, 1_f64, 3_f64]), Ok(1_f64));
+        assert_eq!(instance.call(&[100_f64, 4_f64, 1_f64, 3_f64]), Ok(4_f64));
+        assert_eq!(instance.call(&[25_f64, 10_f64, 20_f64, 30_f64]), Ok(15_f64));
+        assert_eq!(instance.call(&[50, 10, 30, 20]), Ok(20));
+
+        assert!(instance.call(&[101_f64, 1_f64]).is_err());
+        assert!(instance.call(&[-1_f64, 1_f64]).is_err());
+        assert!(instance.call(&[50_f64]).is_err());
+    }
+
+    #[test]
+    fn range_test(){
+        let instance = RangeFunction;
+
+        assert_eq!(instance.call(&[3_f64, -1_f64, 2_f64]), Ok(4_f64));
+        assert_eq!(instance.call(&[7]), Ok(0));
+
+        assert!(instance.call(&[i32::max_value(), -10]).is_err());
+        assert!(instance.call(empty_array::<i64>().as_ref()).is_err());
+    }
+
+    #[test]
+    fn gcd_lcm_test(){
+        assert_eq!(GcdFunction.call(&[12, 18]), Ok(6));
+        assert_eq!(GcdFunction.call(&[-12, 18, 8]), Ok(2));
+        assert_eq!(GcdFunction.call(&[0, 0]), Ok(0));
+        assert_eq!(LcmFunction.call(&[4, 6, 10]), Ok(60));
+        assert_eq!(LcmFunction.call(&[-4, 6]), Ok(12));
+        assert_eq!(LcmFunction.call(&[12_f64, 18_f64]), Ok(36_f64));
+
+        assert!(GcdFunction.call(&[i64::min_value(), 2]).is_err());
+        assert!(LcmFunction.call(&[i64::max_value(), i64::max_value() - 1]).is_err());
+        assert!(GcdFunction.call(&[2.5_f64, 5_f64]).is_err());
+        assert!(GcdFunction.call(&[2]).is_err());
+    }
+
+    #[test]
+    fn ncr_npr_test(){
+        assert_eq!(NcrFunction.call(&[5, 2]), Ok(10));
+        assert_eq!(NcrFunction.call(&[5, 6]), Ok(0));
+        assert_eq!(ChooseFunction.call(&[0, 0]), Ok(1));
+        // The result fits in i64 even if 62! doesn't
+        assert_eq!(NcrFunction.call(&[62_i64, 31]), Ok(465428353255261088));
+        assert_eq!(NprFunction.call(&[5, 2]), Ok(20));
+        assert_eq!(NprFunction.call(&[20_i64, 20]), Ok(2432902008176640000));
+
+        assert!(NcrFunction.call(&[70_i64, 35]).is_err());
+        assert!(NprFunction.call(&[21_i64, 21]).is_err());
+        assert!(NcrFunction.call(&[-5, 2]).is_err());
+    }
+
+    #[test]
+    fn prime_test(){
+        assert_eq!(IsPrimeFunction.call(&[97]), Ok(1));
+        assert_eq!(IsPrimeFunction.call(&[1]), Ok(0));
+        assert_eq!(IsPrimeFunction.call(&[-7]), Ok(0));
+        assert_eq!(IsPrimeFunction.call(&[1_000_000_007_i64]), Ok(1));
+        assert_eq!(NextPrimeFunction.call(&[-10]), Ok(2));
+        assert_eq!(NextPrimeFunction.call(&[13]), Ok(17));
+        assert_eq!(NextPrimeFunction.call(&[1_000_000_000_i64]), Ok(1_000_000_007));
+
+        assert!(NextPrimeFunction.call(&[i8::max_value()]).is_err());
+    }
+
+    #[test]
+    fn factor_test(){
+        assert_eq!(FactorCountFunction.call(&[360]), Ok(6));
+        assert_eq!(FactorCountFunction.call(&[1]), Ok(0));
+        assert_eq!(DivisorCountFunction.call(&[360]), Ok(24));
+        assert_eq!(DivisorCountFunction.call(&[1]), Ok(1));
+        assert_eq!(MinFactorFunction.call(&[91]), Ok(7));
+        assert_eq!(MinFactorFunction.call(&[997_i64 * 1_000_000_007]), Ok(997));
+
+        assert!(FactorCountFunction.call(&[0]).is_err());
+        assert!(DivisorCountFunction.call(&[-4]).is_err());
+    }
+
+    #[test]
+    fn fib_test(){
+        assert_eq!(FibFunction.call(&[0]), Ok(0));
+        assert_eq!(FibFunction.call(&[1]), Ok(1));
+        assert_eq!(FibFunction.call(&[10]), Ok(55));
+        assert_eq!(FibFunction.call(&[46_i32]), Ok(1836311903));
+        assert_eq!(FibFunction.call(&[30_f64]), Ok(832040_f64));
+
+        assert!(FibFunction.call(&[47_i32]).is_err());
+        assert!(FibFunction.call(&[-1]).is_err());
+    }
+
+    #[test]
+    fn modular_test(){
+        assert_eq!(ModPowFunction.call(&[4, 13, 497]), Ok(445));
+        assert_eq!(ModPowFunction.call(&[-2, 3, 5]), Ok(2));
+        assert_eq!(ModPowFunction.call(&[5, 0, 1]), Ok(0));
+        assert_eq!(ModPowFunction.call(&[2_i64, 1_000_000, i64::max_value()]), Ok(2_i64.pow(1_000_000 % 63)));
+        assert_eq!(ModInvFunction.call(&[3, 11]), Ok(4));
+        assert_eq!(ModInvFunction.call(&[-3, 11]), Ok(7));
+        assert_eq!(ModInvFunction.call(&[10_u32, 17]), Ok(12));
+
+        assert!(ModInvFunction.call(&[4, 8]).is_err());
+        assert!(ModPowFunction.call(&[2, -1, 5]).is_err());
+        assert!(ModPowFunction.call(&[2, 3, 0]).is_err());
+    }
+
+    #[test]
+    fn pow_test(){
+        let instance = PowOperator;
+
+        assert_eq!(instance.call(3_i64, 39), Ok(4052555153018976267));
+        assert_eq!(instance.call(2_i64, 62), Ok(1 << 62));
+        assert_eq!(instance.call(-2_i64, 63), Ok(i64::min_value()));
+        assert_eq!(instance.call(7, 0), Ok(1));
+        assert_eq!(instance.call(0, 0), Ok(1));
+        assert_eq!(instance.call(1, -5), Ok(1));
+        assert_eq!(instance.call(-1, -5), Ok(-1));
+        assert_eq!(instance.call(-1, -4), Ok(1));
+        assert_eq!(instance.call(2_f64, -1_f64), Ok(0.5_f64));
+        assert_eq!(instance.call(4_f64, 0.5_f64), Ok(2_f64));
+
+        assert_eq!(instance.call(2_i64, 63), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(3_i64, 40), Err(Error::from(ErrorKind::Overflow)));
+        assert_eq!(instance.call(0, -1), Err(Error::from(ErrorKind::DivisionByZero)));
+        assert_eq!(instance.call(2, -1).unwrap_err().kind(), ErrorKind::NegativeValue);
+        assert_eq!(instance.call(2_u8, 8), Err(Error::from(ErrorKind::Overflow)));
+    }
+}
@@ -0,0 +1,363 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::error::*;
+use crate::function::Function;
+use crate::ops::math::try_to_float;
+use crate::utils::gamma::ln_gamma;
+use crate::utils::special::{beta_inc, erfc, gamma_p, gamma_q, norm_inv};
+use crate::Result;
+
+/// Defines a function of float arguments, `$args` are the valid number of arguments
+/// and `$defaults` the values of the arguments not provided.
+macro_rules! distribution_function {
+    ($(#[$meta:meta])* $struct_name:ident, $name:expr, $args:expr, |$values:ident| $body:expr) => {
+        distribution_function!($(#[$meta])* $struct_name, $name, $args, [0.0; 3], |$values| $body);
+    };
+
+    ($(#[$meta:meta])* $struct_name:ident, $name:expr, $args:expr, $defaults:expr, |$values:ident| $body:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $struct_name;
+        impl<N: ToPrimitive + FromPrimitive> Function<N> for $struct_name {
+            #[inline]
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn call(&self, args: &[N]) -> Result<N> {
+                if !$args.contains(&args.len()) {
+                    return Err(Error::from(ErrorKind::InvalidArgumentCount));
+                }
+
+                let mut $values: [f64; 3] = $defaults;
+                for (value, arg) in $values.iter_mut().zip(args) {
+                    *value = try_to_float(arg)?;
+                }
+
+                let result: f64 = $body?;
+                N::from_f64(result).ok_or(Error::from(ErrorKind::Overflow))
+            }
+        }
+    };
+}
+
+distribution_function!(
+    /// Gets the probability density of a normal distribution: `normpdf(x)` with
+    /// mean 0 and standard deviation 1, or `normpdf(x, mu, sigma)`.
+    NormPdfFunction, "normpdf", [1, 3], [0.0, 0.0, 1.0], |values| {
+        normal_args("normpdf", &values).map(|(x, mean, std_dev)| {
+            let z = (x - mean) / std_dev;
+            f64::exp(-z * z / 2.0) / (std_dev * f64::sqrt(2.0 * PI))
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the cumulative probability of a normal distribution: `normcdf(x)` with
+    /// mean 0 and standard deviation 1, or `normcdf(x, mu, sigma)`.
+    NormCdfFunction, "normcdf", [1, 3], [0.0, 0.0, 1.0], |values| {
+        normal_args("normcdf", &values)
+            .map(|(x, mean, std_dev)| 0.5 * erfc(-(x - mean) / (std_dev * SQRT_2)))
+    }
+);
+
+distribution_function!(
+    /// Gets the value with the given cumulative probability in a normal distribution:
+    /// `norminv(p)` with mean 0 and standard deviation 1, or `norminv(p, mu, sigma)`.
+    NormInvFunction, "norminv", [1, 3], [0.0, 0.0, 1.0], |values| {
+        normal_args("norminv", &values).and_then(|(p, mean, std_dev)| {
+            check_probability("norminv", p)?;
+            Ok(mean + std_dev * norm_inv(p))
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability of `k` successes in `n` trials with probability `p`: `binompdf(n, p, k)`.
+    BinomPdfFunction, "binompdf", [3], |values| {
+        let [n, p, k] = values;
+        binomial_args("binompdf", n, p).map(|_| {
+            if k < 0.0 || k > n || k.fract() != 0.0 {
+                0.0
+            } else if p == 0.0 || p == 1.0 {
+                // Avoids the logarithm of 0
+                let certain = if p == 0.0 { 0.0 } else { n };
+                if k == certain { 1.0 } else { 0.0 }
+            } else {
+                f64::exp(ln_choose(n, k) + k * p.ln() + (n - k) * (1.0 - p).ln())
+            }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability of at most `k` successes in `n` trials with probability `p`:
+    /// `binomcdf(n, p, k)`.
+    BinomCdfFunction, "binomcdf", [3], |values| {
+        let [n, p, k] = values;
+        binomial_args("binomcdf", n, p).map(|_| {
+            let k = k.floor();
+            if k < 0.0 {
+                0.0
+            } else if k >= n {
+                1.0
+            } else if p == 0.0 || p == 1.0 {
+                1.0 - p
+            } else {
+                beta_inc(n - k, k + 1.0, 1.0 - p)
+            }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability of `k` events in a poisson distribution with mean `lambda`:
+    /// `poissonpdf(lambda, k)`.
+    PoissonPdfFunction, "poissonpdf", [2], |values| {
+        let [mean, k, _] = values;
+        check_positive("poissonpdf", "mean", mean).map(|_| {
+            if k < 0.0 || k.fract() != 0.0 {
+                0.0
+            } else {
+                f64::exp(k * mean.ln() - mean - ln_gamma(k + 1.0))
+            }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability of at most `k` events in a poisson distribution with mean `lambda`:
+    /// `poissoncdf(lambda, k)`.
+    PoissonCdfFunction, "poissoncdf", [2], |values| {
+        let [mean, k, _] = values;
+        check_positive("poissoncdf", "mean", mean).map(|_| {
+            let k = k.floor();
+            if k < 0.0 {
+                0.0
+            } else {
+                gamma_q(k + 1.0, mean)
+            }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability density of a student's t distribution with `df` degrees of freedom:
+    /// `tpdf(x, df)`.
+    TPdfFunction, "tpdf", [2], |values| {
+        let [x, df, _] = values;
+        check_positive("tpdf", "degrees of freedom", df).map(|_| {
+            let ln_norm = ln_gamma((df + 1.0) / 2.0) - ln_gamma(df / 2.0) - 0.5 * (df * PI).ln();
+            f64::exp(ln_norm - (df + 1.0) / 2.0 * (x * x / df).ln_1p())
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the cumulative probability of a student's t distribution with `df` degrees of freedom:
+    /// `tcdf(x, df)`.
+    TCdfFunction, "tcdf", [2], |values| {
+        let [x, df, _] = values;
+        check_positive("tcdf", "degrees of freedom", df).map(|_| {
+            let tail = 0.5 * beta_inc(df / 2.0, 0.5, df / (df + x * x));
+            if x > 0.0 { 1.0 - tail } else { tail }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability density of a chi-squared distribution with `k` degrees of freedom:
+    /// `chi2pdf(x, k)`.
+    Chi2PdfFunction, "chi2pdf", [2], |values| {
+        let [x, k, _] = values;
+        check_positive("chi2pdf", "degrees of freedom", k).map(|_| {
+            if x < 0.0 {
+                0.0
+            } else if x == 0.0 {
+                // The density at 0 is infinite for k < 2
+                if k < 2.0 {
+                    f64::INFINITY
+                } else if k == 2.0 {
+                    0.5
+                } else {
+                    0.0
+                }
+            } else {
+                let half = k / 2.0;
+                f64::exp((half - 1.0) * x.ln() - x / 2.0 - half * 2_f64.ln() - ln_gamma(half))
+            }
+        })
+    }
+);
+
+distribution_function!(
+    /// Gets the cumulative probability of a chi-squared distribution with `k` degrees of freedom:
+    /// `chi2cdf(x, k)`.
+    Chi2CdfFunction, "chi2cdf", [2], |values| {
+        let [x, k, _] = values;
+        check_positive("chi2cdf", "degrees of freedom", k)
+            .map(|_| if x <= 0.0 { 0.0 } else { gamma_p(k / 2.0, x / 2.0) })
+    }
+);
+
+distribution_function!(
+    /// Gets the probability density of an exponential distribution with the given rate:
+    /// `exppdf(x, lambda)`.
+    ExpPdfFunction, "exppdf", [2], |values| {
+        let [x, rate, _] = values;
+        check_positive("exppdf", "rate", rate)
+            .map(|_| if x < 0.0 { 0.0 } else { rate * f64::exp(-rate * x) })
+    }
+);
+
+distribution_function!(
+    /// Gets the cumulative probability of an exponential distribution with the given rate:
+    /// `expcdf(x, lambda)`.
+    ExpCdfFunction, "expcdf", [2], |values| {
+        let [x, rate, _] = values;
+        check_positive("expcdf", "rate", rate)
+            .map(|_| if x < 0.0 { 0.0 } else { -f64::exp_m1(-rate * x) })
+    }
+);
+
+/// Gets the value, mean and standard deviation of the arguments of a normal distribution function.
+fn normal_args(name: &str, values: &[f64; 3]) -> Result<(f64, f64, f64)> {
+    let [x, mean, std_dev] = *values;
+    check_positive(name, "standard deviation", std_dev)?;
+    Ok((x, mean, std_dev))
+}
+
+fn binomial_args(name: &str, n: f64, p: f64) -> Result<()> {
+    if n.is_sign_negative() || n.fract() != 0.0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects a non-negative integer number of trials but was {}", name, n),
+        ));
+    }
+
+    if !(0.0..=1.0).contains(&p) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects a probability in [0, 1] but was {}", name, p),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_positive(name: &str, arg: &str, value: f64) -> Result<()> {
+    if value <= 0.0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects a positive {} but was {}", name, arg, value),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check_probability(name: &str, p: f64) -> Result<()> {
+    if p <= 0.0 || p >= 1.0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects a probability in (0, 1) but was {}", name, p),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Computes the logarithm of the binomial coefficient `n choose k`.
+#[inline]
+fn ln_choose(n: f64, k: f64) -> f64 {
+    ln_gamma(n + 1.0) - ln_gamma(k + 1.0) - ln_gamma(n - k + 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_almost_eq(expected: f64, actual: Result<f64>, delta: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (expected - actual).abs() <= delta,
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    const ERROR: f64 = 0.000_000_000_01;
+
+    #[test]
+    fn normal_test() {
+        assert_almost_eq(0.398_942_280_401_432_7, NormPdfFunction.call(&[0.0]), ERROR);
+        assert_almost_eq(0.120_985_362_259_571_6, NormPdfFunction.call(&[3.0, 1.0, 2.0]), ERROR);
+        assert_almost_eq(0.975_002_104_851_779_6, NormCdfFunction.call(&[1.96]), ERROR);
+        assert_almost_eq(0.5, NormCdfFunction.call(&[10.0, 10.0, 3.0]), ERROR);
+        assert_almost_eq(1.959_963_984_540_054, NormInvFunction.call(&[0.975]), ERROR);
+        assert_almost_eq(13.0, NormInvFunction.call(&[0.841_344_746_068_542_9, 10.0, 3.0]), ERROR);
+
+        assert!(NormPdfFunction.call(&[1.0, 0.0, -1.0]).is_err());
+        assert!(NormCdfFunction.call(&[1.0, 0.0]).is_err());
+        assert!(NormInvFunction.call(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn binomial_test() {
+        assert_almost_eq(0.246_093_75, BinomPdfFunction.call(&[10.0, 0.5, 5.0]), ERROR);
+        assert_almost_eq(0.0, BinomPdfFunction.call(&[10.0, 0.5, 11.0]), ERROR);
+        assert_almost_eq(1.0, BinomPdfFunction.call(&[10.0, 1.0, 10.0]), ERROR);
+        assert_almost_eq(0.623_046_875, BinomCdfFunction.call(&[10.0, 0.5, 5.0]), ERROR);
+        assert_almost_eq(1.0, BinomCdfFunction.call(&[10.0, 0.3, 10.0]), ERROR);
+        assert_almost_eq(0.0, BinomCdfFunction.call(&[10.0, 0.3, -1.0]), ERROR);
+        assert_almost_eq(0.772_271_797_418_159_5, BinomCdfFunction.call(&[20.0, 0.3, 7.0]), ERROR);
+
+        assert!(BinomPdfFunction.call(&[2.5, 0.5, 1.0]).is_err());
+        assert!(BinomCdfFunction.call(&[10.0, 1.5, 1.0]).is_err());
+    }
+
+    #[test]
+    fn poisson_test() {
+        assert_almost_eq(0.224_041_807_655_387_8, PoissonPdfFunction.call(&[3.0, 2.0]), ERROR);
+        assert_almost_eq(0.0, PoissonPdfFunction.call(&[3.0, 1.5]), ERROR);
+        assert_almost_eq(0.423_190_081_126_843_5, PoissonCdfFunction.call(&[3.0, 2.0]), ERROR);
+
+        assert!(PoissonPdfFunction.call(&[0.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn student_t_test() {
+        // With 1 degree of freedom is the cauchy distribution
+        assert_almost_eq(1.0 / (2.0 * PI), TPdfFunction.call(&[1.0, 1.0]), ERROR);
+        assert_almost_eq(0.5 + f64::atan(2.0) / PI, TCdfFunction.call(&[2.0, 1.0]), ERROR);
+        assert_almost_eq(0.5 - f64::atan(2.0) / PI, TCdfFunction.call(&[-2.0, 1.0]), ERROR);
+        // With 2 degrees of freedom the cdf is `1/2 + x / (2 sqrt(2 + x²))`
+        assert_almost_eq(0.5 + 1.5 / (2.0 * f64::sqrt(4.25)), TCdfFunction.call(&[1.5, 2.0]), ERROR);
+        assert_almost_eq(0.5, TCdfFunction.call(&[0.0, 7.0]), ERROR);
+
+        assert!(TCdfFunction.call(&[1.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn chi_squared_test() {
+        // With 2 degrees of freedom is an exponential distribution with rate 1/2
+        assert_almost_eq(0.5 * f64::exp(-1.5), Chi2PdfFunction.call(&[3.0, 2.0]), ERROR);
+        assert_almost_eq(1.0 - f64::exp(-1.5), Chi2CdfFunction.call(&[3.0, 2.0]), ERROR);
+        assert_almost_eq(0.95, Chi2CdfFunction.call(&[3.841_458_820_694_124, 1.0]), ERROR);
+        assert_almost_eq(0.0, Chi2CdfFunction.call(&[-1.0, 3.0]), ERROR);
+
+        assert!(Chi2CdfFunction.call(&[1.0, -3.0]).is_err());
+    }
+
+    #[test]
+    fn exponential_test() {
+        assert_almost_eq(2.0 * f64::exp(-1.0), ExpPdfFunction.call(&[0.5, 2.0]), ERROR);
+        assert_almost_eq(1.0 - f64::exp(-1.0), ExpCdfFunction.call(&[0.5, 2.0]), ERROR);
+        assert_almost_eq(0.0, ExpCdfFunction.call(&[-0.5, 2.0]), ERROR);
+
+        assert!(ExpPdfFunction.call(&[1.0]).is_err());
+    }
+}
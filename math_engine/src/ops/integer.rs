@@ -0,0 +1,1032 @@
+use std::fmt::Display;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+use num_traits::{FromPrimitive, One, ToPrimitive, Zero};
+
+use crate::error::*;
+use crate::num::checked::{CheckedAdd, CheckedDiv, CheckedMul, CheckedRem, CheckedSub};
+use crate::Result;
+
+/// The operations used by the combinatorics and number theory functions,
+/// which operate on the integer values of `N` exactly.
+pub trait IntegerOps<N> {
+    /// Adds two values, returns `None` on overflow.
+    fn add(a: &N, b: &N) -> Option<N>;
+    /// Subtracts two values, returns `None` on overflow.
+    fn sub(a: &N, b: &N) -> Option<N>;
+    /// Multiplies two values, returns `None` on overflow.
+    fn mul(a: &N, b: &N) -> Option<N>;
+    /// Divides two values, returns `None` if the divisor is zero or on overflow.
+    fn div(a: &N, b: &N) -> Option<N>;
+    /// Gets the remainder of two values, returns `None` if the divisor is zero or on overflow.
+    fn rem(a: &N, b: &N) -> Option<N>;
+}
+
+/// Integer operations checked for overflow, used by the `ops::checked` functions.
+pub struct Checked;
+impl<N> IntegerOps<N> for Checked
+where
+    N: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv + CheckedRem,
+{
+    #[inline]
+    fn add(a: &N, b: &N) -> Option<N> {
+        a.checked_add(b)
+    }
+
+    #[inline]
+    fn sub(a: &N, b: &N) -> Option<N> {
+        a.checked_sub(b)
+    }
+
+    #[inline]
+    fn mul(a: &N, b: &N) -> Option<N> {
+        a.checked_mul(b)
+    }
+
+    #[inline]
+    fn div(a: &N, b: &N) -> Option<N> {
+        a.checked_div(b)
+    }
+
+    #[inline]
+    fn rem(a: &N, b: &N) -> Option<N> {
+        a.checked_rem(b)
+    }
+}
+
+/// Integer operations not checked for overflow, used by the `ops::unchecked` functions.
+///
+/// Only the division by zero is checked, except for the primitive integers which are computed
+/// as `i128` or `u128` so an overflow returns `None` instead of `panic` or wrap.
+pub struct Unchecked;
+impl<N> IntegerOps<N> for Unchecked
+where
+    N: Add<Output = N> + Sub<Output = N> + Mul<Output = N> + Div<Output = N> + Rem<Output = N>,
+    N: Zero + ToPrimitive + FromPrimitive + Clone,
+{
+    #[inline]
+    fn add(a: &N, b: &N) -> Option<N> {
+        if is_primitive_integer::<N>() {
+            return exact(a, b, i128::checked_add, u128::checked_add);
+        }
+
+        Some(a.clone() + b.clone())
+    }
+
+    #[inline]
+    fn sub(a: &N, b: &N) -> Option<N> {
+        if is_primitive_integer::<N>() {
+            return exact(a, b, i128::checked_sub, u128::checked_sub);
+        }
+
+        Some(a.clone() - b.clone())
+    }
+
+    #[inline]
+    fn mul(a: &N, b: &N) -> Option<N> {
+        if is_primitive_integer::<N>() {
+            return exact(a, b, i128::checked_mul, u128::checked_mul);
+        }
+
+        Some(a.clone() * b.clone())
+    }
+
+    #[inline]
+    fn div(a: &N, b: &N) -> Option<N> {
+        if is_primitive_integer::<N>() {
+            return exact(a, b, i128::checked_div, u128::checked_div);
+        }
+
+        if b.is_zero() {
+            None
+        } else {
+            Some(a.clone() / b.clone())
+        }
+    }
+
+    #[inline]
+    fn rem(a: &N, b: &N) -> Option<N> {
+        if is_primitive_integer::<N>() {
+            return exact(a, b, i128::checked_rem, u128::checked_rem);
+        }
+
+        if b.is_zero() {
+            None
+        } else {
+            Some(a.clone() % b.clone())
+        }
+    }
+}
+
+/// Checks if `N` is a primitive integer: can't represent `f64::MAX` and truncates `0.5`.
+#[inline]
+fn is_primitive_integer<N: Zero + FromPrimitive>() -> bool {
+    N::from_f64(f64::MAX).is_none() && N::from_f64(0.5).is_some_and(|n| n.is_zero())
+}
+
+/// Computes the operation as `i128` or as `u128` for the values greater than `i128::MAX`,
+/// returns `None` if the operation overflows or the result doesn't fit in `N`.
+#[inline]
+fn exact<N>(
+    a: &N,
+    b: &N,
+    signed: fn(i128, i128) -> Option<i128>,
+    unsigned: fn(u128, u128) -> Option<u128>,
+) -> Option<N>
+where
+    N: ToPrimitive + FromPrimitive,
+{
+    match (a.to_i128(), b.to_i128()) {
+        (Some(a), Some(b)) => signed(a, b).and_then(N::from_i128),
+        _ => unsigned(a.to_u128()?, b.to_u128()?).and_then(N::from_u128),
+    }
+}
+
+/// Defines the combinatorics and number theory functions using the given `IntegerOps`.
+macro_rules! integer_functions {
+    (@function $ops:ty; $(#[$meta:meta])* $struct_name:ident, $name:expr, |$args:ident| $body:expr) => {
+        $(#[$meta])*
+        pub struct $struct_name;
+        impl<N> Function<N> for $struct_name
+        where
+            N: num_traits::Zero
+                + num_traits::One
+                + num_traits::FromPrimitive
+                + PartialOrd
+                + Clone
+                + std::fmt::Display,
+            $ops: $crate::ops::integer::IntegerOps<N>,
+        {
+            #[inline]
+            fn name(&self) -> &str {
+                $name
+            }
+
+            #[inline]
+            fn call(&self, $args: &[N]) -> Result<N> {
+                $body
+            }
+        }
+    };
+
+    ($ops:ty) => {
+        integer_functions!(@function $ops;
+            /// Gets the greatest common divisor of the values: `gcd(a, b, ...)`.
+            GcdFunction, "gcd", |args| $crate::ops::integer::gcd_all::<N, $ops>(args)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the least common multiple of the values: `lcm(a, b, ...)`.
+            LcmFunction, "lcm", |args| $crate::ops::integer::lcm_all::<N, $ops>(args)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the number of combinations of `k` elements from `n`: `ncr(n, k)`.
+            NcrFunction, "ncr", |args| $crate::ops::integer::call_2(args, $crate::ops::integer::ncr::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the number of combinations of `k` elements from `n`: `choose(n, k)`, same as `ncr`.
+            ChooseFunction, "choose", |args| $crate::ops::integer::call_2(args, $crate::ops::integer::ncr::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the number of permutations of `k` elements from `n`: `npr(n, k)`.
+            NprFunction, "npr", |args| $crate::ops::integer::call_2(args, $crate::ops::integer::npr::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Returns `1` if the value is a prime number, otherwise `0`: `isprime(n)`.
+            ///
+            /// The test is only exact for values less than `3317044064679887385961981`, greater values are an error.
+            IsPrimeFunction, "isprime", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::is_prime_value::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the smallest prime number greater than the value: `nextprime(n)`.
+            ///
+            /// Returns an error if the next prime is not less than `3317044064679887385961981`, see `isprime`.
+            NextPrimeFunction, "nextprime", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::next_prime::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the number of prime factors of the value, counting repeated factors: `factorcount(n)`.
+            ///
+            /// Returns an error if a factor greater than 41 is not less than `3317044064679887385961981`, see `isprime`.
+            FactorCountFunction, "factorcount", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::factor_count::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the number of positive divisors of the value: `divisorcount(n)`.
+            ///
+            /// Returns an error if a factor greater than 41 is not less than `3317044064679887385961981`, see `isprime`.
+            DivisorCountFunction, "divisorcount", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::divisor_count::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the smallest prime factor of the value, or `1` for `1`: `minfactor(n)`.
+            MinFactorFunction, "minfactor", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::min_factor::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the nth fibonacci number: `fib(n)`.
+            FibFunction, "fib", |args| $crate::ops::integer::call_1(args, $crate::ops::integer::fib::<N, $ops>)
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets `base^exponent mod m`: `modpow(base, exponent, m)`.
+            ModPowFunction, "modpow", |args| {
+                match args {
+                    [base, exponent, m] => $crate::ops::integer::mod_pow::<N, $ops>(base, exponent, m),
+                    _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+                }
+            }
+        );
+
+        integer_functions!(@function $ops;
+            /// Gets the modular multiplicative inverse of `a` modulo `m`: `modinv(a, m)`.
+            ModInvFunction, "modinv", |args| $crate::ops::integer::call_2(args, $crate::ops::integer::mod_inv::<N, $ops>)
+        );
+    };
+}
+
+pub(crate) use integer_functions;
+
+/// The primes used for the trial division and as the bases of the Miller-Rabin test.
+const SMALL_PRIMES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+/// The Miller-Rabin test using `SMALL_PRIMES` as bases is exact for values less than this.
+const MAX_PRIME_TEST: u128 = 3_317_044_064_679_887_385_961_981;
+
+#[inline]
+fn overflow() -> Error {
+    Error::from(ErrorKind::Overflow)
+}
+
+#[inline]
+fn constant<N: FromPrimitive>(n: u32) -> Result<N> {
+    N::from_u32(n).ok_or_else(overflow)
+}
+
+#[doc(hidden)]
+pub fn call_1<N, F>(args: &[N], f: F) -> Result<N>
+where
+    F: FnOnce(&N) -> Result<N>,
+{
+    match args {
+        [n] => f(n),
+        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+    }
+}
+
+#[doc(hidden)]
+pub fn call_2<N, F>(args: &[N], f: F) -> Result<N>
+where
+    F: FnOnce(&N, &N) -> Result<N>,
+{
+    match args {
+        [a, b] => f(a, b),
+        _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+    }
+}
+
+/// Checks the value is an integer which can be represented exactly, floating point types
+/// can't represent all the integers greater than `2^53`.
+fn check_integer<N, O>(name: &str, n: &N) -> Result<()>
+where
+    N: Zero + One + PartialEq + Display,
+    O: IntegerOps<N>,
+{
+    match O::rem(n, &N::one()) {
+        Some(ref r) if r.is_zero() => {}
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`{}` expects integers but was {}", name, n),
+            ))
+        }
+    }
+
+    // An overflow of `n + 1` is fine, only integer types overflow at the max value
+    match O::add(n, &N::one()).map(|next| O::sub(&next, n)) {
+        Some(Some(ref diff)) if !diff.is_one() => Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects integers that can be represented exactly but was {}", name, n),
+        )),
+        _ => Ok(()),
+    }
+}
+
+/// Adds one to the value, returns an overflow error if the result is not exact.
+fn increment<N, O>(n: &N) -> Result<N>
+where
+    N: One + PartialEq,
+    O: IntegerOps<N>,
+{
+    let next = O::add(n, &N::one()).ok_or_else(overflow)?;
+    match O::sub(&next, n) {
+        Some(ref diff) if diff.is_one() => Ok(next),
+        _ => Err(overflow()),
+    }
+}
+
+/// Checks the value is not infinite, floating point types don't overflow using unchecked operations.
+fn check_finite<N, O>(n: N) -> Result<N>
+where
+    N: Zero + PartialEq,
+    O: IntegerOps<N>,
+{
+    // Only zero and the infinity are equal to its double
+    match O::add(&n, &n) {
+        Some(ref double) if *double == n && !n.is_zero() => Err(overflow()),
+        _ => Ok(n),
+    }
+}
+
+/// Checks the value is an integer greater or equals than `min`.
+fn check_integer_min<N, O>(name: &str, n: &N, min: u32) -> Result<()>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Display,
+    O: IntegerOps<N>,
+{
+    check_integer::<N, O>(name, n)?;
+    if *n < constant(min)? {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects an integer greater or equals than {} but was {}", name, min, n),
+        ));
+    }
+
+    Ok(())
+}
+
+fn abs<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    if *n < N::zero() {
+        O::sub(&N::zero(), n).ok_or_else(overflow)
+    } else {
+        Ok(n.clone())
+    }
+}
+
+/// Divides `n` by 2 rounding down, `n` must be a non-negative integer.
+fn halve<N, O>(n: &N) -> Result<(N, bool)>
+where
+    N: Zero + One + FromPrimitive + PartialEq + Clone,
+    O: IntegerOps<N>,
+{
+    let two = constant(2)?;
+    let odd = !O::rem(n, &two).ok_or_else(overflow)?.is_zero();
+    let even = if odd {
+        O::sub(n, &N::one()).ok_or_else(overflow)?
+    } else {
+        n.clone()
+    };
+
+    // Subtracts the remainder first so the division is exact for floating point types
+    Ok((O::div(&even, &two).ok_or_else(overflow)?, odd))
+}
+
+fn gcd<N, O>(a: &N, b: &N) -> Result<N>
+where
+    N: Zero + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    let mut a = abs::<N, O>(a)?;
+    let mut b = abs::<N, O>(b)?;
+
+    while !b.is_zero() {
+        let r = O::rem(&a, &b).ok_or_else(overflow)?;
+        a = b;
+        b = r;
+    }
+
+    Ok(a)
+}
+
+fn lcm<N, O>(a: &N, b: &N) -> Result<N>
+where
+    N: Zero + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    if a.is_zero() || b.is_zero() {
+        return Ok(N::zero());
+    }
+
+    // Divides first to avoid the overflow of `a * b`
+    let g = gcd::<N, O>(a, b)?;
+    let n = O::div(a, &g).and_then(|n| O::mul(&n, b)).ok_or_else(overflow)?;
+    abs::<N, O>(&n)
+}
+
+#[doc(hidden)]
+pub fn gcd_all<N, O>(args: &[N]) -> Result<N>
+where
+    N: Zero + One + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    fold_integers::<N, O, _>("gcd", args, gcd::<N, O>)
+}
+
+#[doc(hidden)]
+pub fn lcm_all<N, O>(args: &[N]) -> Result<N>
+where
+    N: Zero + One + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    fold_integers::<N, O, _>("lcm", args, lcm::<N, O>)
+}
+
+fn fold_integers<N, O, F>(name: &str, args: &[N], f: F) -> Result<N>
+where
+    N: Zero + One + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+    F: Fn(&N, &N) -> Result<N>,
+{
+    if args.len() < 2 {
+        return Err(Error::from(ErrorKind::InvalidArgumentCount));
+    }
+
+    for n in args {
+        check_integer::<N, O>(name, n)?;
+    }
+
+    let mut result = abs::<N, O>(&args[0])?;
+    for n in &args[1..] {
+        result = f(&result, n)?;
+    }
+
+    Ok(result)
+}
+
+#[doc(hidden)]
+pub fn ncr<N, O>(n: &N, k: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer_min::<N, O>("ncr", n, 0)?;
+    check_integer_min::<N, O>("ncr", k, 0)?;
+
+    if k > n {
+        return Ok(N::zero());
+    }
+
+    // C(n, k) = C(n, n - k)
+    let n_minus_k = O::sub(n, k).ok_or_else(overflow)?;
+    let k = if n_minus_k < *k { n_minus_k.clone() } else { k.clone() };
+    let start = O::sub(n, &k).ok_or_else(overflow)?;
+
+    let mut result = N::one();
+    let mut i = N::one();
+
+    while i <= k {
+        // result * (start + i) / i is exact, divides by the gcd first
+        // to only overflow if the next value overflows
+        let g = gcd::<N, O>(&result, &i)?;
+        let factor = O::add(&start, &i)
+            .and_then(|n| O::div(&i, &g).and_then(|d| O::div(&n, &d)))
+            .ok_or_else(overflow)?;
+
+        result = O::div(&result, &g)
+            .and_then(|n| O::mul(&n, &factor))
+            .ok_or_else(overflow)
+            .and_then(check_finite::<N, O>)?;
+
+        i = O::add(&i, &N::one()).ok_or_else(overflow)?;
+    }
+
+    Ok(result)
+}
+
+#[doc(hidden)]
+pub fn npr<N, O>(n: &N, k: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer_min::<N, O>("npr", n, 0)?;
+    check_integer_min::<N, O>("npr", k, 0)?;
+
+    if k > n {
+        return Ok(N::zero());
+    }
+
+    // n * (n - 1) * ... * (n - k + 1)
+    let mut result = N::one();
+    let mut i = O::sub(n, k).ok_or_else(overflow)?;
+
+    while i < *n {
+        i = O::add(&i, &N::one()).ok_or_else(overflow)?;
+        result = O::mul(&result, &i)
+            .ok_or_else(overflow)
+            .and_then(check_finite::<N, O>)?;
+    }
+
+    Ok(result)
+}
+
+/// Computes `(a + b) mod m` for `a` and `b` in `[0, m)` without overflow.
+fn add_mod<N, O>(a: &N, b: &N, m: &N) -> Result<N>
+where
+    N: PartialOrd,
+    O: IntegerOps<N>,
+{
+    let rest = O::sub(m, b).ok_or_else(overflow)?;
+    if *a >= rest {
+        O::sub(a, &rest)
+    } else {
+        O::add(a, b)
+    }
+    .ok_or_else(overflow)
+}
+
+/// Computes `(a * b) mod m` for `a` and `b` in `[0, m)` without overflow,
+/// using additions instead of the product.
+fn mul_mod<N, O>(a: &N, b: &N, m: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    let mut result = N::zero();
+    let mut a = a.clone();
+    let mut b = b.clone();
+
+    while !b.is_zero() {
+        let (half, odd) = halve::<N, O>(&b)?;
+        if odd {
+            result = add_mod::<N, O>(&result, &a, m)?;
+        }
+
+        a = add_mod::<N, O>(&a, &a, m)?;
+        b = half;
+    }
+
+    Ok(result)
+}
+
+/// Computes `base^exponent mod m` for `base` in `[0, m)` and a non-negative `exponent`.
+fn pow_mod<N, O>(base: &N, exponent: &N, m: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    let mut result = O::rem(&N::one(), m).ok_or_else(overflow)?;
+    let mut base = base.clone();
+    let mut exponent = exponent.clone();
+
+    while !exponent.is_zero() {
+        let (half, odd) = halve::<N, O>(&exponent)?;
+        if odd {
+            result = mul_mod::<N, O>(&result, &base, m)?;
+        }
+
+        base = mul_mod::<N, O>(&base, &base, m)?;
+        exponent = half;
+    }
+
+    Ok(result)
+}
+
+/// Gets the value of `n mod m` in `[0, m)` for a positive `m`.
+fn normalize_mod<N, O>(n: &N, m: &N) -> Result<N>
+where
+    N: Zero + PartialOrd,
+    O: IntegerOps<N>,
+{
+    let r = O::rem(n, m).ok_or_else(overflow)?;
+    if r < N::zero() {
+        O::add(&r, m).ok_or_else(overflow)
+    } else {
+        Ok(r)
+    }
+}
+
+#[doc(hidden)]
+pub fn mod_pow<N, O>(base: &N, exponent: &N, m: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer::<N, O>("modpow", base)?;
+    check_integer_min::<N, O>("modpow", exponent, 0)?;
+    check_integer_min::<N, O>("modpow", m, 1)?;
+
+    let base = normalize_mod::<N, O>(base, m)?;
+    pow_mod::<N, O>(&base, exponent, m)
+}
+
+#[doc(hidden)]
+pub fn mod_inv<N, O>(a: &N, m: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer::<N, O>("modinv", a)?;
+    check_integer_min::<N, O>("modinv", m, 1)?;
+
+    // Extended euclidean algorithm, the coefficients are kept in `[0, m)`
+    // so unsigned types don't underflow
+    let (mut r0, mut r1) = (m.clone(), normalize_mod::<N, O>(a, m)?);
+    let (mut t0, mut t1) = (N::zero(), O::rem(&N::one(), m).ok_or_else(overflow)?);
+
+    while !r1.is_zero() {
+        let r = O::rem(&r0, &r1).ok_or_else(overflow)?;
+        let q = O::sub(&r0, &r)
+            .and_then(|n| O::div(&n, &r1))
+            .and_then(|n| O::rem(&n, m))
+            .ok_or_else(overflow)?;
+
+        let qt = mul_mod::<N, O>(&q, &t1, m)?;
+        let t = if t0 >= qt {
+            O::sub(&t0, &qt)
+        } else {
+            O::sub(&qt, &t0).and_then(|n| O::sub(m, &n))
+        }
+        .ok_or_else(overflow)?;
+
+        r0 = std::mem::replace(&mut r1, r);
+        t0 = std::mem::replace(&mut t1, t);
+    }
+
+    if !(r0.is_one() || m.is_one()) {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`modinv`: {} has no inverse modulo {}", a, m),
+        ));
+    }
+
+    Ok(t0)
+}
+
+/// Checks if a non-negative integer is prime using the Miller-Rabin test,
+/// returns an error for values not less than `MAX_PRIME_TEST` where the result is not exact.
+fn is_prime<N, O>(name: &str, n: &N) -> Result<bool>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    if *n < constant(2)? {
+        return Ok(false);
+    }
+
+    // Types which can't represent the bound are always in range
+    if let Some(max) = N::from_u128(MAX_PRIME_TEST) {
+        if *n >= max {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "`{}` can only test primes less than {} but was {}",
+                    name, MAX_PRIME_TEST, n
+                ),
+            ));
+        }
+    }
+
+    for p in SMALL_PRIMES.iter() {
+        let p = constant::<N>(*p)?;
+        if *n == p {
+            return Ok(true);
+        }
+
+        if O::rem(n, &p).ok_or_else(overflow)?.is_zero() {
+            return Ok(false);
+        }
+    }
+
+    // n - 1 = d * 2^s
+    let n_minus_1 = O::sub(n, &N::one()).ok_or_else(overflow)?;
+    let mut d = n_minus_1.clone();
+    let mut s = 0;
+
+    loop {
+        let (half, odd) = halve::<N, O>(&d)?;
+        if odd {
+            break;
+        }
+
+        d = half;
+        s += 1;
+    }
+
+    'bases: for base in SMALL_PRIMES.iter() {
+        let mut x = pow_mod::<N, O>(&constant(*base)?, &d, n)?;
+        if x.is_one() || x == n_minus_1 {
+            continue;
+        }
+
+        for _ in 1..s {
+            x = mul_mod::<N, O>(&x, &x, n)?;
+            if x == n_minus_1 {
+                continue 'bases;
+            }
+        }
+
+        return Ok(false);
+    }
+
+    Ok(true)
+}
+
+#[doc(hidden)]
+pub fn is_prime_value<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer::<N, O>("isprime", n)?;
+
+    if is_prime::<N, O>("isprime", n)? {
+        Ok(N::one())
+    } else {
+        Ok(N::zero())
+    }
+}
+
+#[doc(hidden)]
+pub fn next_prime<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer::<N, O>("nextprime", n)?;
+
+    let two = constant(2)?;
+    if *n < two {
+        return Ok(two);
+    }
+
+    let mut next = increment::<N, O>(n)?;
+    while !is_prime::<N, O>("nextprime", &next)? {
+        next = increment::<N, O>(&next)?;
+    }
+
+    Ok(next)
+}
+
+/// Gets the prime factors of a positive integer, including the repeated factors.
+fn prime_factors<N, O>(name: &str, n: &N) -> Result<Vec<N>>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer_min::<N, O>(name, n, 1)?;
+
+    let mut factors = Vec::new();
+    let mut n = n.clone();
+
+    for p in SMALL_PRIMES.iter() {
+        let p = constant::<N>(*p)?;
+        while O::rem(&n, &p).ok_or_else(overflow)?.is_zero() {
+            n = O::div(&n, &p).ok_or_else(overflow)?;
+            factors.push(p.clone());
+        }
+    }
+
+    // The remaining factors are greater than the small primes,
+    // splits the composites using the Pollard's rho algorithm
+    let mut pending = vec![n];
+    while let Some(n) = pending.pop() {
+        if n.is_one() {
+            continue;
+        }
+
+        if is_prime::<N, O>(name, &n)? {
+            factors.push(n);
+        } else {
+            let d = pollard_rho::<N, O>(&n)?;
+            pending.push(O::div(&n, &d).ok_or_else(overflow)?);
+            pending.push(d);
+        }
+    }
+
+    Ok(factors)
+}
+
+/// Finds a non-trivial factor of an odd composite number.
+fn pollard_rho<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone,
+    O: IntegerOps<N>,
+{
+    let two = constant::<N>(2)?;
+    let mut c = N::one();
+
+    loop {
+        let next = |x: &N| -> Result<N> {
+            let square = mul_mod::<N, O>(x, x, n)?;
+            add_mod::<N, O>(&square, &c, n)
+        };
+
+        let mut x = two.clone();
+        let mut y = two.clone();
+        let mut d = N::one();
+
+        while d.is_one() {
+            x = next(&x)?;
+            y = next(&next(&y)?)?;
+
+            let diff = if x > y { O::sub(&x, &y) } else { O::sub(&y, &x) };
+            d = gcd::<N, O>(&diff.ok_or_else(overflow)?, n)?;
+        }
+
+        if d != *n {
+            return Ok(d);
+        }
+
+        // The sequence cycled without finding a factor, retries with other constant
+        c = O::add(&c, &N::one()).ok_or_else(overflow)?;
+    }
+}
+
+#[doc(hidden)]
+pub fn factor_count<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    let factors = prime_factors::<N, O>("factorcount", n)?;
+    N::from_usize(factors.len()).ok_or_else(overflow)
+}
+
+#[doc(hidden)]
+pub fn divisor_count<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    let mut factors = prime_factors::<N, O>("divisorcount", n)?;
+    factors.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    // The product of (exponent + 1) of each prime factor
+    let mut result = N::one();
+    let mut start = 0;
+
+    while start < factors.len() {
+        let end = factors[start..]
+            .iter()
+            .position(|p| *p != factors[start])
+            .map_or(factors.len(), |i| start + i);
+
+        let exponent = N::from_usize(end - start + 1).ok_or_else(overflow)?;
+        result = O::mul(&result, &exponent).ok_or_else(overflow)?;
+        start = end;
+    }
+
+    Ok(result)
+}
+
+#[doc(hidden)]
+pub fn min_factor<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    let factors = prime_factors::<N, O>("minfactor", n)?;
+    Ok(factors
+        .into_iter()
+        .fold(None, |min: Option<N>, p| match min {
+            Some(min) if min <= p => Some(min),
+            _ => Some(p),
+        })
+        .unwrap_or_else(N::one))
+}
+
+#[doc(hidden)]
+pub fn fib<N, O>(n: &N) -> Result<N>
+where
+    N: Zero + One + FromPrimitive + PartialOrd + Clone + Display,
+    O: IntegerOps<N>,
+{
+    check_integer_min::<N, O>("fib", n, 0)?;
+
+    let mut bits = Vec::new();
+    let mut m = n.clone();
+    while !m.is_zero() {
+        let (half, odd) = halve::<N, O>(&m)?;
+        bits.push(odd);
+        m = half;
+    }
+
+    // Fast doubling, with (a, b) = (F(k), F(k + 1)):
+    // F(2k) = F(k) * (2F(k + 1) - F(k)) and F(2k + 1) = F(k)^2 + F(k + 1)^2
+    let (mut a, mut b) = (N::zero(), N::one());
+
+    for (i, odd) in bits.iter().rev().enumerate() {
+        let last = i + 1 == bits.len();
+
+        let f_2k = || {
+            O::add(&b, &b)
+                .and_then(|n| O::sub(&n, &a))
+                .and_then(|n| O::mul(&a, &n))
+                .ok_or_else(overflow)
+        };
+
+        let f_2k_1 = || {
+            O::mul(&a, &a)
+                .and_then(|x| O::mul(&b, &b).and_then(|y| O::add(&x, &y)))
+                .ok_or_else(overflow)
+        };
+
+        // The last step only computes F(n) to not overflow calculating F(n + 1)
+        if last {
+            return if *odd { f_2k_1() } else { f_2k() };
+        }
+
+        let (c, d) = (f_2k()?, f_2k_1()?);
+        if *odd {
+            b = O::add(&c, &d).ok_or_else(overflow)?;
+            a = d;
+        } else {
+            a = c;
+            b = d;
+        }
+    }
+
+    Ok(a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_mod_test() {
+        let m = i64::max_value() - 24; // 9223372036854775783 is prime
+        let a = m - 1;
+        assert_eq!(Ok(1), mul_mod::<i64, Checked>(&a, &a, &m));
+        assert_eq!(Ok(0), mul_mod::<i64, Checked>(&0, &a, &m));
+        assert_eq!(Ok(6), mul_mod::<i64, Checked>(&2, &3, &7));
+        assert_eq!(Ok(6_f64), mul_mod::<f64, Unchecked>(&2.0, &3.0, &7.0));
+    }
+
+    #[test]
+    fn is_prime_test() {
+        let primes = (0..100)
+            .filter(|n| is_prime::<u32, Checked>("", n).unwrap())
+            .collect::<Vec<u32>>();
+
+        assert_eq!(
+            vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97],
+            primes
+        );
+
+        assert_eq!(Ok(true), is_prime::<i64, Checked>("", &(i64::max_value() - 24)));
+        assert_eq!(Ok(false), is_prime::<i64, Checked>("", &i64::max_value()));
+        // Strong pseudoprime to the bases 2, 3, 5 and 7
+        assert_eq!(Ok(false), is_prime::<u64, Checked>("", &3_215_031_751));
+        assert_eq!(Ok(true), is_prime::<u64, Unchecked>("", &18_446_744_073_709_551_557));
+
+        // The test is not exact from `MAX_PRIME_TEST`
+        assert_eq!(Ok(false), is_prime::<u128, Unchecked>("", &(MAX_PRIME_TEST - 2)));
+        assert!(is_prime::<u128, Unchecked>("", &MAX_PRIME_TEST).is_err());
+        // 2^89 - 1 is a prime
+        assert!(is_prime_value::<i128, Unchecked>(&618_970_019_642_690_137_449_562_111).is_err());
+        assert!(next_prime::<u128, Unchecked>(&MAX_PRIME_TEST).is_err());
+        assert!(factor_count::<u128, Unchecked>(&(2 * 618_970_019_642_690_137_449_562_111)).is_err());
+        assert_eq!(Ok(100), factor_count::<u128, Unchecked>(&(1 << 100)));
+    }
+
+    #[test]
+    fn prime_factors_test() {
+        let mut factors = prime_factors::<i64, Checked>("", &360).unwrap();
+        factors.sort();
+        assert_eq!(vec![2, 2, 2, 3, 3, 5], factors);
+
+        // Product of two large primes
+        let mut factors = prime_factors::<u64, Checked>("", &(1_000_000_007 * 998_244_353)).unwrap();
+        factors.sort();
+        assert_eq!(vec![998_244_353, 1_000_000_007], factors);
+
+        assert_eq!(Ok(vec![]), prime_factors::<i64, Checked>("", &1));
+        assert!(prime_factors::<i64, Checked>("", &0).is_err());
+    }
+
+    #[test]
+    fn float_limits_test() {
+        // Not all the integers greater than 2^53 are representable in `f64`
+        assert!(next_prime::<f64, Checked>(&1e17).is_err());
+        assert!(next_prime::<f64, Unchecked>(&1e17).is_err());
+        assert!(npr::<f64, Checked>(&1e17, &5.0).is_err());
+        assert!(ncr::<f64, Unchecked>(&9_007_199_254_740_992.0, &1.0).is_err());
+        assert!(gcd_all::<f64, Checked>(&[1e17, 10.0]).is_err());
+        let max = 9_007_199_254_740_991.0;
+        assert_eq!(Ok(max), ncr::<f64, Checked>(&max, &1.0));
+        assert_eq!(Ok(9_007_199_254_740_881.0), next_prime::<f64, Checked>(&(max - 111.0)));
+
+        // The next prime after 2^53 - 1 is not representable
+        assert!(next_prime::<f64, Checked>(&9_007_199_254_740_991.0).is_err());
+
+        // The results overflow instead of looping over `k`
+        assert!(ncr::<f64, Checked>(&1e15, &5e14).is_err());
+        assert!(ncr::<f64, Unchecked>(&1e15, &5e14).is_err());
+        assert!(npr::<f64, Checked>(&1e15, &1e14).is_err());
+        assert!(npr::<f64, Unchecked>(&1e15, &1e14).is_err());
+
+        // The max value of an integer type is exact
+        assert_eq!(Ok(i64::max_value()), ncr::<i64, Checked>(&i64::max_value(), &1));
+    }
+}
@@ -1,17 +1,24 @@
+pub mod bitwise;
 pub mod checked;
+pub mod random;
+pub mod distributions;
+pub mod integer;
+pub mod special;
 pub mod unchecked;
 
 pub mod math {
     use std::fmt::Debug;
     use std::ops::{Mul, Sub};
     use num_traits::{FromPrimitive, Inv, One, ToPrimitive, Zero};
-    use rand::random;
+    use crate::utils::rng::ContextRng;
     use crate::error::*;
+    use crate::evaluator::limits;
     pub use crate::function::{BinaryFunction, Function, UnaryFunction};
     use crate::function::{Associativity, Notation, Precedence};
     use crate::utils::gamma::gamma;
     use crate::Result;
     use crate::utils::approx::Approx;
+    use crate::context::AngleUnit;
 
     pub struct UnaryPlus;
     impl<N> UnaryFunction<N> for UnaryPlus {
@@ -54,6 +61,9 @@ pub mod math {
                 return Err(Error::from(ErrorKind::NegativeValue));
             }
 
+            // Each multiplication is an step of the evaluation
+            limits::charge(value.to_usize().unwrap_or(usize::MAX))?;
+
             // 0! = 1 and 1! = 1
             if value.is_zero() || value.is_one() {
                 return Ok(N::one());
@@ -288,7 +298,72 @@ pub mod math {
         }
     }
 
-    pub struct RandFunction;
+    /// Gets the nth root of a value: `root(x, n)`, negative values only have odd roots.
+    ///
+    /// The root is computed using `f64` for all the types, so for integer types the result is
+    /// truncated, eg: `root(10, 3)` is 2.
+    pub struct RootFunction;
+    impl<N: ToPrimitive + FromPrimitive> Function<N> for RootFunction {
+        fn name(&self) -> &str {
+            "root"
+        }
+
+        fn call(&self, args: &[N]) -> Result<N> {
+            if args.len() != 2 {
+                return Err(Error::from(ErrorKind::InvalidArgumentCount));
+            }
+
+            let value = try_to_float(&args[0])?;
+            let n = try_to_float(&args[1])?;
+
+            if n == 0_f64 {
+                return Err(Error::from(ErrorKind::DivisionByZero));
+            }
+
+            let result = if value < 0_f64 && n.fract() == 0_f64 && n % 2_f64 != 0_f64 {
+                -(-value).powf(n.inv())
+            } else {
+                value.powf(n.inv())
+            };
+
+            if result.is_nan() || result.is_infinite() {
+                Err(Error::from(ErrorKind::NAN))
+            } else {
+                N::from_f64(result.approx()).ok_or(Error::from(ErrorKind::Overflow))
+            }
+        }
+
+        fn to_latex(&self, args: &[String]) -> Option<String> {
+            match args {
+                [value, n] => Some(format!(r"\sqrt[{}]{{{}}}", n, value)),
+                _ => None,
+            }
+        }
+    }
+
+    /// Gets a random value: `random()` in `[0, 1)`, `random(max)` in `[0, max)`
+    /// and `random(min, max)` in `[min, max)`.
+    #[derive(Debug, Clone, Default)]
+    pub struct RandFunction {
+        rng: ContextRng,
+    }
+
+    impl RandFunction {
+        /// Constructs a new `RandFunction` with its own random number generator.
+        #[inline]
+        pub fn new() -> Self {
+            RandFunction {
+                rng: ContextRng::new(),
+            }
+        }
+
+        /// Constructs a new `RandFunction` which draws its values from the given generator.
+        #[inline]
+        pub fn with_rng(rng: ContextRng) -> Self {
+            RandFunction { rng }
+        }
+    }
+
     impl<N: ToPrimitive + FromPrimitive> Function<N> for RandFunction {
         #[inline]
         fn name(&self) -> &str {
@@ -297,14 +372,14 @@ pub mod math {
 
         fn call(&self, args: &[N]) -> Result<N> {
             match args.len() {
-                0 => N::from_f64(random::<f64>()).ok_or(Error::from(ErrorKind::Overflow)),
+                0 => N::from_f64(self.rng.next_f64()).ok_or(Error::from(ErrorKind::Overflow)),
                 1 => {
                     let max = try_to_float(&args[0])?;
                     if max.is_sign_negative(){
                         return Err(Error::from(ErrorKind::NegativeValue));
                     }
 
-                    N::from_f64(random::<f64>() * max).ok_or(Error::from(ErrorKind::Overflow))
+                    N::from_f64(self.rng.next_f64() * max).ok_or(Error::from(ErrorKind::Overflow))
                 }
                 2 => {
                     let min = try_to_float(&args[0])?;
@@ -317,7 +392,7 @@ pub mod math {
                         )
                     }
 
-                    let value = min + ((max - min) * random::<f64>());
+                    let value = min + ((max - min) * self.rng.next_f64());
                     N::from_f64(value).ok_or(Error::from(ErrorKind::Overflow))
                 }
                 _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
@@ -333,87 +408,167 @@ pub mod math {
 
     //////////////////// Trigonometric ////////////////////
 
-    macro_rules! impl_trig {
-        ($t:ty, $method_name:ident) => {
-            impl<N: ToPrimitive + FromPrimitive> Function<N> for $t {
-                fn name(&self) -> &str {
-                    stringify!($method_name)
+    /// A function which takes or returns angles in the given `AngleUnit`,
+    /// created with the `with_unit` method of the trigonometric functions: `SinFunction::with_unit(AngleUnit::Radians)`.
+    #[derive(Debug, Copy, Clone)]
+    pub struct WithAngleUnit<F> {
+        pub(crate) function: F,
+        pub(crate) unit: AngleUnit,
+    }
+
+    impl<F> WithAngleUnit<F> {
+        /// Gets the function used by this instance.
+        #[inline]
+        pub fn function(&self) -> &F {
+            &self.function
+        }
+
+        /// Gets the `AngleUnit` used by this instance.
+        #[inline]
+        pub fn unit(&self) -> AngleUnit {
+            self.unit
+        }
+    }
+
+    /// Declares functions which take or return angles in the given default `AngleUnit`,
+    /// the functions are implemented for `WithAngleUnit` to use any other unit.
+    macro_rules! angle_functions {
+        ($default:expr; $($struct_name:ident),* $(,)?) => {
+            $(
+                #[derive(Debug, Copy, Clone)]
+                pub struct $struct_name;
+
+                impl $struct_name {
+                    const DEFAULT: $crate::ops::math::WithAngleUnit<$struct_name> =
+                        $crate::ops::math::WithAngleUnit { function: $struct_name, unit: $default };
+
+                    /// Constructs an instance which uses the given `AngleUnit`.
+                    #[inline]
+                    pub fn with_unit(unit: $crate::context::AngleUnit) -> $crate::ops::math::WithAngleUnit<Self> {
+                        $crate::ops::math::WithAngleUnit { function: $struct_name, unit }
+                    }
                 }
 
-                fn call(&self, args: &[N]) -> Result<N> {
-                    if args.len() != 1 {
-                        Err(Error::from(ErrorKind::InvalidArgumentCount))
-                    } else {
-                        match args[0].to_f64()
-                            .map(f64::to_radians)
-                            .map(f64::$method_name)
-                            .map(|n| n.approx()){
-                            Some(n) => {
-                                if n.is_nan() || n.is_infinite() {
-                                    Err(Error::from(ErrorKind::NAN))
-                                } else {
-                                    N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow))
-                                }
-                            }
-                            None => Err(Error::from(ErrorKind::Overflow)),
-                        }
+                impl<N> $crate::function::Function<N> for $struct_name
+                where
+                    $crate::ops::math::WithAngleUnit<$struct_name>: $crate::function::Function<N>,
+                {
+                    #[inline]
+                    fn name(&self) -> &str {
+                        let default: &'static $crate::ops::math::WithAngleUnit<$struct_name> = &Self::DEFAULT;
+                        $crate::function::Function::<N>::name(default)
+                    }
+
+                    #[inline]
+                    fn call(&self, args: &[N]) -> $crate::Result<N> {
+                        $crate::function::Function::<N>::call(&Self::DEFAULT, args)
                     }
                 }
-            }
+
+                impl<N> $crate::function::UnaryFunction<N> for $struct_name
+                where
+                    $crate::ops::math::WithAngleUnit<$struct_name>: $crate::function::UnaryFunction<N>,
+                {
+                    #[inline]
+                    fn name(&self) -> &str {
+                        let default: &'static $crate::ops::math::WithAngleUnit<$struct_name> = &Self::DEFAULT;
+                        $crate::function::UnaryFunction::<N>::name(default)
+                    }
+
+                    #[inline]
+                    fn notation(&self) -> $crate::function::Notation {
+                        $crate::function::UnaryFunction::<N>::notation(&Self::DEFAULT)
+                    }
+
+                    #[inline]
+                    fn call(&self, value: N) -> $crate::Result<N> {
+                        $crate::function::UnaryFunction::<N>::call(&Self::DEFAULT, value)
+                    }
+                }
+            )*
         };
     }
 
-    macro_rules! impl_trig_rec {
-        ($t:ty, $method_name:ident, $name:ident) => {
+    #[cfg(any(feature = "decimal", feature = "complex"))]
+    pub(crate) use angle_functions;
+
+    /// Calls `f` with the single argument as `f64`, returns an error if the result is not finite.
+    fn call_f64<N, F>(args: &[N], f: F) -> Result<N>
+    where
+        N: ToPrimitive + FromPrimitive,
+        F: FnOnce(f64) -> f64,
+    {
+        match args {
+            [x] => match x.to_f64().map(f) {
+                Some(n) if n.is_nan() || n.is_infinite() => Err(Error::from(ErrorKind::NAN)),
+                Some(n) => N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow)),
+                None => Err(Error::from(ErrorKind::Overflow)),
+            },
+            _ => Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        }
+    }
+
+    macro_rules! impl_f64_function {
+        ($t:ty, $name:ident, |$this:ident, $n:ident| $body:expr) => {
             impl<N: ToPrimitive + FromPrimitive> Function<N> for $t {
                 fn name(&self) -> &str {
                     stringify!($name)
                 }
 
                 fn call(&self, args: &[N]) -> Result<N> {
-                    if args.len() != 1 {
-                        Err(Error::from(ErrorKind::InvalidArgumentCount))
-                    } else {
-                        match args[0]
-                            .to_f64()
-                            .map(f64::to_radians)
-                            .map(f64::$method_name)
-                            .map(|n| n.approx())
-                            .map(f64::inv)
-                        {
-                            Some(n) => {
-                                if n.is_nan() || n.is_infinite() {
-                                    Err(Error::from(ErrorKind::NAN))
-                                } else {
-                                    N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow))
-                                }
-                            }
-                            None => Err(Error::from(ErrorKind::Overflow)),
-                        }
-                    }
+                    let $this = self;
+                    call_f64(args, |$n| $body)
                 }
             }
         };
     }
 
-    pub struct SinFunction;
-    impl_trig!(SinFunction, sin);
+    macro_rules! impl_trig {
+        ($t:ty, $method_name:ident) => {
+            impl_f64_function!(WithAngleUnit<$t>, $method_name, |this, n| {
+                this.unit.to_radians(n).$method_name().approx()
+            });
+        };
+    }
 
-    pub struct CosFunction;
-    impl_trig!(CosFunction, cos);
+    macro_rules! impl_trig_rec {
+        ($t:ty, $method_name:ident, $name:ident) => {
+            impl_f64_function!(WithAngleUnit<$t>, $name, |this, n| {
+                this.unit.to_radians(n).$method_name().approx().inv()
+            });
+        };
+    }
 
-    pub struct TanFunction;
-    impl_trig!(TanFunction, tan);
+    angle_functions!(
+        AngleUnit::Degrees;
+        SinFunction, CosFunction, TanFunction, CscFunction, SecFunction, CotFunction,
+        ASinFunction, ACosFunction, ATanFunction, ACscFunction, ASecFunction, ACotFunction,
+        DegreeOperator,
+    );
 
-    pub struct CscFunction;
+    impl_trig!(SinFunction, sin);
+    impl_trig!(CosFunction, cos);
+    impl_trig!(TanFunction, tan);
     impl_trig_rec!(CscFunction, sin, csc);
-
-    pub struct SecFunction;
     impl_trig_rec!(SecFunction, cos, sec);
-
-    pub struct CotFunction;
     impl_trig_rec!(CotFunction, tan, cot);
 
+    /// Converts an angle in degrees to the `AngleUnit` of the context: `90°`.
+    impl<N: ToPrimitive + FromPrimitive> UnaryFunction<N> for WithAngleUnit<DegreeOperator> {
+        fn name(&self) -> &str {
+            "°"
+        }
+
+        fn notation(&self) -> Notation {
+            Notation::Postfix
+        }
+
+        fn call(&self, value: N) -> Result<N> {
+            let unit = self.unit;
+            call_f64(&[value], |n| unit.from_degrees(n))
+        }
+    }
+
     pub struct SinhFunction;
     forward_func_impl!(SinhFunction, sinh);
 
@@ -436,104 +591,42 @@ pub mod math {
 
     macro_rules! impl_arc_trig {
         ($t:ty, $method_name:ident) => {
-            impl_arc_trig!($t, $method_name, $method_name);
-        };
-
-        ($t:ty, $method_name:ident, $name:ident) => {
-            impl<N: ToPrimitive + FromPrimitive> Function<N> for $t {
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
-
-                fn call(&self, args: &[N]) -> Result<N> {
-                    if args.len() != 1 {
-                        Err(Error::from(ErrorKind::InvalidArgumentCount))
-                    } else {
-                        match args[0].to_f64()
-                            .map(f64::$method_name)
-                            .map(f64::to_degrees)
-                            .map(|n| n.approx()){
-                            Some(n) => {
-                                if n.is_nan() || n.is_infinite() {
-                                    Err(Error::from(ErrorKind::NAN))
-                                } else {
-                                    N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow))
-                                }
-                            }
-                            None => Err(Error::from(ErrorKind::Overflow)),
-                        }
-                    }
-                }
-            }
+            impl_f64_function!(WithAngleUnit<$t>, $method_name, |this, n| {
+                this.unit.from_radians(n.$method_name()).approx()
+            });
         };
     }
 
     macro_rules! impl_arc_trig_rec {
-        ($t:ty, $method_name:ident) => {
-            impl_arc_trig_rec!($t, $method_name, $method_name);
-        };
-
         ($t:ty, $method_name:ident, $name:ident) => {
-            impl<N: ToPrimitive + FromPrimitive> Function<N> for $t {
-                fn name(&self) -> &str {
-                    stringify!($name)
-                }
-
-                fn call(&self, args: &[N]) -> Result<N> {
-                    if args.len() != 1 {
-                        Err(Error::from(ErrorKind::InvalidArgumentCount))
-                    } else {
-                        match args[0].to_f64()
-                            .map(f64::inv)
-                            .map(f64::$method_name)
-                            .map(f64::to_degrees)
-                            .map(|n| n.approx()){
-                            Some(n) => {
-                                if n.is_nan() || n.is_infinite() {
-                                    Err(Error::from(ErrorKind::NAN))
-                                } else {
-                                    N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow))
-                                }
-                            }
-                            None => Err(Error::from(ErrorKind::Overflow)),
-                        }
-                    }
-                }
-            }
+            impl_f64_function!(WithAngleUnit<$t>, $name, |this, n| {
+                this.unit.from_radians(n.inv().$method_name()).approx()
+            });
         };
     }
 
-    pub struct ASinFunction;
     impl_arc_trig!(ASinFunction, asin);
-
-    pub struct ACosFunction;
     impl_arc_trig!(ACosFunction, acos);
+    impl_arc_trig_rec!(ACscFunction, asin, acsc);
+    impl_arc_trig_rec!(ASecFunction, acos, asec);
+    impl_arc_trig_rec!(ACotFunction, atan, acot);
 
-    pub struct ATanFunction;
-    impl<N: ToPrimitive + FromPrimitive> Function<N> for ATanFunction {
+    /// Gets the angle of the tangent `atan(x)` or the angle of the point `atan(y, x)`.
+    impl<N: ToPrimitive + FromPrimitive> Function<N> for WithAngleUnit<ATanFunction> {
         fn name(&self) -> &str {
             stringify!(atan)
         }
 
         fn call(&self, args: &[N]) -> Result<N> {
             match args.len() {
-                1 => match args[0].to_f64().map(f64::atan).map(f64::to_degrees) {
-                    Some(n) => {
-                        if n.is_nan() || n.is_infinite() {
-                            Err(Error::from(ErrorKind::NAN))
-                        } else {
-                            N::from_f64(n).ok_or(Error::from(ErrorKind::Overflow))
-                        }
-                    }
-                    None => Err(Error::from(ErrorKind::Overflow)),
-                },
+                1 => call_f64(args, |n| self.unit.from_radians(n.atan())),
                 2 => {
                     if let (Some(y), Some(x)) = (args[0].to_f64(), args[1].to_f64()){
                         if y.is_zero() && x.is_zero(){
                             return Err(Error::from(ErrorKind::NAN));
                         }
 
-                        let result = y.atan2(x).to_degrees();
+                        let result = self.unit.from_radians(y.atan2(x));
                         if result.is_nan() || result.is_infinite() {
                             Err(Error::from(ErrorKind::NAN))
                         } else {
@@ -548,32 +641,226 @@ pub mod math {
         }
     }
 
-    pub struct ACscFunction;
-    impl_arc_trig_rec!(ACscFunction, asin, acsc);
-
-    pub struct ASecFunction;
-    impl_arc_trig_rec!(ASecFunction, acos, asec);
-
-    pub struct ACotFunction;
-    impl_arc_trig_rec!(ACotFunction, atan, acot);
+    //////////////////// Inverse Hyperbolic ////////////////////
 
     pub struct ASinhFunction;
-    impl_arc_trig!(ASinhFunction, asinh);
+    impl_f64_function!(ASinhFunction, asinh, |_this, n| n.asinh().to_degrees().approx());
 
     pub struct ACoshFunction;
-    impl_arc_trig!(ACoshFunction, acosh);
+    impl_f64_function!(ACoshFunction, acosh, |_this, n| n.acosh().to_degrees().approx());
 
     pub struct ATanhFunction;
-    impl_arc_trig!(ATanhFunction, atanh);
+    impl_f64_function!(ATanhFunction, atanh, |_this, n| n.atanh().to_degrees().approx());
 
     pub struct ACschFunction;
-    impl_arc_trig_rec!(ACschFunction, asinh, acsch);
+    impl_f64_function!(ACschFunction, acsch, |_this, n| n.inv().asinh().to_degrees().approx());
 
     pub struct ASechFunction;
-    impl_arc_trig_rec!(ASechFunction, acosh, asech);
+    impl_f64_function!(ASechFunction, asech, |_this, n| n.inv().acosh().to_degrees().approx());
 
     pub struct ACothFunction;
-    impl_arc_trig_rec!(ACothFunction, atanh, acoth);
+    impl_f64_function!(ACothFunction, acoth, |_this, n| n.inv().atanh().to_degrees().approx());
+
+    pub struct CountFunction;
+    impl<N: FromPrimitive> Function<N> for CountFunction {
+        fn name(&self) -> &str {
+            "count"
+        }
+
+        fn call(&self, args: &[N]) -> Result<N> {
+            N::from_usize(args.len()).ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Gets the most common value, if many values are the most common returns the smallest.
+    pub struct ModeFunction;
+    impl<N: PartialOrd + Clone> Function<N> for ModeFunction {
+        fn name(&self) -> &str {
+            "mode"
+        }
+
+        fn call(&self, args: &[N]) -> Result<N> {
+            let sorted = sorted_args(args)?;
+            let mut mode = &sorted[0];
+            let mut max_count = 0;
+            let mut start = 0;
+
+            for i in 1..=sorted.len() {
+                if i == sorted.len() || sorted[i] != sorted[start] {
+                    if i - start > max_count {
+                        max_count = i - start;
+                        mode = &sorted[start];
+                    }
+
+                    start = i;
+                }
+            }
+
+            Ok(mode.clone())
+        }
+    }
+
+    macro_rules! impl_variance {
+        ($func_name:ident, $name:ident, $ddof:expr, $map:expr) => {
+            impl<N: ToPrimitive + FromPrimitive> Function<N> for $func_name {
+                fn name(&self) -> &str {
+                    stringify!($name)
+                }
+
+                fn call(&self, args: &[N]) -> Result<N> {
+                    let variance = welford_variance(args, $ddof)?;
+                    let map: fn(f64) -> f64 = $map;
+                    N::from_f64(map(variance)).ok_or(Error::from(ErrorKind::Overflow))
+                }
+            }
+        };
+    }
+
+    /// Gets the sample variance, using `n - 1` as divisor.
+    pub struct VarianceFunction;
+    impl_variance!(VarianceFunction, variance, 1, |n| n);
+
+    /// Gets the population variance, using `n` as divisor.
+    pub struct PopVarianceFunction;
+    impl_variance!(PopVarianceFunction, pvariance, 0, |n| n);
+
+    /// Gets the sample standard deviation, using `n - 1` as divisor.
+    pub struct StdDevFunction;
+    impl_variance!(StdDevFunction, stddev, 1, f64::sqrt);
+
+    /// Gets the population standard deviation, using `n` as divisor.
+    pub struct PopStdDevFunction;
+    impl_variance!(PopStdDevFunction, pstddev, 0, f64::sqrt);
+
+    /// Gets the geometric mean of positive values.
+    pub struct GeoMeanFunction;
+    impl<N: ToPrimitive + FromPrimitive> Function<N> for GeoMeanFunction {
+        fn name(&self) -> &str {
+            "geomean"
+        }
+
+        fn call(&self, args: &[N]) -> Result<N> {
+            if args.is_empty() {
+                return Err(Error::from(ErrorKind::InvalidArgumentCount));
+            }
+
+            let mut logs = Vec::with_capacity(args.len());
+            for n in args {
+                let value = try_to_float(n)?;
+                if value <= 0.0 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("`geomean` expects positive values but was {}", value),
+                    ));
+                }
+
+                logs.push(value.ln());
+            }
+
+            // Sums the logarithms to avoid the overflow of the product
+            let result = (compensated_sum(logs) / args.len() as f64).exp();
+            N::from_f64(result.approx()).ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Gets the harmonic mean of positive values.
+    pub struct HarMeanFunction;
+    impl<N: ToPrimitive + FromPrimitive> Function<N> for HarMeanFunction {
+        fn name(&self) -> &str {
+            "harmean"
+        }
+
+        fn call(&self, args: &[N]) -> Result<N> {
+            if args.is_empty() {
+                return Err(Error::from(ErrorKind::InvalidArgumentCount));
+            }
+
+            let mut inverses = Vec::with_capacity(args.len());
+            for n in args {
+                let value = try_to_float(n)?;
+                if value <= 0.0 {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("`harmean` expects positive values but was {}", value),
+                    ));
+                }
+
+                inverses.push(value.recip());
+            }
+
+            let result = args.len() as f64 / compensated_sum(inverses);
+            N::from_f64(result.approx()).ok_or(Error::from(ErrorKind::Overflow))
+        }
+    }
+
+    /// Gets a sorted copy of the given values, returns an error if empty or the values cannot be compared.
+    pub(crate) fn sorted_args<N: PartialOrd + Clone>(args: &[N]) -> Result<Vec<N>> {
+        if args.is_empty() {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        if args.iter().any(|n| n.partial_cmp(n).is_none()) {
+            return Err(Error::from(ErrorKind::NAN));
+        }
+
+        let mut sorted = args.to_vec();
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Ok(sorted)
+    }
+
+    /// Gets the index of the value below the percentile `p` in `[0, 100]` in `len` sorted values
+    /// and the fraction to the next value.
+    pub(crate) fn percentile_rank<N: ToPrimitive>(p: &N, len: usize) -> Result<(usize, f64)> {
+        let p = try_to_float(p)?;
+        if !(0.0..=100.0).contains(&p) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`percentile` expects a value in [0, 100] but was {}", p),
+            ));
+        }
+
+        let rank = (len - 1) as f64 * p / 100.0;
+        let index = rank.floor() as usize;
+        Ok((index, rank - index as f64))
+    }
+
+    /// Sums the given values using the Neumaier compensated summation,
+    /// which keeps the rounding error independent of the number of values.
+    pub(crate) fn compensated_sum<I: IntoIterator<Item = f64>>(values: I) -> f64 {
+        let mut sum = 0.0;
+        let mut compensation = 0.0;
+
+        for value in values {
+            let t = sum + value;
+            if f64::abs(sum) >= f64::abs(value) {
+                compensation += (sum - t) + value;
+            } else {
+                compensation += (value - t) + sum;
+            }
+            sum = t;
+        }
+
+        sum + compensation
+    }
+
+    /// Computes the variance using the Welford's online algorithm, the divisor is `n - ddof`.
+    pub(crate) fn welford_variance<N: ToPrimitive>(args: &[N], ddof: usize) -> Result<f64> {
+        if args.len() <= ddof {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let mut mean = 0.0;
+        let mut m2 = 0.0;
+
+        for (i, n) in args.iter().enumerate() {
+            let value = try_to_float(n)?;
+            let delta = value - mean;
+            mean += delta / (i + 1) as f64;
+            m2 += delta * (value - mean);
+        }
+
+        Ok(m2 / (args.len() - ddof) as f64)
+    }
 
     #[inline(always)]
     pub(crate) fn try_to_float<N: ToPrimitive>(n: &N) -> Result<f64> {
@@ -615,6 +902,8 @@ mod tests{
     use super::math::*;
     use num_traits::Inv;
     use crate::utils::approx::Approx;
+    use crate::utils::rng::ContextRng;
+    use crate::context::AngleUnit;
 
     const ERROR : f64 = 0.000_000_000_01;
 
@@ -687,6 +976,67 @@ mod tests{
         assert!(instance.call(&empty_array::<f64>()).is_err())
     }
 
+    #[test]
+    fn count_test(){
+        let instance = CountFunction;
+
+        assert_eq!(instance.call(&[5, 3, 1]), Ok(3));
+        assert_eq!(instance.call(&empty_array::<f64>()), Ok(0_f64));
+    }
+
+    #[test]
+    fn mode_test(){
+        let instance = ModeFunction;
+
+        assert_eq!(instance.call(&[1, 3, 3, 2, 2]), Ok(2));
+        assert_eq!(instance.call(&[4_f64, 1_f64, 4_f64]), Ok(4_f64));
+
+        assert!(instance.call(&[1_f64, f64::NAN]).is_err());
+        assert!(instance.call(&empty_array::<i64>()).is_err());
+    }
+
+    #[test]
+    fn variance_test(){
+        let values = [2_f64, 4_f64, 4_f64, 4_f64, 5_f64, 5_f64, 7_f64, 9_f64];
+
+        assert!(almost_eq(VarianceFunction.call(&values).unwrap(), 32_f64 / 7_f64, ERROR));
+        assert_eq!(PopVarianceFunction.call(&values), Ok(4_f64));
+        assert!(almost_eq(StdDevFunction.call(&values).unwrap(), (32_f64 / 7_f64).sqrt(), ERROR));
+        assert_eq!(PopStdDevFunction.call(&values), Ok(2_f64));
+        assert_eq!(PopStdDevFunction.call(&[2, 4, 4, 4, 5, 5, 7, 9]), Ok(2));
+
+        // Large offsets don't lose precision
+        let shifted = values.iter().map(|n| n + 1e9).collect::<Vec<f64>>();
+        assert!(almost_eq(PopVarianceFunction.call(&shifted).unwrap(), 4_f64, 0.000_001));
+
+        assert!(VarianceFunction.call(&[1_f64]).is_err());
+        assert_eq!(PopVarianceFunction.call(&[1_f64]), Ok(0_f64));
+        assert!(PopVarianceFunction.call(&empty_array::<f64>()).is_err());
+    }
+
+    #[test]
+    fn geomean_test(){
+        let instance = GeoMeanFunction;
+
+        assert!(almost_eq(instance.call(&[2_f64, 8_f64]).unwrap(), 4_f64, ERROR));
+        assert_eq!(instance.call(&[1, 2, 4]), Ok(2));
+
+        assert!(instance.call(&[2_f64, 0_f64]).is_err());
+        assert!(instance.call(&[2_f64, -1_f64]).is_err());
+        assert!(instance.call(&empty_array::<f64>()).is_err());
+    }
+
+    #[test]
+    fn harmean_test(){
+        let instance = HarMeanFunction;
+
+        assert!(almost_eq(instance.call(&[1_f64, 4_f64, 4_f64]).unwrap(), 2_f64, ERROR));
+        assert_eq!(instance.call(&[3, 6]), Ok(4));
+
+        assert!(instance.call(&[2_f64, 0_f64]).is_err());
+        assert!(instance.call(&empty_array::<f64>()).is_err());
+    }
+
     #[test]
     fn floor_test(){
         let instance = FloorFunction;
@@ -756,6 +1106,22 @@ mod tests{
         assert!(instance.call(&empty_array::<f64>()).is_err())
     }
 
+    #[test]
+    fn root_test(){
+        let instance = RootFunction;
+
+        assert_eq!(instance.call(&[27, 3]), Ok(3));
+        assert_eq!(instance.call(&[64, 3]), Ok(4));
+        assert_eq!(instance.call(&[-8, 3]), Ok(-2));
+        assert_eq!(instance.call(&[10, 3]), Ok(2));
+        assert_eq!(instance.call(&[16_f64, 4_f64]), Ok(2_f64));
+        assert_eq!(instance.call(&[2_f64, 2_f64]), Ok(2_f64.sqrt()));
+
+        assert!(instance.call(&[-16, 2]).is_err());
+        assert!(instance.call(&[8, 0]).is_err());
+        assert!(instance.call(&[8]).is_err());
+    }
+
     #[test]
     fn exp_test(){
         let instance = ExpFunction;
@@ -813,7 +1179,7 @@ mod tests{
     #[test]
     fn rand_test(){
         const SAMPLES : usize = 1000;
-        let instance = RandFunction;
+        let instance = RandFunction::new();
 
         fn compute_random(rand: &RandFunction){
             for _ in 0..SAMPLES{
@@ -848,6 +1214,18 @@ mod tests{
         assert!(instance.call(&[10_f64, 3_f64, 7_f64]).is_err());
     }
 
+    #[test]
+    fn rand_seed_test(){
+        let a = RandFunction::with_rng(ContextRng::with_seed(5));
+        let b = RandFunction::with_rng(ContextRng::with_seed(5));
+
+        for _ in 0..100 {
+            let x : f64 = a.call(&[-10_f64, 10_f64]).unwrap();
+            let y : f64 = b.call(&[-10_f64, 10_f64]).unwrap();
+            assert_eq!(x, y);
+        }
+    }
+
     #[test]
     fn to_radians_test(){
         let instance = ToRadiansFunction;
@@ -1291,4 +1669,34 @@ mod tests{
         assert!(instance.call(&[10_f64, 3_f64, 7_f64]).is_err());
         assert!(instance.call(&empty_array::<f64>()).is_err());
     }
+
+    #[test]
+    fn angle_unit_test(){
+        use std::f64::consts::PI;
+
+        let sin = SinFunction::with_unit(AngleUnit::Radians);
+        assert_eq!(sin.call(&[PI / 2.0]), Ok(1_f64));
+        let sin = SinFunction::with_unit(AngleUnit::Gradians);
+        assert_eq!(sin.call(&[100_f64]), Ok(1_f64));
+        let cot = CotFunction::with_unit(AngleUnit::Gradians);
+        assert_eq!(cot.call(&[50_f64]), Ok(1_f64));
+
+        let asin = ASinFunction::with_unit(AngleUnit::Radians);
+        assert_eq!(asin.call(&[1_f64]), Ok(PI / 2.0));
+        let acos = ACosFunction::with_unit(AngleUnit::Gradians);
+        assert_eq!(acos.call(&[-1_f64]), Ok(200_f64));
+        let atan = ATanFunction::with_unit(AngleUnit::Gradians);
+        assert_eq!(atan.call(&[1_f64, -1_f64]), Ok(150_f64));
+        assert_eq!(AngleUnit::Gradians, ATanFunction::with_unit(AngleUnit::Gradians).unit());
+    }
+
+    #[test]
+    fn degree_operator_test(){
+        use std::f64::consts::PI;
+
+        assert_eq!(DegreeOperator::with_unit(AngleUnit::Degrees).call(45_f64), Ok(45_f64));
+        assert_eq!(DegreeOperator::with_unit(AngleUnit::Radians).call(180_f64), Ok(PI));
+        assert_eq!(DegreeOperator::with_unit(AngleUnit::Gradians).call(90_f64), Ok(100_f64));
+        assert!(DegreeOperator.call(f64::NAN).is_err());
+    }
 }
@@ -0,0 +1,515 @@
+use std::f64::consts::PI;
+
+use num_traits::{FromPrimitive, ToPrimitive};
+use rand::distributions::Uniform;
+use rand::rngs::StdRng;
+use rand::Rng;
+
+use crate::error::*;
+use crate::function::Function;
+use crate::ops::math::try_to_float;
+use crate::utils::gamma::ln_gamma;
+use crate::utils::rng::ContextRng;
+use crate::Result;
+
+macro_rules! random_function {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Default)]
+        pub struct $name {
+            rng: ContextRng,
+        }
+
+        impl $name {
+            /// Constructs a new function with its own random number generator.
+            #[inline]
+            pub fn new() -> Self {
+                $name {
+                    rng: ContextRng::new(),
+                }
+            }
+
+            /// Constructs a new function which draws its values from the given generator.
+            #[inline]
+            pub fn with_rng(rng: ContextRng) -> Self {
+                $name { rng }
+            }
+        }
+    };
+}
+
+random_function!(
+    /// Gets a random integer in the range `[a, b]`: `randint(a, b)`.
+    RandIntFunction
+);
+
+random_function!(
+    /// Gets a random value from a normal distribution: `randnormal()` with mean 0
+    /// and standard deviation 1, or `randnormal(mu, sigma)`.
+    RandNormalFunction
+);
+
+random_function!(
+    /// Gets a random value from an exponential distribution with the given rate: `randexp(lambda)`.
+    RandExpFunction
+);
+
+random_function!(
+    /// Gets a random value from a poisson distribution with the given mean: `randpoisson(lambda)`.
+    RandPoissonFunction
+);
+
+random_function!(
+    /// Gets the number of successes in `n` trials with probability `p`: `randbinomial(n, p)`.
+    RandBinomialFunction
+);
+
+random_function!(
+    /// Gets one of the arguments at random: `choice(a, b, c, ...)`.
+    ChoiceFunction
+);
+
+impl<N: ToPrimitive + FromPrimitive> Function<N> for RandIntFunction {
+    fn name(&self) -> &str {
+        "randint"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() != 2 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        // Integer types are sampled exactly, as a `f64` can't represent all the `i64` values
+        if is_integer_type::<N>() {
+            let (a, b) = match (args[0].to_i128(), args[1].to_i128()) {
+                (Some(a), Some(b)) => (a, b),
+                _ => return Err(Error::from(ErrorKind::Overflow)),
+            };
+
+            if a > b {
+                return Err(invalid_range(a, b));
+            }
+
+            // The inclusive version of `gen_range`
+            let value = self.rng.with(|rng| rng.sample(Uniform::new_inclusive(a, b)));
+            return N::from_i128(value).ok_or(Error::from(ErrorKind::Overflow));
+        }
+
+        let a = try_to_float(&args[0])?;
+        let b = try_to_float(&args[1])?;
+        let (min, max) = (a.ceil(), b.floor());
+
+        if min > max {
+            return Err(invalid_range(a, b));
+        }
+
+        let value = (min + (self.rng.next_f64() * (max - min + 1.0)).floor()).min(max);
+        N::from_f64(value).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+/// Checks if `N` is an integer type, which truncates `0.5` to `0`.
+fn is_integer_type<N: ToPrimitive + FromPrimitive>() -> bool {
+    N::from_f64(0.5).and_then(|n| n.to_f64()) == Some(0.0)
+}
+
+fn invalid_range<T: std::fmt::Display>(a: T, b: T) -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        format!("Invalid range for `randint`: no integers between {} and {}", a, b),
+    )
+}
+
+impl<N: ToPrimitive + FromPrimitive> Function<N> for RandNormalFunction {
+    fn name(&self) -> &str {
+        "randnormal"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        let (mean, std_dev) = match args.len() {
+            0 => (0.0, 1.0),
+            2 => (try_to_float(&args[0])?, try_to_float(&args[1])?),
+            _ => return Err(Error::from(ErrorKind::InvalidArgumentCount)),
+        };
+
+        if std_dev.is_sign_negative() {
+            return Err(Error::from(ErrorKind::NegativeValue));
+        }
+
+        let value = mean + std_dev * self.rng.with(standard_normal);
+        N::from_f64(value).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+impl<N: ToPrimitive + FromPrimitive> Function<N> for RandExpFunction {
+    fn name(&self) -> &str {
+        "randexp"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() != 1 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let rate = try_to_float(&args[0])?;
+        if rate <= 0.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`randexp` expects a positive rate but was {}", rate),
+            ));
+        }
+
+        // Inverse transform, `1 - u` is in `(0, 1]` so the logarithm is finite.
+        let value = -(1.0 - self.rng.next_f64()).ln() / rate;
+        N::from_f64(value).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+impl<N: ToPrimitive + FromPrimitive> Function<N> for RandPoissonFunction {
+    fn name(&self) -> &str {
+        "randpoisson"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() != 1 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let mean = try_to_float(&args[0])?;
+        if mean.is_sign_negative() {
+            return Err(Error::from(ErrorKind::NegativeValue));
+        }
+
+        let value = self.rng.with(|rng| poisson(rng, mean));
+        N::from_f64(value).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+impl<N: ToPrimitive + FromPrimitive> Function<N> for RandBinomialFunction {
+    fn name(&self) -> &str {
+        "randbinomial"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() != 2 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let n = try_to_float(&args[0])?;
+        let p = try_to_float(&args[1])?;
+
+        // Integers greater than 2^53 cannot be represented exactly
+        if n.is_sign_negative() || n.fract() != 0.0 || n > 9_007_199_254_740_992.0 {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`randbinomial` expects a non-negative integer number of trials but was {}", n),
+            ));
+        }
+
+        if !(0.0..=1.0).contains(&p) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`randbinomial` expects a probability in [0, 1] but was {}", p),
+            ));
+        }
+
+        let value = self.rng.with(|rng| binomial(rng, n as u64, p));
+        N::from_u64(value).ok_or(Error::from(ErrorKind::Overflow))
+    }
+}
+
+impl<N: Clone> Function<N> for ChoiceFunction {
+    fn name(&self) -> &str {
+        "choice"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.is_empty() {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let index = ((self.rng.next_f64() * args.len() as f64) as usize).min(args.len() - 1);
+        Ok(args[index].clone())
+    }
+}
+
+/// Gets a value from the standard normal distribution using the Box-Muller transform.
+fn standard_normal(rng: &mut StdRng) -> f64 {
+    let u1 = 1.0 - rng.gen::<f64>(); // (0, 1]
+    let u2 = rng.gen::<f64>();
+    f64::sqrt(-2.0 * u1.ln()) * f64::cos(2.0 * PI * u2)
+}
+
+/// Gets a value from the gamma distribution with the given shape `>= 1` and scale 1.
+///
+/// See: Marsaglia and Tsang, "A Simple Method for Generating Gamma Variables" (2000).
+fn standard_gamma(rng: &mut StdRng, shape: f64) -> f64 {
+    let d = shape - 1.0 / 3.0;
+    let c = 1.0 / f64::sqrt(9.0 * d);
+
+    loop {
+        let x = standard_normal(rng);
+        let v = 1.0 + c * x;
+        if v <= 0.0 {
+            continue;
+        }
+
+        let v = v * v * v;
+        let u = rng.gen::<f64>();
+        if u < 1.0 - 0.0331 * x.powi(4) || u.ln() < 0.5 * x * x + d * (1.0 - v + v.ln()) {
+            return d * v;
+        }
+    }
+}
+
+/// Gets a value from the poisson distribution with the given mean.
+fn poisson(rng: &mut StdRng, mean: f64) -> f64 {
+    if mean < 30.0 {
+        // Knuth's multiplication method, takes `O(mean)` steps.
+        let limit = (-mean).exp();
+        let mut k = 0.0;
+        let mut p = rng.gen::<f64>();
+
+        while p > limit {
+            k += 1.0;
+            p *= rng.gen::<f64>();
+        }
+
+        return k;
+    }
+
+    // Transformed rejection with squeeze.
+    // See: Hörmann, "The transformed rejection method for generating Poisson random variables" (1993).
+    let sqrt_mean = mean.sqrt();
+    let log_mean = mean.ln();
+    let b = 0.931 + 2.53 * sqrt_mean;
+    let a = -0.059 + 0.02483 * b;
+    let inv_alpha = 1.1239 + 1.1328 / (b - 3.4);
+    let v_r = 0.9277 - 3.6224 / (b - 2.0);
+
+    loop {
+        let u = rng.gen::<f64>() - 0.5;
+        let v = rng.gen::<f64>();
+        let us = 0.5 - u.abs();
+        let k = f64::floor((2.0 * a / us + b) * u + mean + 0.43);
+
+        if us >= 0.07 && v <= v_r {
+            return k;
+        }
+
+        if k < 0.0 || (us < 0.013 && v > us) {
+            continue;
+        }
+
+        if v.ln() + inv_alpha.ln() - (a / (us * us) + b).ln() <= -mean + k * log_mean - ln_gamma(k + 1.0) {
+            return k;
+        }
+    }
+}
+
+/// Gets a value from the binomial distribution with `n` trials and probability `p`.
+///
+/// Large distributions are reduced by splitting at the median order statistic of the
+/// `n` uniform values, which follows a beta distribution, until the expected number
+/// of successes is small enough to count them directly.
+///
+/// See: Devroye, "Non-Uniform Random Variate Generation" (1986), chapter X.4.
+fn binomial(rng: &mut StdRng, mut n: u64, mut p: f64) -> u64 {
+    const DIRECT_LIMIT: f64 = 64.0;
+
+    let mut successes = 0;
+
+    loop {
+        if n == 0 || p <= 0.0 {
+            return successes;
+        }
+
+        if p >= 1.0 {
+            return successes + n;
+        }
+
+        if n as f64 * p.min(1.0 - p) <= DIRECT_LIMIT {
+            return successes + binomial_waiting_time(rng, n, p);
+        }
+
+        // The i-th smallest of n uniform values follows Beta(i, n + 1 - i)
+        let i = n - n / 2;
+        let x = standard_gamma(rng, i as f64);
+        let y = standard_gamma(rng, (n + 1 - i) as f64);
+        let median = x / (x + y);
+
+        if median <= p {
+            // The `i` smallest values are successes, the rest are uniform in (median, 1)
+            successes += i;
+            n -= i;
+            p = (p - median) / (1.0 - median);
+        } else {
+            // The values from the median are failures, the rest are uniform in (0, median)
+            n = i - 1;
+            p /= median;
+        }
+    }
+}
+
+/// Counts the successes using the geometric waiting time between them, takes `O(n * min(p, 1 - p))` steps.
+fn binomial_waiting_time(rng: &mut StdRng, n: u64, p: f64) -> u64 {
+    if p > 0.5 {
+        return n - binomial_waiting_time(rng, n, 1.0 - p);
+    }
+
+    let log_q = (1.0 - p).ln();
+    let mut successes = 0;
+    let mut trials = 0.0;
+
+    loop {
+        // Number of trials until the next success
+        trials += f64::floor((1.0 - rng.gen::<f64>()).ln() / log_q) + 1.0;
+        if trials > n as f64 {
+            return successes;
+        }
+
+        successes += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: usize = 20_000;
+
+    fn mean_and_variance<F: Function<f64>>(func: &F, args: &[f64]) -> (f64, f64) {
+        let values = (0..SAMPLES)
+            .map(|_| func.call(args).unwrap())
+            .collect::<Vec<f64>>();
+
+        let mean = values.iter().sum::<f64>() / SAMPLES as f64;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (SAMPLES - 1) as f64;
+        (mean, variance)
+    }
+
+    fn assert_close(expected: f64, actual: f64, tolerance: f64) {
+        assert!(
+            (expected - actual).abs() <= tolerance,
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn randint_test() {
+        let func = RandIntFunction::with_rng(ContextRng::with_seed(1));
+        let mut seen = [false; 6];
+
+        for _ in 0..1000 {
+            let value: i64 = func.call(&[1, 6]).unwrap();
+            assert!(value >= 1 && value <= 6, "value out of range: {}", value);
+            seen[(value - 1) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|b| *b));
+
+        // Values not representable as `f64`
+        let max = i64::MAX;
+        assert_eq!(Ok(max - 1), func.call(&[max - 1, max - 1]));
+        assert!(func.call(&[max - 2, max]).is_ok_and(|n| n >= max - 2));
+        assert_eq!(Ok(u64::MAX), func.call(&[u64::MAX, u64::MAX]));
+        assert!(func.call(&[6, 1]).is_err());
+
+        assert_eq!(Ok(3.0), func.call(&[2.5, 3.7]));
+        assert!(func.call(&[2.2, 2.8]).is_err());
+        assert!(func.call(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn randnormal_test() {
+        let func = RandNormalFunction::with_rng(ContextRng::with_seed(2));
+        let (mean, variance) = mean_and_variance(&func, &[10.0, 2.0]);
+        assert_close(10.0, mean, 0.1);
+        assert_close(4.0, variance, 0.2);
+
+        let (mean, variance) = mean_and_variance(&func, &[]);
+        assert_close(0.0, mean, 0.05);
+        assert_close(1.0, variance, 0.05);
+
+        assert!(func.call(&[0.0, -1.0]).is_err());
+    }
+
+    #[test]
+    fn randexp_test() {
+        let func = RandExpFunction::with_rng(ContextRng::with_seed(3));
+        let (mean, variance) = mean_and_variance(&func, &[4.0]);
+        assert_close(0.25, mean, 0.01);
+        assert_close(0.0625, variance, 0.005);
+
+        assert!(func.call(&[0.0]).is_err());
+        assert!(func.call(&[-2.0]).is_err());
+    }
+
+    #[test]
+    fn randpoisson_test() {
+        let func = RandPoissonFunction::with_rng(ContextRng::with_seed(4));
+
+        for lambda in &[3.5, 250.0] {
+            let (mean, variance) = mean_and_variance(&func, &[*lambda]);
+            assert_close(*lambda, mean, lambda * 0.02);
+            assert_close(*lambda, variance, lambda * 0.06);
+        }
+
+        assert_eq!(Ok(0.0), func.call(&[0.0]));
+        assert!(func.call(&[-1.0]).is_err());
+    }
+
+    #[test]
+    fn randbinomial_test() {
+        let func = RandBinomialFunction::with_rng(ContextRng::with_seed(5));
+
+        for (n, p) in &[(20.0, 0.3), (10_000.0, 0.6), (1_000_000_000.0, 0.25)] {
+            let (mean, variance) = mean_and_variance(&func, &[*n, *p]);
+            let expected_variance: f64 = n * p * (1.0 - p);
+            assert_close(n * p, mean, expected_variance.sqrt() * 0.05);
+            assert_close(expected_variance, variance, expected_variance * 0.06);
+        }
+
+        assert_eq!(Ok(10.0), func.call(&[10.0, 1.0]));
+        assert_eq!(Ok(0.0), func.call(&[10.0, 0.0]));
+        assert!(func.call(&[10.5, 0.5]).is_err());
+        assert!(func.call(&[10.0, 1.5]).is_err());
+    }
+
+    #[test]
+    fn choice_test() {
+        let func = ChoiceFunction::with_rng(ContextRng::with_seed(6));
+        let mut seen = [false; 3];
+
+        for _ in 0..100 {
+            let value: i64 = func.call(&[10, 20, 30]).unwrap();
+            seen[(value / 10 - 1) as usize] = true;
+        }
+
+        assert!(seen.iter().all(|b| *b));
+        assert!(Function::<i64>::call(&func, &[]).is_err());
+    }
+
+    #[test]
+    fn seed_test() {
+        fn sequence(seed: u64) -> Vec<f64> {
+            let rng = ContextRng::with_seed(seed);
+            let normal = RandNormalFunction::with_rng(rng.clone());
+            let poisson = RandPoissonFunction::with_rng(rng.clone());
+            let binomial = RandBinomialFunction::with_rng(rng);
+
+            (0..20)
+                .map(|_| {
+                    normal.call(&[0.0, 1.0]).unwrap()
+                        + poisson.call(&[100.0]).unwrap()
+                        + binomial.call(&[1000.0, 0.5]).unwrap()
+                })
+                .collect()
+        }
+
+        assert_eq!(sequence(42), sequence(42));
+        assert_ne!(sequence(42), sequence(43));
+    }
+}
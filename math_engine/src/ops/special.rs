@@ -0,0 +1,342 @@
+use num_traits::{FromPrimitive, ToPrimitive};
+
+use crate::error::*;
+use crate::function::Function;
+use crate::ops::math::try_to_float;
+use crate::utils::gamma::{gamma, ln_gamma};
+use crate::utils::special::{
+    bessel_j, bessel_y, beta, digamma, erf, erf_inv, erfc, lambert_w, zeta, BESSEL_MAX_ORDER,
+};
+use crate::Result;
+
+/// Defines a function of float arguments, `$args` are the valid number of arguments
+/// and `$defaults` the values of the arguments not provided.
+///
+/// A `NaN` result is reported as a value outside the domain of the function.
+macro_rules! special_function {
+    ($(#[$meta:meta])* $struct_name:ident, $name:expr, $args:expr, |$values:ident| $body:expr) => {
+        special_function!($(#[$meta])* $struct_name, $name, $args, [0.0; 2], |$values| $body);
+    };
+
+    ($(#[$meta:meta])* $struct_name:ident, $name:expr, $args:expr, $defaults:expr, |$values:ident| $body:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, Default)]
+        pub struct $struct_name;
+        impl<N: ToPrimitive + FromPrimitive> Function<N> for $struct_name {
+            #[inline]
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn call(&self, args: &[N]) -> Result<N> {
+                if !$args.contains(&args.len()) {
+                    return Err(Error::from(ErrorKind::InvalidArgumentCount));
+                }
+
+                let mut $values: [f64; 2] = $defaults;
+                for (value, arg) in $values.iter_mut().zip(args) {
+                    *value = try_to_float(arg)?;
+                }
+
+                let result: f64 = $body?;
+                check_result($name, &$values[..args.len()], result)
+            }
+        }
+    };
+}
+
+special_function!(
+    /// Computes the gamma function: `gamma(x)`, which is `(x - 1)!` for positive integers.
+    GammaFunction, "gamma", [1], |values| {
+        let [x, _] = values;
+        check_not_pole("gamma", x).map(|_| {
+            if x.fract() == 0.0 && x <= 171.0 {
+                // Exact for the integers, `gamma(172)` overflows
+                (2..x as u32).map(f64::from).product()
+            } else {
+                gamma(x)
+            }
+        })
+    }
+);
+
+special_function!(
+    /// Computes the natural logarithm of the absolute value of the gamma function: `lgamma(x)`.
+    LnGammaFunction, "lgamma", [1], |values| {
+        let [x, _] = values;
+        check_not_pole("lgamma", x).map(|_| ln_gamma(x))
+    }
+);
+
+special_function!(
+    /// Computes the digamma function, the logarithmic derivative of the gamma function: `digamma(x)`.
+    DigammaFunction, "digamma", [1], |values| {
+        let [x, _] = values;
+        check_not_pole("digamma", x).map(|_| digamma(x))
+    }
+);
+
+special_function!(
+    /// Computes the beta function: `beta(a, b)`.
+    BetaFunction, "beta", [2], |values| {
+        let [a, b] = values;
+        check_not_pole("beta", a)
+            .and_then(|_| check_not_pole("beta", b))
+            .map(|_| beta(a, b))
+    }
+);
+
+special_function!(
+    /// Computes the error function: `erf(x)`.
+    ErfFunction, "erf", [1], |values| Ok::<f64, Error>(erf(values[0]))
+);
+
+special_function!(
+    /// Computes the complementary error function: `erfc(x)`.
+    ErfcFunction, "erfc", [1], |values| Ok::<f64, Error>(erfc(values[0]))
+);
+
+special_function!(
+    /// Computes the inverse of the error function: `erfinv(x)`, for `x` in `(-1, 1)`.
+    ErfInvFunction, "erfinv", [1], |values| Ok::<f64, Error>(erf_inv(values[0]))
+);
+
+special_function!(
+    /// Computes the Riemann zeta function: `zeta(s)`.
+    ZetaFunction, "zeta", [1], |values| Ok::<f64, Error>(zeta(values[0]))
+);
+
+special_function!(
+    /// Computes the Bessel function of the first kind of integer order `n`: `besselj(n, x)`.
+    BesselJFunction, "besselj", [2], |values| {
+        let [n, x] = values;
+        bessel_order("besselj", n).map(|n| bessel_j(n, x))
+    }
+);
+
+special_function!(
+    /// Computes the Bessel function of the second kind of integer order `n`: `bessely(n, x)`,
+    /// for `x > 0`.
+    BesselYFunction, "bessely", [2], |values| {
+        let [n, x] = values;
+        bessel_order("bessely", n).map(|n| bessel_y(n, x))
+    }
+);
+
+special_function!(
+    /// Computes the Lambert W function, the solution of `w * exp(w) = x`: `lambertw(x)` for the
+    /// principal branch or `lambertw(x, k)` for the branch `k`, which can be 0 or -1.
+    LambertWFunction, "lambertw", [1, 2], |values| {
+        let [x, k] = values;
+        match integer_arg("lambertw", "branch", k)? {
+            branch @ (0 | -1) => Ok(lambert_w(branch, x)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("`lambertw` expects the branch 0 or -1 but was {}", k),
+            )),
+        }
+    }
+);
+
+/// Converts the result of a function, `NaN` means the arguments are outside its domain.
+fn check_result<N: FromPrimitive>(name: &str, args: &[f64], result: f64) -> Result<N> {
+    if result.is_nan() {
+        let args = args.iter().map(f64::to_string).collect::<Vec<_>>();
+        return Err(Error::new(
+            ErrorKind::NAN,
+            format!("`{}` is not defined for ({})", name, args.join(", ")),
+        ));
+    }
+
+    if result.is_infinite() {
+        return Err(Error::from(ErrorKind::Overflow));
+    }
+
+    N::from_f64(result).ok_or(Error::from(ErrorKind::Overflow))
+}
+
+/// Checks the value is not a pole of the gamma function: zero or a negative integer.
+fn check_not_pole(name: &str, value: f64) -> Result<()> {
+    if value <= 0.0 && value.fract() == 0.0 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "`{}` is not defined for zero or negative integers but was {}",
+                name, value
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn integer_arg(name: &str, arg: &str, value: f64) -> Result<i32> {
+    if value.fract() != 0.0 || value.abs() > i32::MAX as f64 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!("`{}` expects an integer {} but was {}", name, arg, value),
+        ));
+    }
+
+    Ok(value as i32)
+}
+
+/// Gets the order of a Bessel function, an integer with absolute value up to `BESSEL_MAX_ORDER`.
+fn bessel_order(name: &str, value: f64) -> Result<i32> {
+    let order = integer_arg(name, "order", value)?;
+    if order.unsigned_abs() > BESSEL_MAX_ORDER as u32 {
+        return Err(Error::new(
+            ErrorKind::InvalidInput,
+            format!(
+                "`{}` expects an order in [-{max}, {max}] but was {}",
+                name,
+                value,
+                max = BESSEL_MAX_ORDER
+            ),
+        ));
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_almost_eq(expected: f64, actual: Result<f64>, delta: f64) {
+        let actual = actual.unwrap();
+        assert!(
+            (expected - actual).abs() <= delta * expected.abs().max(1.0),
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    const ERROR: f64 = 0.000_000_000_01;
+
+    #[test]
+    fn gamma_test() {
+        assert_eq!(Ok(1.0), GammaFunction.call(&[1.0]));
+        assert_eq!(Ok(120.0), GammaFunction.call(&[6.0]));
+        assert_eq!(Ok(6_402_373_705_728_000.0), GammaFunction.call(&[19.0]));
+        assert_almost_eq(1.772_453_850_905_516, GammaFunction.call(&[0.5]), ERROR);
+        assert_almost_eq(-3.544_907_701_811_032, GammaFunction.call(&[-0.5]), ERROR);
+        assert_almost_eq(
+            7.257_415_615_307_999e306,
+            GammaFunction.call(&[171.0]),
+            ERROR,
+        );
+        assert_almost_eq(857.933_669_825_857_5, LnGammaFunction.call(&[200.0]), ERROR);
+        assert_almost_eq(
+            1.265_512_123_484_645_4,
+            LnGammaFunction.call(&[-0.5]),
+            ERROR,
+        );
+
+        assert!(GammaFunction.call(&[0.0]).is_err());
+        assert!(GammaFunction.call(&[-3.0]).is_err());
+        assert!(GammaFunction.call(&[172.0]).is_err());
+        assert!(LnGammaFunction.call(&[-1.0]).is_err());
+    }
+
+    #[test]
+    fn digamma_and_beta_test() {
+        assert_almost_eq(
+            -0.577_215_664_901_532_9,
+            DigammaFunction.call(&[1.0]),
+            ERROR,
+        );
+        assert_almost_eq(1.256_117_668_431_800_5, DigammaFunction.call(&[4.0]), ERROR);
+        assert_almost_eq(1.0 / 12.0, BetaFunction.call(&[2.0, 3.0]), ERROR);
+        assert_almost_eq(
+            0.196_349_540_849_362_08,
+            BetaFunction.call(&[2.5, 1.5]),
+            ERROR,
+        );
+
+        assert!(DigammaFunction.call(&[-2.0]).is_err());
+        assert!(BetaFunction.call(&[0.0, 1.0]).is_err());
+        assert!(BetaFunction.call(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn erf_test() {
+        assert_almost_eq(0.842_700_792_949_714_9, ErfFunction.call(&[1.0]), ERROR);
+        assert_almost_eq(0.004_677_734_981_047_266, ErfcFunction.call(&[2.0]), ERROR);
+        assert_almost_eq(0.476_936_276_204_469_9, ErfInvFunction.call(&[0.5]), ERROR);
+        assert_almost_eq(
+            -1.163_087_153_676_674_2,
+            ErfInvFunction.call(&[-0.9]),
+            ERROR,
+        );
+
+        assert!(ErfInvFunction.call(&[1.0]).is_err());
+        assert!(ErfInvFunction.call(&[2.0]).is_err());
+    }
+
+    #[test]
+    fn zeta_test() {
+        assert_almost_eq(1.644_934_066_848_226_4, ZetaFunction.call(&[2.0]), ERROR);
+        assert_almost_eq(1.082_323_233_711_138_2, ZetaFunction.call(&[4.0]), ERROR);
+        assert_almost_eq(-0.5, ZetaFunction.call(&[0.0]), ERROR);
+        assert_almost_eq(0.008_333_333_333_333_333, ZetaFunction.call(&[-3.0]), ERROR);
+
+        assert!(ZetaFunction.call(&[1.0]).is_err());
+    }
+
+    #[test]
+    fn bessel_test() {
+        assert_almost_eq(
+            0.765_197_686_557_966_6,
+            BesselJFunction.call(&[0.0, 1.0]),
+            ERROR,
+        );
+        assert_almost_eq(
+            0.352_834_028_615_637_7,
+            BesselJFunction.call(&[2.0, 2.0]),
+            ERROR,
+        );
+        assert_almost_eq(
+            -0.107_032_431_540_937_55,
+            BesselYFunction.call(&[1.0, 2.0]),
+            ERROR,
+        );
+        assert_almost_eq(
+            -0.617_408_104_190_682_7,
+            BesselYFunction.call(&[2.0, 2.0]),
+            ERROR,
+        );
+
+        assert!(BesselJFunction.call(&[0.5, 1.0]).is_err());
+        assert!(BesselYFunction.call(&[0.0, 0.0]).is_err());
+        assert!(BesselYFunction.call(&[0.0]).is_err());
+        assert!(BesselJFunction.call(&[2_147_483_647.0, 1.0]).is_err());
+        assert!(BesselJFunction.call(&[-1001.0, 1.0]).is_err());
+        assert!(BesselYFunction.call(&[1e12, 1.0]).is_err());
+    }
+
+    #[test]
+    fn lambert_w_test() {
+        assert_almost_eq(
+            0.567_143_290_409_783_9,
+            LambertWFunction.call(&[1.0]),
+            ERROR,
+        );
+        assert_almost_eq(
+            1.0,
+            LambertWFunction.call(&[std::f64::consts::E, 0.0]),
+            ERROR,
+        );
+        assert_almost_eq(
+            -1.781_337_023_421_627_7,
+            LambertWFunction.call(&[-0.3, -1.0]),
+            ERROR,
+        );
+
+        assert!(LambertWFunction.call(&[-1.0]).is_err());
+        assert!(LambertWFunction.call(&[1.0, -1.0]).is_err());
+        assert!(LambertWFunction.call(&[1.0, 1.0]).is_err());
+        assert!(LambertWFunction.call(&[1.0, 0.5]).is_err());
+    }
+}
@@ -1,11 +1,13 @@
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
-use num_traits::{FromPrimitive, Zero};
+use num_traits::{FromPrimitive, ToPrimitive, Zero};
 
 use crate::error::*;
 use crate::function::{
     Associativity, BinaryFunction, Function, Notation, Precedence, UnaryFunction,
 };
+use crate::ops::integer::{integer_functions, Unchecked};
+use crate::ops::math::{percentile_rank, sorted_args};
 use crate::Result;
 
 pub struct AddOperator;
@@ -214,6 +216,76 @@ impl<N: Add<N, Output = N> + Div<N, Output = N> + FromPrimitive + Clone> Functio
     }
 }
 
+pub struct MedianFunction;
+impl<N> Function<N> for MedianFunction
+where
+    N: Add<N, Output = N> + Sub<N, Output = N> + Div<N, Output = N> + FromPrimitive + PartialOrd + Clone,
+{
+    fn name(&self) -> &str {
+        "median"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        let sorted = sorted_args(args)?;
+        let mid = sorted.len() / 2;
+
+        if sorted.len() % 2 == 1 {
+            Ok(sorted[mid].clone())
+        } else {
+            let (a, b) = (sorted[mid - 1].clone(), sorted[mid].clone());
+            Ok(a.clone() + (b - a) / N::from_u8(2).unwrap())
+        }
+    }
+}
+
+/// Gets the percentile `p` in `[0, 100]` of the values: `percentile(p, ...)`,
+/// interpolating linearly between the closest ranks.
+pub struct PercentileFunction;
+impl<N> Function<N> for PercentileFunction
+where
+    N: Add<N, Output = N> + Sub<N, Output = N> + ToPrimitive + FromPrimitive + PartialOrd + Clone,
+{
+    fn name(&self) -> &str {
+        "percentile"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        if args.len() < 2 {
+            return Err(Error::from(ErrorKind::InvalidArgumentCount));
+        }
+
+        let sorted = sorted_args(&args[1..])?;
+        let (index, fraction) = percentile_rank(&args[0], sorted.len())?;
+
+        if fraction == 0.0 {
+            return Ok(sorted[index].clone());
+        }
+
+        let (a, b) = (sorted[index].clone(), sorted[index + 1].clone());
+        let delta = (b - a.clone())
+            .to_f64()
+            .and_then(|n| N::from_f64(n * fraction))
+            .ok_or(Error::from(ErrorKind::Overflow))?;
+
+        Ok(a + delta)
+    }
+}
+
+/// Gets the difference between the max and min values.
+pub struct RangeFunction;
+impl<N: Sub<N, Output = N> + PartialOrd + Clone> Function<N> for RangeFunction {
+    fn name(&self) -> &str {
+        "range"
+    }
+
+    fn call(&self, args: &[N]) -> Result<N> {
+        let sorted = sorted_args(args)?;
+        Ok(sorted[sorted.len() - 1].clone() - sorted[0].clone())
+    }
+}
+
+integer_functions!(Unchecked);
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -311,4 +383,58 @@ mod tests{
 
         assert!(instance.call(empty_array::<i64>().as_ref()).is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn median_test(){
+        let instance = MedianFunction;
+
+        assert_eq!(instance.call(&[3_f64, 1_f64, 2_f64]), Ok(2_f64));
+        assert_eq!(instance.call(&[4_f64, 1_f64, 2_f64, 3_f64]), Ok(2.5_f64));
+
+        assert!(instance.call(empty_array::<i64>().as_ref()).is_err());
+    }
+
+    #[test]
+    fn percentile_test(){
+        let instance = PercentileFunction;
+
+        assert_eq!(instance.call(&[50_f64, 4_f64, 1_f64, 3_f64, 2_f64]), Ok(2.5_f64));
+        assert_eq!(instance.call(&[25_f64, 10_f64, 20_f64, 30_f64]), Ok(15_f64));
+
+        assert!(instance.call(&[101_f64, 1_f64]).is_err());
+    }
+
+    #[test]
+    fn integer_functions_test(){
+        assert_eq!(GcdFunction.call(&[12, 18]), Ok(6));
+        assert_eq!(LcmFunction.call(&[4_f64, 6_f64]), Ok(12_f64));
+        assert_eq!(NcrFunction.call(&[10, 3]), Ok(120));
+        assert_eq!(NprFunction.call(&[10, 3]), Ok(720));
+        assert_eq!(IsPrimeFunction.call(&[7919]), Ok(1));
+        assert_eq!(NextPrimeFunction.call(&[7919]), Ok(7927));
+        assert_eq!(DivisorCountFunction.call(&[12]), Ok(6));
+        assert_eq!(FibFunction.call(&[20]), Ok(6765));
+        assert_eq!(ModPowFunction.call(&[3, 200, 13]), Ok(9));
+        assert_eq!(ModInvFunction.call(&[7_f64, 26_f64]), Ok(15_f64));
+
+        assert!(GcdFunction.call(&[1.5_f64, 3_f64]).is_err());
+        assert!(ModInvFunction.call(&[2, 4]).is_err());
+    }
+
+    #[test]
+    fn integer_functions_max_value_test(){
+        let max = i64::max_value();
+
+        assert_eq!(GcdFunction.call(&[max, 1]), Ok(1));
+        assert_eq!(IsPrimeFunction.call(&[max]), Ok(0));
+        assert_eq!(MinFactorFunction.call(&[max]), Ok(7));
+        assert_eq!(ModPowFunction.call(&[3, 5, max]), Ok(243));
+        assert_eq!(NextPrimeFunction.call(&[max - 25]), Ok(max - 24));
+
+        assert_eq!(FibFunction.call(&[92_i64]), Ok(7_540_113_804_746_346_429));
+        assert_eq!(ErrorKind::Overflow, FibFunction.call(&[93_i64]).unwrap_err().kind());
+        assert_eq!(ErrorKind::Overflow, NcrFunction.call(&[100_i64, 50]).unwrap_err().kind());
+        assert_eq!(ErrorKind::Overflow, NextPrimeFunction.call(&[max]).unwrap_err().kind());
+        assert_eq!(ErrorKind::Overflow, NprFunction.call(&[u8::max_value(), 2]).unwrap_err().kind());
+    }
+}
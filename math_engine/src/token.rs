@@ -3,6 +3,7 @@ use Token::*;
 
 /// Represents a token in an expression.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Token<N> {
     /// A number
     Number(N),
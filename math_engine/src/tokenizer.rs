@@ -9,6 +9,8 @@ use crate::token::Token;
 use crate::utils::extensions::{ OptionStrExt, StrExt };
 use crate::utils::string_tokenizer::{StringTokenizer, TokenizeKind};
 
+pub mod latex;
+
 /// Provides a way to retrieve the tokens of an expression.
 pub trait Tokenize<N> {
     /// Gets the tokens of the specified expression.
@@ -71,8 +73,6 @@ where
     N: FromStr,
 {
     fn tokenize(&self, expression: &str) -> Result<Vec<Token<N>>> {
-        const STRING_TOKENIZER: StringTokenizer =
-            StringTokenizer::new(TokenizeKind::RemoveWhiteSpaces);
         const COMMA: &str = ",";
         const WHITESPACE: &str = " ";
 
@@ -83,14 +83,29 @@ where
             ));
         }
 
+        // Context that contains the variables, constants and functions.
+        let context = self.context;
+        let limits = &context.config().limits;
+
+        if let Some(max) = limits.max_expression_len {
+            let len = expression.chars().count();
+            if len > max {
+                return Err(Error::new(
+                    ErrorKind::ExpressionTooLong,
+                    format!("Expression length is {} but the max is {}", len, max),
+                ));
+            }
+        }
+
         // `Vec` used for fast access indexing, Iterator.nth(..) could be O(N)
-        let raw_tokens = STRING_TOKENIZER.get_tokens(expression);
+        // The shifts are only read as a single token if defined, to not break `Max<1, Sum<2, 3>>`
+        let raw_tokens = StringTokenizer::new(TokenizeKind::RemoveWhiteSpaces)
+            .with_shift_operators(context.is_binary_function("<<"), context.is_binary_function(">>"))
+            .get_tokens(expression);
         // Actual iterator over the string tokens.
         let mut iter = raw_tokens.iter().enumerate().peekable();
         // Stores the tokens to return.
         let mut tokens = Vec::new();
-        // Context that contains the variables, constants and functions.
-        let context = self.context;
 
         while let Some((pos, string)) = iter.next() {
             if is_number(string) {
@@ -184,6 +199,15 @@ where
             }
         }
 
+        if let Some(max) = limits.max_tokens {
+            if tokens.len() > max {
+                return Err(Error::new(
+                    ErrorKind::TooManyTokens,
+                    format!("Expression has {} tokens but the max is {}", tokens.len(), max),
+                ));
+            }
+        }
+
         Ok(tokens)
     }
 }
@@ -440,4 +464,19 @@ mod tests {
             );
         }
     }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn tokenize_default_between_threads_test() {
+        let handles = (0..8)
+            .map(|n| std::thread::spawn(move || Tokenizer::<i64>::new().tokenize(&format!("{} + 1", n)).unwrap()))
+            .collect::<Vec<_>>();
+
+        for (n, handle) in handles.into_iter().enumerate() {
+            assert_eq!(
+                vec![Number(n as i64), BinaryOperator('+'.to_string()), Number(1)],
+                handle.join().unwrap()
+            );
+        }
+    }
 }
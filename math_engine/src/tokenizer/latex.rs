@@ -0,0 +1,607 @@
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use crate::context::{Context, DefaultContext};
+use crate::error::{Error, ErrorKind};
+use crate::formatter::greek_letter;
+use crate::formatter::latex::LATEX_FUNCTIONS;
+use crate::num::checked::CheckedNum;
+use crate::token::Token;
+use crate::tokenizer::{Tokenize, Tokenizer};
+use crate::Result;
+
+/// A `Tokenizer` for math expressions written in `LaTeX`.
+///
+/// The `LaTeX` is converted to an `infix` expression which is tokenized using the `Tokenizer`,
+/// so the tokens can be evaluated with the same `Context`.
+///
+/// Supports: `\frac{a}{b}`, `\sqrt{x}`, `\sqrt[n]{x}`, `x^{2}`, `\cdot`, `\times`, `\div`, `\bmod`,
+/// greek letters as `\pi`, functions as `\sin`, `\log_{b}` or `\operatorname{name}`
+/// and the delimiters `\left( \right)`, `\left[ \right]` and `\left| \right|`.
+///
+/// Adjacent values are multiplied as in `LaTeX`, so `2\pi` is `2 * pi` and `\frac{1}{2}\pi`
+/// is `(1 / 2) * pi`, but two numbers as `2 3` are not.
+///
+/// `\sqrt[n]{x}` is converted to `root(x, n)` if the context has a `root` function, otherwise to
+/// `x^(1/n)`, which is an error for integer types where `1/n` is truncated, except for `n = 2`.
+///
+/// # Example
+/// ```
+/// use math_engine::tokenizer::latex::LatexTokenizer;
+/// use math_engine::tokenizer::{Tokenizer, Tokenize};
+///
+/// let latex : LatexTokenizer<i64> = LatexTokenizer::new();
+/// let tokenizer : Tokenizer<i64> = Tokenizer::new();
+/// assert_eq!(tokenizer.tokenize("2 * sin(30)").unwrap(), latex.tokenize(r"2 \cdot \sin 30").unwrap());
+/// ```
+pub struct LatexTokenizer<'a, N, C = DefaultContext<'a, N>>
+where
+    C: Context<'a, N>,
+{
+    /// The context which contains the variables, constants and functions used
+    /// for tokenize and expression.
+    context: &'a C,
+    _marker: PhantomData<N>,
+}
+
+impl<'a, N> LatexTokenizer<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    /// Constructs a new `LatexTokenizer` using the default checked context.
+    #[inline]
+    pub fn new() -> Self {
+        LatexTokenizer {
+            context: unsafe { DefaultContext::instance() },
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, N> Default for LatexTokenizer<'a, N, DefaultContext<'a, N>>
+where
+    N: CheckedNum + 'static,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, N, C> LatexTokenizer<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: FromStr,
+{
+    /// Constructs a new `LatexTokenizer` with the given `Context`.
+    #[inline]
+    pub fn with_context(context: &'a C) -> Self {
+        LatexTokenizer {
+            context,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, N, C> Tokenize<N> for LatexTokenizer<'a, N, C>
+where
+    C: Context<'a, N>,
+    N: FromStr,
+{
+    fn tokenize(&self, expression: &str) -> Result<Vec<Token<N>>> {
+        // `\sqrt[n]{x}` uses the `root` function of the context if any, otherwise is `x^(1/n)`
+        // but `1/n` is truncated to 0 by integer types, these can't be parsed from `0.5`
+        let nth_root = if self.context.is_function("root") {
+            NthRoot::Function
+        } else if N::from_str("0.5").is_ok() {
+            NthRoot::Pow
+        } else {
+            NthRoot::Unsupported
+        };
+
+        let infix = LatexReader::new(expression, nth_root).read(Until::End)?;
+        let tokens = Tokenizer::with_context(self.context).tokenize(infix.trim())?;
+        Ok(insert_implicit_mul(tokens))
+    }
+}
+
+/// Inserts a `*` between adjacent values: `2\pi`, `\frac{1}{2}\pi`, `x\sin(30)`.
+fn insert_implicit_mul<N>(tokens: Vec<Token<N>>) -> Vec<Token<N>> {
+    let mut output: Vec<Token<N>> = Vec::with_capacity(tokens.len());
+
+    for token in tokens {
+        let is_implicit_mul = match (output.last(), &token) {
+            // Two numbers are not multiplied: `2 3`
+            (Some(Token::Number(_)), Token::Number(_)) => false,
+            (
+                Some(Token::Number(_) | Token::Variable(_) | Token::Constant(_) | Token::GroupingClose(_)),
+                Token::Number(_) | Token::Variable(_) | Token::Constant(_) | Token::Function(_) | Token::GroupingOpen(_),
+            ) => true,
+            _ => false,
+        };
+
+        if is_implicit_mul {
+            output.push(Token::BinaryOperator("*".to_string()));
+        }
+
+        output.push(token);
+    }
+
+    output
+}
+
+/// Where the reading of a group of `LaTeX` ends.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum Until {
+    /// The end of the input.
+    End,
+    /// The closing symbol of a group, eg: `}`.
+    Char(char),
+    /// The `\right` command.
+    Right,
+}
+
+/// How `\sqrt[n]{x}` is converted to `infix`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+enum NthRoot {
+    /// Using the `root` function: `root(x, n)`.
+    Function,
+    /// As a power: `x^(1/n)`.
+    Pow,
+    /// Only the square root is supported: `\sqrt[2]{x}`.
+    Unsupported,
+}
+
+/// Converts `LaTeX` to an `infix` expression.
+struct LatexReader {
+    chars: Vec<char>,
+    pos: usize,
+    nth_root: NthRoot,
+}
+
+impl LatexReader {
+    fn new(latex: &str, nth_root: NthRoot) -> Self {
+        LatexReader {
+            chars: latex.chars().collect(),
+            pos: 0,
+            nth_root,
+        }
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    #[inline]
+    fn skip_whitespaces(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn read(&mut self, until: Until) -> Result<String> {
+        let mut output = String::new();
+
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '}' | ')' | ']' if until == Until::Char(c) => return Ok(output),
+                '}' | ')' | ']' => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidExpression,
+                        format!("Unexpected `{}`", c),
+                    ))
+                }
+                '{' => {
+                    let inner = self.read(Until::Char('}'))?;
+                    push_group(&mut output, &inner);
+                }
+                '(' => {
+                    let inner = self.read(Until::Char(')'))?;
+                    output.push_str(&format!("({})", inner));
+                }
+                '[' => {
+                    let inner = self.read(Until::Char(']'))?;
+                    output.push_str(&format!("[{}]", inner));
+                }
+                '^' => {
+                    let exponent = self.read_argument()?;
+                    output.push('^');
+                    push_group(&mut output, &exponent);
+                }
+                '_' => {
+                    return Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        "Subscripts are only supported for `\\log`",
+                    ))
+                }
+                // Inline math delimiters
+                '$' => {}
+                '\\' => {
+                    let name = self.read_command_name()?;
+                    if name == "right" {
+                        return if until == Until::Right {
+                            Ok(output)
+                        } else {
+                            Err(Error::new(ErrorKind::InvalidExpression, "Unexpected `\\right`"))
+                        };
+                    }
+
+                    self.push_command(&name, &mut output)?;
+                }
+                c if c.is_whitespace() => output.push(' '),
+                _ => output.push(c),
+            }
+        }
+
+        match until {
+            Until::End => Ok(output),
+            Until::Char(c) => Err(Error::new(
+                ErrorKind::InvalidExpression,
+                format!("Expected `{}`", c),
+            )),
+            Until::Right => Err(Error::new(ErrorKind::InvalidExpression, "Expected `\\right`")),
+        }
+    }
+
+    /// Reads the name of a command after the `\`, eg: `frac`, `pi`, `,`.
+    fn read_command_name(&mut self) -> Result<String> {
+        let mut name = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_ascii_alphabetic() {
+                name.push(c);
+                self.pos += 1;
+            } else {
+                break;
+            }
+        }
+
+        // Commands of 1 symbol: `\,`, `\{`
+        if name.is_empty() {
+            match self.peek() {
+                Some(c) => {
+                    self.pos += 1;
+                    name.push(c);
+                }
+                None => return Err(Error::new(ErrorKind::InvalidExpression, "Expected command after `\\`")),
+            }
+        }
+
+        Ok(name)
+    }
+
+    /// Reads a required argument: `{...}` or a single character or command.
+    fn read_argument(&mut self) -> Result<String> {
+        self.skip_whitespaces();
+
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                self.read(Until::Char('}'))
+            }
+            Some('\\') => {
+                self.pos += 1;
+                let name = self.read_command_name()?;
+                let mut output = String::new();
+                self.push_command(&name, &mut output)?;
+                Ok(output)
+            }
+            Some(c) if c.is_alphanumeric() => {
+                self.pos += 1;
+                Ok(c.to_string())
+            }
+            Some(c) => Err(Error::new(
+                ErrorKind::InvalidExpression,
+                format!("Invalid argument: `{}`", c),
+            )),
+            None => Err(Error::new(ErrorKind::InvalidExpression, "Missing argument")),
+        }
+    }
+
+    /// Reads a delimiter after `\left` or `\right`.
+    fn read_delimiter(&mut self) -> Result<char> {
+        self.skip_whitespaces();
+
+        match self.peek() {
+            Some('\\') => {
+                self.pos += 1;
+                match self.read_command_name()?.as_str() {
+                    "{" => Ok('{'),
+                    "}" => Ok('}'),
+                    name => Err(Error::new(
+                        ErrorKind::InvalidInput,
+                        format!("Unsupported delimiter: `\\{}`", name),
+                    )),
+                }
+            }
+            Some(c) => {
+                self.pos += 1;
+                Ok(c)
+            }
+            None => Err(Error::new(ErrorKind::InvalidExpression, "Expected delimiter")),
+        }
+    }
+
+    /// Reads the content of a `\left ... \right` group.
+    fn read_left_right(&mut self) -> Result<String> {
+        let open = self.read_delimiter()?;
+        let inner = self.read(Until::Right)?;
+        let close = self.read_delimiter()?;
+
+        match (open, close) {
+            ('(', ')') | ('{', '}') => Ok(format!("({})", inner)),
+            ('[', ']') => Ok(format!("[{}]", inner)),
+            ('|', '|') => Ok(format!("abs({})", inner)),
+            _ => Err(Error::new(
+                ErrorKind::InvalidExpression,
+                format!("Mismatched delimiters: `\\left{}` and `\\right{}`", open, close),
+            )),
+        }
+    }
+
+    /// Reads a text argument as in `\operatorname{name}`.
+    fn read_text(&mut self) -> Result<String> {
+        self.skip_whitespaces();
+        if self.peek() != Some('{') {
+            return Err(Error::new(ErrorKind::InvalidExpression, "Expected `{`"));
+        }
+
+        self.pos += 1;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            if c == '}' {
+                return Ok(text.trim().to_string());
+            }
+            text.push(c);
+        }
+
+        Err(Error::new(ErrorKind::InvalidExpression, "Expected `}`"))
+    }
+
+    fn push_command(&mut self, name: &str, output: &mut String) -> Result<()> {
+        match name {
+            // Spacing
+            "," | ";" | ":" | "!" | " " | "quad" | "qquad" => output.push(' '),
+            "cdot" | "times" | "ast" => output.push_str(" * "),
+            "div" => output.push_str(" / "),
+            "bmod" | "mod" => output.push_str(" mod "),
+            "left" => {
+                let group = self.read_left_right()?;
+                output.push_str(&group);
+            }
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.read_argument()?;
+                let denominator = self.read_argument()?;
+                output.push('(');
+                push_group(output, &numerator);
+                output.push('/');
+                push_group(output, &denominator);
+                output.push(')');
+            }
+            "sqrt" => {
+                self.skip_whitespaces();
+                let index = if self.peek() == Some('[') {
+                    self.pos += 1;
+                    Some(self.read(Until::Char(']'))?)
+                } else {
+                    None
+                };
+
+                let radicand = self.read_argument()?;
+                match index.as_deref().map(str::trim) {
+                    None | Some("2") => output.push_str(&format!(" sqrt({}) ", radicand)),
+                    Some(n) => match self.nth_root {
+                        NthRoot::Function => output.push_str(&format!(" root({}, {}) ", radicand, n)),
+                        // x^(1/n)
+                        NthRoot::Pow => output.push_str(&format!(" (({})^(1/({}))) ", radicand, n)),
+                        NthRoot::Unsupported => {
+                            return Err(Error::new(
+                                ErrorKind::InvalidInput,
+                                format!("`\\sqrt[{}]` requires a `root` function for integer types", n),
+                            ))
+                        }
+                    },
+                }
+            }
+            "operatorname" => {
+                let func = self.read_text()?;
+                self.push_function(&func, output)?;
+            }
+            "mathrm" | "mathit" | "text" => {
+                let text = self.read_text()?;
+                output.push_str(&format!(" {} ", text));
+            }
+            "arcsin" | "arccos" | "arctan" => {
+                // arcsin -> asin
+                let func = format!("a{}", &name[3..]);
+                self.push_function(&func, output)?;
+            }
+            _ if LATEX_FUNCTIONS.contains(&name) => self.push_function(name, output)?,
+            _ if greek_letter(&name.to_lowercase()).is_some() => {
+                output.push_str(&format!(" {} ", name))
+            }
+            // \varepsilon, \varphi, \vartheta...
+            _ if name.starts_with("var") && greek_letter(&name[3..]).is_some() => {
+                output.push_str(&format!(" {} ", &name[3..]))
+            }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Unsupported LaTeX command: `\\{}`", name),
+                ))
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pushes a function call, the arguments can be in parentheses or a single value: `\sin x`.
+    fn push_function(&mut self, name: &str, output: &mut String) -> Result<()> {
+        let mut base = None;
+        let mut power = None;
+
+        // \log_{2}, \sin^{2}
+        loop {
+            self.skip_whitespaces();
+            match self.peek() {
+                Some('_') if base.is_none() && name == "log" => {
+                    self.pos += 1;
+                    base = Some(self.read_argument()?);
+                }
+                Some('^') if power.is_none() => {
+                    self.pos += 1;
+                    power = Some(self.read_argument()?);
+                }
+                _ => break,
+            }
+        }
+
+        let args = match self.peek() {
+            Some('(') => {
+                self.pos += 1;
+                self.read(Until::Char(')'))?
+            }
+            Some('{') => {
+                self.pos += 1;
+                self.read(Until::Char('}'))?
+            }
+            Some('\\') if self.chars[self.pos..].starts_with(&['\\', 'l', 'e', 'f', 't']) => {
+                self.pos += "\\left".len();
+                let group = self.read_left_right()?;
+                if !group.starts_with('(') {
+                    return Err(Error::new(
+                        ErrorKind::InvalidExpression,
+                        format!("Function arguments of `{}` are not within a parentheses", name),
+                    ));
+                }
+
+                group[1..group.len() - 1].to_string()
+            }
+            // \sin 30, \ln x
+            Some(c) if c.is_alphanumeric() => {
+                let mut arg = String::new();
+                while let Some(c) = self.peek().filter(|c| c.is_alphanumeric() || *c == '.') {
+                    self.pos += 1;
+                    arg.push(c);
+                }
+                arg
+            }
+            _ => self.read_argument()?,
+        };
+
+        let call = match base {
+            Some(base) => format!("{}({}, {})", name, args, base),
+            None => format!("{}({})", name, args),
+        };
+
+        match power {
+            Some(power) => output.push_str(&format!(" ({})^({}) ", call, power)),
+            None => output.push_str(&format!(" {} ", call)),
+        }
+
+        Ok(())
+    }
+}
+
+/// Pushes the content of a `LaTeX` group, using parentheses only if is not a single value.
+fn push_group(output: &mut String, inner: &str) {
+    let inner = inner.trim();
+    if !inner.is_empty() && inner.chars().all(|c| c.is_alphanumeric() || c == '.') {
+        output.push(' ');
+        output.push_str(inner);
+        output.push(' ');
+    } else {
+        output.push('(');
+        output.push_str(inner);
+        output.push(')');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::evaluator::{Evaluate, Evaluator};
+
+    fn eval_latex(latex: &str) -> Result<f64> {
+        let evaluator = Evaluator::with_context(DefaultContext::new_unchecked());
+        let tokens = LatexTokenizer::with_context(evaluator.context()).tokenize(latex)?;
+        evaluator.eval_tokens(&tokens)
+    }
+
+    #[test]
+    fn same_tokens_test() {
+        let tokenizer: Tokenizer<i64> = Tokenizer::new();
+        let latex: LatexTokenizer<i64> = LatexTokenizer::new();
+
+        assert_eq!(tokenizer.tokenize("2 * 3").unwrap(), latex.tokenize(r"2 \cdot 3").unwrap());
+        assert_eq!(tokenizer.tokenize("2 * -3").unwrap(), latex.tokenize(r"2 \times -3").unwrap());
+        assert_eq!(tokenizer.tokenize("sin(30)").unwrap(), latex.tokenize(r"\sin(30)").unwrap());
+        assert_eq!(tokenizer.tokenize("sin(30)").unwrap(), latex.tokenize(r"\sin 30").unwrap());
+        assert_eq!(tokenizer.tokenize("(2 + 3)").unwrap(), latex.tokenize(r"\left( 2 + 3 \right)").unwrap());
+        assert_eq!(tokenizer.tokenize("2 ^ 10").unwrap(), latex.tokenize(r"2^{10}").unwrap());
+        assert_eq!(tokenizer.tokenize("(1 / 2)").unwrap(), latex.tokenize(r"\frac{1}{2}").unwrap());
+        assert_eq!(tokenizer.tokenize("sqrt(4)").unwrap(), latex.tokenize(r"\sqrt{4}").unwrap());
+        assert_eq!(tokenizer.tokenize("pi").unwrap(), latex.tokenize(r"\pi").unwrap());
+        assert_eq!(tokenizer.tokenize("abs(-5)").unwrap(), latex.tokenize(r"\left| -5 \right|").unwrap());
+    }
+
+    #[test]
+    fn eval_latex_test() {
+        assert_eq!(eval_latex(r"\frac{1 + 2}{4}"), Ok(0.75));
+        assert_eq!(eval_latex(r"\frac12"), Ok(0.5));
+        assert_eq!(eval_latex(r"2^{3} \cdot 2"), Ok(16.0));
+        assert_eq!(eval_latex(r"2^3 + 1"), Ok(9.0));
+        assert_eq!(eval_latex(r"\sqrt[3]{27}"), Ok(3.0));
+        assert_eq!(eval_latex(r"\sqrt{16} \div 2"), Ok(2.0));
+        assert_eq!(eval_latex(r"\log_{2}{8}"), Ok(3.0));
+        assert_eq!(eval_latex(r"\operatorname{max}(1, 5, 3)"), Ok(5.0));
+        assert_eq!(eval_latex(r"$\left(\frac{10}{5}\right)!$"), Ok(2.0));
+        assert_eq!(eval_latex(r"\pi"), Ok(std::f64::consts::PI));
+    }
+
+    #[test]
+    fn latex_implicit_mul_test() {
+        use std::f64::consts::PI;
+
+        assert_eq!(eval_latex(r"2\pi"), Ok(2.0 * PI));
+        assert_eq!(eval_latex(r"\frac{1}{2}\pi"), Ok(0.5 * PI));
+        assert_eq!(eval_latex(r"3\left(1 + 2\right)"), Ok(9.0));
+        assert_eq!(eval_latex(r"2\sqrt{16}"), Ok(8.0));
+        assert_eq!(eval_latex(r"\pi^{2} \cdot 2"), Ok(PI.powf(2.0) * 2.0));
+        assert!(eval_latex(r"2 3").is_err());
+    }
+
+    #[test]
+    fn latex_nth_root_integer_test() {
+        let mut evaluator: Evaluator<i64> = Evaluator::with_context(DefaultContext::new_checked());
+        fn eval(evaluator: &Evaluator<i64>, latex: &str) -> Result<i64> {
+            let tokens = LatexTokenizer::with_context(evaluator.context()).tokenize(latex)?;
+            evaluator.eval_tokens(&tokens)
+        }
+
+        assert_eq!(eval(&evaluator, r"\sqrt[3]{27}"), Ok(3));
+
+        // `1/n` is 0 for integers so only the square root can be used without `root`
+        evaluator.mut_context().remove_function("root");
+        assert_eq!(eval(&evaluator, r"\sqrt[2]{16}"), Ok(4));
+        assert_eq!(eval(&evaluator, r"\sqrt{16}"), Ok(4));
+        assert_eq!(eval(&evaluator, r"\sqrt[3]{27}").unwrap_err().kind(), ErrorKind::InvalidInput);
+
+        evaluator.mut_context().add_function(crate::ops::math::RootFunction);
+        assert_eq!(eval(&evaluator, r"\sqrt[3]{27}"), Ok(3));
+        assert_eq!(eval(&evaluator, r"\sqrt[3]{64}"), Ok(4));
+        assert_eq!(eval(&evaluator, r"\sqrt[2]{16}"), Ok(4));
+    }
+
+    #[test]
+    fn invalid_latex_test() {
+        assert!(eval_latex(r"\frac{1}").is_err());
+        assert!(eval_latex(r"\left( 2 + 3").is_err());
+        assert!(eval_latex(r"\left( 2 + 3 \right]").is_err());
+        assert!(eval_latex(r"2 + 3}").is_err());
+        assert!(eval_latex(r"x_{1}").is_err());
+        assert!(eval_latex(r"\unknown{2}").is_err());
+    }
+}
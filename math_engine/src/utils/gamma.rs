@@ -1,27 +1,37 @@
 use num_traits::ToPrimitive;
 use std::f64::consts;
 
-pub fn gamma(mut x: f64) -> f64 {
-    //Using Coefficients from: https://mrob.com/pub/ries/lanczos-gamma.html
-    const G: f64 = 4.742_187_5;
-    const P: [f64; 15] = [
-        0.999_999_999_999_997_091_82,
-        57.156_235_665_862_923_517,
-        -59.597_960_355_475_491_248,
-        14.136_097_974_741_747_174,
-        -0.491_913_816_097_620_199_78,
-        0.000_033_994_649_984_811_888_699,
-        0.000_046_523_628_927_048_575_665,
-        -0.000_098_374_475_304_879_564_677,
-        0.000_158_088_703_224_912_488_84,
-        -0.000_210_264_441_724_104_883_19,
-        0.000_217_439_618_115_212_643_2,
-        -0.000_164_318_106_536_763_890_22,
-        0.000_084_418_223_983_852_743_293,
-        -0.000_026_190_838_401_581_408_67,
-        0.000_003_689_918_265_953_162_270_4,
-    ];
+//Using Coefficients from: https://mrob.com/pub/ries/lanczos-gamma.html
+const G: f64 = 4.742_187_5;
+const P: [f64; 15] = [
+    0.999_999_999_999_997_091_82,
+    57.156_235_665_862_923_517,
+    -59.597_960_355_475_491_248,
+    14.136_097_974_741_747_174,
+    -0.491_913_816_097_620_199_78,
+    0.000_033_994_649_984_811_888_699,
+    0.000_046_523_628_927_048_575_665,
+    -0.000_098_374_475_304_879_564_677,
+    0.000_158_088_703_224_912_488_84,
+    -0.000_210_264_441_724_104_883_19,
+    0.000_217_439_618_115_212_643_2,
+    -0.000_164_318_106_536_763_890_22,
+    0.000_084_418_223_983_852_743_293,
+    -0.000_026_190_838_401_581_408_67,
+    0.000_003_689_918_265_953_162_270_4,
+];
 
+/// Computes the sum of the Lanczos series for `x + 1`.
+#[inline]
+fn lanczos_sum(x: f64) -> f64 {
+    let mut factor: f64 = P[0];
+    for (n, coefficient) in P.iter().enumerate().skip(1) {
+        factor += coefficient / (x + n.to_f64().unwrap());
+    }
+    factor
+}
+
+pub fn gamma(mut x: f64) -> f64 {
     // Using Lanczos approximation
     // ~10^-13 precision
     // See: https://en.wikipedia.org/wiki/Lanczos_approximation
@@ -31,11 +41,7 @@ pub fn gamma(mut x: f64) -> f64 {
         // Lanczos solve gamma for (x + 1)
         x -= 1.0;
 
-        let mut factor: f64 = P[0];
-        for (n, coefficient) in P.iter().enumerate().skip(1) {
-            factor += coefficient / (x + n.to_f64().unwrap());
-        }
-
+        let factor = lanczos_sum(x);
         let t: f64 = x + G + 0.5;
 
         // Result = sqrt( 2 * PI) * t ^ (x + 1/2) * e^(-t) * factor
@@ -43,6 +49,23 @@ pub fn gamma(mut x: f64) -> f64 {
     }
 }
 
+/// Computes the natural logarithm of the absolute value of the gamma function,
+/// unlike `gamma` this not overflow for large values.
+pub fn ln_gamma(mut x: f64) -> f64 {
+    if x < 0.5 {
+        // Reflection formula: ln|Γ(x)| = ln(π / |sin(πx)|) - ln|Γ(1 - x)|
+        f64::ln(consts::PI / f64::sin(consts::PI * x).abs()) - ln_gamma(1.0 - x)
+    } else {
+        x -= 1.0;
+
+        let factor = lanczos_sum(x);
+        let t: f64 = x + G + 0.5;
+
+        // Result = ln(sqrt(2 * PI)) + (x + 1/2) * ln(t) - t + ln(factor)
+        0.5 * f64::ln(2.0 * consts::PI) + (x + 0.5) * t.ln() - t + factor.ln()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +101,14 @@ mod tests {
         //assert_eq!(gamma_fact(0.5), 0.88622692545275801364908374167057);
         //assert_eq!(gamma_fact(0.1), 0.95135076986687318362924871772654);
     }
+
+    #[test]
+    fn ln_gamma_test() {
+        const ERROR: f64 = 0.000000001;
+        assert!((ln_gamma(1.0)).abs() < ERROR);
+        assert!((ln_gamma(0.5) - 0.57236494292470008707).abs() < ERROR);
+        assert!((ln_gamma(10.0) - 362880_f64.ln()).abs() < ERROR);
+        assert!((ln_gamma(200.0) - 857.93366982585743).abs() < 0.0000001);
+        assert!((ln_gamma(-0.5) - 1.2655121234846453965).abs() < ERROR);
+    }
 }
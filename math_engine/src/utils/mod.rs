@@ -7,3 +7,5 @@ pub mod untyped;
 pub mod extensions;
 pub mod static_store;
 pub mod approx;
+pub mod rng;
+pub mod special;
@@ -0,0 +1,119 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::{Mutex, MutexGuard};
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::Shared;
+
+/// A random number generator shared by the random functions of a context.
+///
+/// Cloning a `ContextRng` returns a handle to the same generator, so the
+/// functions and the context draw from a single sequence.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::rng::ContextRng;
+///
+/// let a = ContextRng::with_seed(42);
+/// let b = ContextRng::with_seed(42);
+/// assert_eq!(a.next_f64(), b.next_f64());
+/// ```
+#[derive(Clone)]
+pub struct ContextRng {
+    inner: Shared<Mutex<StdRng>>,
+}
+
+impl ContextRng {
+    /// Constructs a new `ContextRng` seeded from the operating system.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_rng(StdRng::from_entropy())
+    }
+
+    /// Constructs a new `ContextRng` with the given seed, the same seed always
+    /// produces the same sequence of values.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        Self::from_rng(StdRng::seed_from_u64(seed))
+    }
+
+    /// Constructs a new `ContextRng` with the given seed, or seeded from
+    /// the operating system if `None`.
+    #[inline]
+    pub fn from_seed(seed: Option<u64>) -> Self {
+        match seed {
+            Some(seed) => Self::with_seed(seed),
+            None => Self::new(),
+        }
+    }
+
+    /// Restarts the sequence of this generator and all its clones using the given seed.
+    #[inline]
+    pub fn set_seed(&self, seed: u64) {
+        *self.lock() = StdRng::seed_from_u64(seed);
+    }
+
+    /// Gets a random value in the range `[0, 1)`.
+    #[inline]
+    pub fn next_f64(&self) -> f64 {
+        self.lock().gen::<f64>()
+    }
+
+    /// Calls the given function with the underlying generator.
+    #[inline]
+    pub fn with<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce(&mut StdRng) -> R,
+    {
+        f(&mut self.lock())
+    }
+
+    #[inline]
+    fn from_rng(rng: StdRng) -> Self {
+        ContextRng {
+            inner: Shared::new(Mutex::new(rng)),
+        }
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, StdRng> {
+        // The generator is always in a valid state, even if a previous caller panicked
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl Default for ContextRng {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Debug for ContextRng {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ContextRng")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seed_test() {
+        let a = ContextRng::with_seed(7);
+        let b = ContextRng::with_seed(7);
+        let c = a.clone();
+
+        let x = (0..5).map(|_| a.next_f64()).collect::<Vec<f64>>();
+        let y = (0..5).map(|_| b.next_f64()).collect::<Vec<f64>>();
+        assert_eq!(x, y);
+        assert!(x.iter().all(|n| *n >= 0.0 && *n < 1.0));
+
+        // Clones share the same sequence
+        c.set_seed(7);
+        assert_eq!(x[0], a.next_f64());
+        assert_eq!(x[1], c.next_f64());
+    }
+}
@@ -0,0 +1,816 @@
+use std::f64::consts;
+
+use crate::utils::gamma::{gamma, ln_gamma};
+
+/// Max number of iterations of the series and continued fractions.
+const MAX_ITERATIONS: usize = 500;
+/// The relative precision of the series and continued fractions.
+const EPSILON: f64 = 1e-16;
+/// A value near the smallest `f64` used to avoid divisions by zero in the continued fractions.
+const MIN_VALUE: f64 = 1e-300;
+/// The max absolute value of the order of the Bessel functions, the cost of the
+/// recurrences grows with the order.
+pub const BESSEL_MAX_ORDER: i32 = 1000;
+
+/// Computes the error function.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::erf;
+///
+/// assert!((erf(1.0) - 0.842_700_792_949_714_9).abs() < 1e-15);
+/// assert_eq!(-erf(0.5), erf(-0.5));
+/// ```
+pub fn erf(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+
+    // erf(x) = P(1/2, x²)
+    let value = gamma_p(0.5, x * x);
+    if x.is_sign_negative() {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Computes the complementary error function `1 - erf(x)`, without losing
+/// precision for large values of `x`.
+pub fn erfc(x: f64) -> f64 {
+    if x.is_nan() {
+        return f64::NAN;
+    }
+
+    // erfc(x) = Q(1/2, x²) and erfc(-x) = 2 - erfc(x)
+    if x.is_sign_negative() {
+        1.0 + gamma_p(0.5, x * x)
+    } else {
+        gamma_q(0.5, x * x)
+    }
+}
+
+/// Computes the regularized lower incomplete gamma function `P(a, x)`,
+/// for `a > 0` and `x >= 0`, or `NaN` for other values.
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    if a.is_nan() || x.is_nan() || a <= 0.0 || x < 0.0 {
+        return f64::NAN;
+    }
+
+    if x == 0.0 {
+        0.0
+    } else if x < a + 1.0 {
+        gamma_series(a, x)
+    } else {
+        1.0 - gamma_continued_fraction(a, x)
+    }
+}
+
+/// Computes the regularized upper incomplete gamma function `Q(a, x) = 1 - P(a, x)`,
+/// for `a > 0` and `x >= 0`, or `NaN` for other values.
+pub fn gamma_q(a: f64, x: f64) -> f64 {
+    if a.is_nan() || x.is_nan() || a <= 0.0 || x < 0.0 {
+        return f64::NAN;
+    }
+
+    if x == 0.0 {
+        1.0
+    } else if x < a + 1.0 {
+        1.0 - gamma_series(a, x)
+    } else {
+        gamma_continued_fraction(a, x)
+    }
+}
+
+/// Computes the regularized incomplete beta function `I_x(a, b)`,
+/// for `a > 0`, `b > 0` and `x` in `[0, 1]`, or `NaN` for other values.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::beta_inc;
+///
+/// assert!((beta_inc(2.0, 3.0, 0.4) - 0.5248).abs() < 1e-14);
+/// ```
+pub fn beta_inc(a: f64, b: f64, x: f64) -> f64 {
+    if a.is_nan() || b.is_nan() || a <= 0.0 || b <= 0.0 || !(0.0..=1.0).contains(&x) {
+        return f64::NAN;
+    }
+
+    if x == 0.0 || x == 1.0 {
+        return x;
+    }
+
+    let front = f64::exp(
+        ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln(),
+    );
+
+    // The continued fraction converges fast for `x < (a + 1) / (a + b + 2)`,
+    // otherwise uses the symmetry `I_x(a, b) = 1 - I_(1-x)(b, a)`
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// Computes the inverse of the standard normal cumulative distribution function,
+/// for `p` in `(0, 1)`, returns `-inf` and `inf` for 0 and 1 and `NaN` for other values.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::norm_inv;
+///
+/// assert!((norm_inv(0.975) - 1.959_963_984_540_054).abs() < 1e-14);
+/// ```
+pub fn norm_inv(p: f64) -> f64 {
+    if !(0.0..=1.0).contains(&p) {
+        return f64::NAN;
+    }
+
+    if p == 0.0 {
+        return f64::NEG_INFINITY;
+    }
+
+    if p == 1.0 {
+        return f64::INFINITY;
+    }
+
+    // Rational approximation by Peter J. Acklam, with a relative error of 1.15e-9
+    // See: https://web.archive.org/web/20151030215612/http://home.online.no/~pjacklam/notes/invnorm/
+    const A: [f64; 6] = [
+        -3.969_683_028_665_376e1,
+        2.209_460_984_245_205e2,
+        -2.759_285_104_469_687e2,
+        1.383_577_518_672_69e2,
+        -3.066_479_806_614_716e1,
+        2.506_628_277_459_239,
+    ];
+    const B: [f64; 5] = [
+        -5.447_609_879_822_406e1,
+        1.615_858_368_580_409e2,
+        -1.556_989_798_598_866e2,
+        6.680_131_188_771_972e1,
+        -1.328_068_155_288_572e1,
+    ];
+    const C: [f64; 6] = [
+        -7.784_894_002_430_293e-3,
+        -3.223_964_580_411_365e-1,
+        -2.400_758_277_161_838,
+        -2.549_732_539_343_734,
+        4.374_664_141_464_968,
+        2.938_163_982_698_783,
+    ];
+    const D: [f64; 4] = [
+        7.784_695_709_041_462e-3,
+        3.224_671_290_700_398e-1,
+        2.445_134_137_142_996,
+        3.754_408_661_907_416,
+    ];
+    const P_LOW: f64 = 0.024_25;
+
+    let x = if p < P_LOW {
+        let q = f64::sqrt(-2.0 * p.ln());
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    } else if p <= 1.0 - P_LOW {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q
+            / (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    } else {
+        let q = f64::sqrt(-2.0 * (1.0 - p).ln());
+        -(((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5])
+            / ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    // A step of the Halley's method gives full precision
+    let e = 0.5 * erfc(-x / consts::SQRT_2) - p;
+    let u = e * f64::sqrt(2.0 * consts::PI) * f64::exp(x * x / 2.0);
+    x - u / (1.0 + x * u / 2.0)
+}
+
+/// Computes the inverse of the error function, for `x` in `(-1, 1)`, returns `-inf` and `inf`
+/// for -1 and 1 and `NaN` for other values.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::{erf, erf_inv};
+///
+/// assert!((erf(erf_inv(0.3)) - 0.3).abs() < 1e-15);
+/// ```
+pub fn erf_inv(x: f64) -> f64 {
+    if x.is_nan() || x.abs() > 1.0 {
+        return f64::NAN;
+    }
+
+    if x == 0.0 {
+        return 0.0;
+    }
+
+    if x.abs() == 1.0 {
+        return f64::INFINITY.copysign(x);
+    }
+
+    // erf(x) = 2Φ(x√2) - 1, which gives a first approximation
+    let y = x.abs();
+    let mut value = norm_inv((y + 1.0) / 2.0) / consts::SQRT_2;
+
+    // Newton's steps, using `erfc` near 1 to avoid the cancellation of `erf(value) - y`
+    for _ in 0..2 {
+        let error = if y < 0.5 {
+            erf(value) - y
+        } else {
+            (1.0 - y) - erfc(value)
+        };
+
+        value -= error / (consts::FRAC_2_SQRT_PI * f64::exp(-value * value));
+    }
+
+    value.copysign(x)
+}
+
+/// Computes the digamma function, the logarithmic derivative of the gamma function,
+/// returns `NaN` for zero and the negative integers.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::digamma;
+///
+/// // ψ(1) = -γ
+/// assert!((digamma(1.0) + 0.577_215_664_901_532_9).abs() < 1e-15);
+/// ```
+pub fn digamma(mut x: f64) -> f64 {
+    if x.is_nan() || (x <= 0.0 && x.fract() == 0.0) {
+        return f64::NAN;
+    }
+
+    if x < 0.0 {
+        // Reflection formula: ψ(x) = ψ(1 - x) - π / tan(πx)
+        return digamma(1.0 - x) - consts::PI / f64::tan(consts::PI * x);
+    }
+
+    // Recurrence ψ(x) = ψ(x + 1) - 1/x until the asymptotic series is precise
+    let mut result = 0.0;
+    while x < 10.0 {
+        result -= x.recip();
+        x += 1.0;
+    }
+
+    // ψ(x) ~ ln(x) - 1/2x - Σ B(2k) / (2k x^2k)
+    let f = (x * x).recip();
+    let series = f
+        * (1.0 / 12.0
+            - f * (1.0 / 120.0
+                - f * (1.0 / 252.0
+                    - f * (1.0 / 240.0 - f * (1.0 / 132.0 - f * (691.0 / 32760.0 - f / 12.0))))));
+
+    result + x.ln() - 0.5 / x - series
+}
+
+/// Computes the beta function `B(a, b) = Γ(a)Γ(b) / Γ(a + b)`.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::beta;
+///
+/// assert!((beta(2.0, 3.0) - 1.0 / 12.0).abs() < 1e-15);
+/// ```
+pub fn beta(a: f64, b: f64) -> f64 {
+    if a > 0.0 && b > 0.0 {
+        // Using logarithms avoids the overflow of the gamma function
+        f64::exp(ln_gamma(a) + ln_gamma(b) - ln_gamma(a + b))
+    } else {
+        gamma(a) * gamma(b) / gamma(a + b)
+    }
+}
+
+/// Computes the Riemann zeta function for real values, returns `NaN` for the pole at 1.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::zeta;
+///
+/// // ζ(2) = π²/6
+/// assert!((zeta(2.0) - std::f64::consts::PI.powi(2) / 6.0).abs() < 1e-15);
+/// ```
+pub fn zeta(s: f64) -> f64 {
+    if s.is_nan() || s == 1.0 {
+        return f64::NAN;
+    }
+
+    if s < 0.0 {
+        // The trivial zeros
+        if s.fract() == 0.0 && s % 2.0 == 0.0 {
+            return 0.0;
+        }
+
+        // Reflection formula: ζ(s) = 2^s π^(s - 1) sin(πs/2) Γ(1 - s) ζ(1 - s)
+        return f64::powf(2.0, s)
+            * f64::powf(consts::PI, s - 1.0)
+            * f64::sin(consts::PI * s / 2.0)
+            * gamma(1.0 - s)
+            * zeta(1.0 - s);
+    }
+
+    // Borwein's algorithm for the alternating zeta function η(s) = (1 - 2^(1 - s)) ζ(s),
+    // with an error of about 3 / (3 + √8)^n
+    // See: https://en.wikipedia.org/wiki/Riemann_zeta_function#Numerical_algorithms
+    const N: usize = 30;
+    let mut d = [0.0; N + 1];
+    let mut term = 1.0;
+    d[0] = 1.0;
+
+    for i in 1..=N {
+        // term = n (n + i - 1)! 4^i / ((n - i)! (2i)!)
+        let (n, i_f64) = (N as f64, i as f64);
+        term *= 4.0 * (n + i_f64 - 1.0) * (n - i_f64 + 1.0) / ((2.0 * i_f64) * (2.0 * i_f64 - 1.0));
+        d[i] = d[i - 1] + term;
+    }
+
+    let sum: f64 = (0..N)
+        .map(|k| {
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            sign * (d[k] - d[N]) / ((k + 1) as f64).powf(s)
+        })
+        .sum();
+
+    -sum / (d[N] * (1.0 - f64::powf(2.0, 1.0 - s)))
+}
+
+/// Computes the Bessel function of the first kind `J_n(x)` of integer order,
+/// returns `NaN` if `|n|` is greater than `BESSEL_MAX_ORDER`.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::bessel_j;
+///
+/// assert!((bessel_j(0, 1.0) - 0.765_197_686_557_966_6).abs() < 1e-15);
+/// ```
+pub fn bessel_j(n: i32, x: f64) -> f64 {
+    if x.is_nan() || n.unsigned_abs() > BESSEL_MAX_ORDER as u32 {
+        return f64::NAN;
+    }
+
+    // J(-n, x) = (-1)^n J(n, x) and J(n, -x) = (-1)^n J(n, x)
+    let sign = if n % 2 != 0 && ((n < 0) != (x < 0.0)) {
+        -1.0
+    } else {
+        1.0
+    };
+    let (n, x) = (n.unsigned_abs() as usize, x.abs());
+
+    if x == 0.0 {
+        return if n == 0 { 1.0 } else { 0.0 };
+    }
+
+    let value = if use_hankel_expansion(n, x) {
+        hankel_expansion(n, x).0
+    } else {
+        bessel_j_sequence(n, x)[n]
+    };
+
+    sign * value
+}
+
+/// Computes the Bessel function of the second kind `Y_n(x)` of integer order, for `x > 0`
+/// or `NaN` for other values or if `|n|` is greater than `BESSEL_MAX_ORDER`.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::bessel_y;
+///
+/// assert!((bessel_y(0, 1.0) - 0.088_256_964_215_676_96).abs() < 1e-15);
+/// ```
+pub fn bessel_y(n: i32, x: f64) -> f64 {
+    if x.is_nan() || x <= 0.0 || n.unsigned_abs() > BESSEL_MAX_ORDER as u32 {
+        return f64::NAN;
+    }
+
+    // Y(-n, x) = (-1)^n Y(n, x)
+    let sign = if n < 0 && n % 2 != 0 { -1.0 } else { 1.0 };
+    let n = n.unsigned_abs() as usize;
+
+    let (mut y0, mut y1) = if use_hankel_expansion(1, x) {
+        (hankel_expansion(0, x).1, hankel_expansion(1, x).1)
+    } else {
+        // Neumann series: Y(0, x) = 2/π [(ln(x/2) + γ) J(0, x) - 2 Σ (-1)^k J(2k, x) / k]
+        // and its derivative Y(1, x) = -Y'(0, x)
+        let j = bessel_j_sequence(1, x);
+        let log = (x / 2.0).ln() + EULER_GAMMA;
+        let (mut sum0, mut sum1) = (0.0, 0.0);
+
+        for k in 1..(j.len() - 1) / 2 {
+            let sign = if k % 2 == 0 { 1.0 } else { -1.0 };
+            sum0 += sign * j[2 * k] / k as f64;
+            sum1 += sign * (j[2 * k - 1] - j[2 * k + 1]) / k as f64;
+        }
+
+        let y0 = consts::FRAC_2_PI * (log * j[0] - 2.0 * sum0);
+        let y1 = consts::FRAC_2_PI * (log * j[1] - j[0] / x + sum1);
+        (y0, y1)
+    };
+
+    if n == 0 {
+        return y0;
+    }
+
+    // The forward recurrence is stable for Y(n, x)
+    for k in 1..n {
+        let next = 2.0 * k as f64 / x * y1 - y0;
+        y0 = y1;
+        y1 = next;
+    }
+
+    sign * y1
+}
+
+/// Computes the Lambert W function, the solution of `w e^w = x`. The branch `0` is defined
+/// for `x >= -1/e` and the branch `-1` for `x` in `[-1/e, 0)`, returns `NaN` for other values.
+///
+/// # Example
+/// ```
+/// use math_engine::utils::special::lambert_w;
+///
+/// // W(e) = 1
+/// assert!((lambert_w(0, std::f64::consts::E) - 1.0).abs() < 1e-15);
+/// ```
+pub fn lambert_w(branch: i32, x: f64) -> f64 {
+    const BRANCH_POINT: f64 = -1.0 / consts::E;
+
+    if x.is_nan() || x < BRANCH_POINT || (branch != 0 && branch != -1) {
+        return f64::NAN;
+    }
+
+    if branch == -1 && x > 0.0 {
+        return f64::NAN;
+    }
+
+    if x == 0.0 {
+        return if branch == 0 { 0.0 } else { f64::NEG_INFINITY };
+    }
+
+    if x == BRANCH_POINT {
+        return -1.0;
+    }
+
+    // Initial approximation, the series near the branch point or the asymptotic expansion
+    let p = f64::sqrt((2.0 * (consts::E * x + 1.0)).max(0.0));
+    let mut w = if branch == 0 {
+        if x < -0.25 {
+            -1.0 + p - p * p / 3.0 + 11.0 / 72.0 * p * p * p
+        } else if x < 3.0 {
+            x.ln_1p()
+        } else {
+            x.ln() - x.ln().ln()
+        }
+    } else if x < -0.25 {
+        -1.0 - p - p * p / 3.0 - 11.0 / 72.0 * p * p * p
+    } else {
+        (-x).ln() - (-(-x).ln()).ln()
+    };
+
+    // Halley's method
+    for _ in 0..MAX_ITERATIONS {
+        let exp = w.exp();
+        let f = w * exp - x;
+        let next = w - f / (exp * (w + 1.0) - (w + 2.0) * f / (2.0 * w + 2.0));
+
+        if !next.is_finite() {
+            break;
+        }
+
+        let done = (next - w).abs() <= EPSILON * next.abs();
+        w = next;
+
+        if done {
+            break;
+        }
+    }
+
+    w
+}
+
+/// The Euler–Mascheroni constant.
+const EULER_GAMMA: f64 = 0.577_215_664_901_532_9;
+
+/// Checks if the Hankel's asymptotic expansion is precise for `J_n(x)` and `Y_n(x)`.
+#[inline]
+fn use_hankel_expansion(n: usize, x: f64) -> bool {
+    x >= 25.0 && (n * n) as f64 <= x
+}
+
+/// Computes `(J_n(x), Y_n(x))` using the Hankel's asymptotic expansion for large `x`.
+fn hankel_expansion(n: usize, x: f64) -> (f64, f64) {
+    let mu = 4.0 * (n * n) as f64;
+    let (mut p, mut q) = (1.0, 0.0);
+    let mut term: f64 = 1.0;
+
+    // The series diverges, stops at the smallest term
+    for k in 1..MAX_ITERATIONS {
+        let next = term * (mu - ((2 * k - 1) * (2 * k - 1)) as f64) / (k as f64 * 8.0 * x);
+        if next.abs() >= term.abs() || next.abs() < EPSILON * EPSILON {
+            break;
+        }
+
+        term = next;
+        match k % 4 {
+            1 => q += term,
+            2 => p -= term,
+            3 => q -= term,
+            _ => p += term,
+        }
+    }
+
+    let chi = x - (n as f64 / 2.0 + 0.25) * consts::PI;
+    let amplitude = f64::sqrt(consts::FRAC_2_PI / x);
+    let (sin, cos) = chi.sin_cos();
+    let j = amplitude * (p * cos - q * sin);
+    let y = amplitude * (p * sin + q * cos);
+    (j, y)
+}
+
+/// Computes `J_k(x)` for `k` from 0 to at least `n` using the Miller's backward recurrence,
+/// normalized with `J(0, x) + 2 Σ J(2k, x) = 1`.
+fn bessel_j_sequence(n: usize, x: f64) -> Vec<f64> {
+    // The start must be far enough for the values to be negligible
+    let order = n.max(x as usize);
+    let start = 2 * ((order + 30 + f64::sqrt(40.0 * order.max(1) as f64) as usize) / 2);
+
+    let mut values = vec![0.0; start + 2];
+    values[start] = MIN_VALUE;
+
+    for k in (1..=start).rev() {
+        values[k - 1] = 2.0 * k as f64 / x * values[k] - values[k + 1];
+
+        // Rescale to avoid overflows
+        if values[k - 1].abs() > 1e250 {
+            for value in &mut values[k - 1..] {
+                *value *= 1e-250;
+            }
+        }
+    }
+
+    let norm = values[0] + 2.0 * values.iter().skip(2).step_by(2).sum::<f64>();
+    values.truncate(start + 1);
+    values.iter_mut().for_each(|value| *value /= norm);
+    values
+}
+
+/// Computes `P(a, x)` using its series representation, converges fast for `x < a + 1`.
+fn gamma_series(a: f64, x: f64) -> f64 {
+    let mut n = a;
+    let mut term = 1.0 / a;
+    let mut sum = term;
+
+    for _ in 0..MAX_ITERATIONS {
+        n += 1.0;
+        term *= x / n;
+        sum += term;
+
+        if term.abs() < sum.abs() * EPSILON {
+            break;
+        }
+    }
+
+    sum * f64::exp(-x + a * x.ln() - ln_gamma(a))
+}
+
+/// Computes `Q(a, x)` using its continued fraction with the modified Lentz's method,
+/// converges fast for `x >= a + 1`.
+fn gamma_continued_fraction(a: f64, x: f64) -> f64 {
+    let mut b = x + 1.0 - a;
+    let mut c = 1.0 / MIN_VALUE;
+    let mut d = 1.0 / b;
+    let mut h = d;
+
+    for i in 1..MAX_ITERATIONS {
+        let i = i as f64;
+        let an = -i * (i - a);
+        b += 2.0;
+        d = non_zero(an * d + b).recip();
+        c = non_zero(b + an / c);
+
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h * f64::exp(-x + a * x.ln() - ln_gamma(a))
+}
+
+/// Computes the continued fraction of the incomplete beta function with the modified Lentz's method.
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let mut c = 1.0;
+    let mut d = non_zero(1.0 - (a + b) * x / (a + 1.0)).recip();
+    let mut h = d;
+
+    for m in 1..MAX_ITERATIONS {
+        let m = m as f64;
+
+        // Even step
+        let numerator = m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m));
+        d = non_zero(1.0 + numerator * d).recip();
+        c = non_zero(1.0 + numerator / c);
+        h *= d * c;
+
+        // Odd step
+        let numerator = -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0));
+        d = non_zero(1.0 + numerator * d).recip();
+        c = non_zero(1.0 + numerator / c);
+
+        let delta = d * c;
+        h *= delta;
+
+        if (delta - 1.0).abs() < EPSILON {
+            break;
+        }
+    }
+
+    h
+}
+
+#[inline]
+fn non_zero(x: f64) -> f64 {
+    if x.abs() < MIN_VALUE {
+        MIN_VALUE
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_almost_eq(expected: f64, actual: f64, delta: f64) {
+        assert!(
+            (expected - actual).abs() <= delta,
+            "expected {} but was {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn erf_test() {
+        assert_eq!(0.0, erf(0.0));
+        assert_almost_eq(0.520_499_877_813_046_5, erf(0.5), 1e-15);
+        assert_almost_eq(0.842_700_792_949_714_9, erf(1.0), 1e-15);
+        assert_almost_eq(-0.995_322_265_018_952_7, erf(-2.0), 1e-15);
+        assert_almost_eq(1.0, erf(10.0), 1e-15);
+        assert!(erf(f64::NAN).is_nan());
+    }
+
+    #[test]
+    fn erfc_test() {
+        assert_eq!(1.0, erfc(0.0));
+        assert_almost_eq(0.157_299_207_050_285_1, erfc(1.0), 1e-15);
+        assert_almost_eq(1.842_700_792_949_715, erfc(-1.0), 1e-15);
+        assert_almost_eq(2.209_049_699_858_544e-5, erfc(3.0), 1e-18);
+        assert_almost_eq(1.537_459_794_428_035e-12, erfc(5.0), 1e-24);
+    }
+
+    #[test]
+    fn gamma_p_test() {
+        // P(1, x) = 1 - e^-x
+        assert_almost_eq(1.0 - f64::exp(-0.5), gamma_p(1.0, 0.5), 1e-14);
+        assert_almost_eq(1.0 - f64::exp(-20.0), gamma_p(1.0, 20.0), 1e-14);
+        // P(2, x) = 1 - (1 + x)e^-x
+        assert_almost_eq(1.0 - 2.0 / std::f64::consts::E, gamma_p(2.0, 1.0), 1e-14);
+        assert_almost_eq(1.0, gamma_p(3.0, 0.0) + gamma_q(3.0, 0.0), 1e-15);
+        assert_almost_eq(6.0 * f64::exp(-4.0) * (1.0 + 4.0 + 8.0), gamma_q(3.0, 4.0) * 6.0, 1e-13);
+
+        assert!(gamma_p(0.0, 1.0).is_nan());
+        assert!(gamma_p(1.0, -1.0).is_nan());
+    }
+
+    #[test]
+    fn beta_inc_test() {
+        // I_x(a, 1) = x^a
+        assert_almost_eq(0.3_f64.powf(2.5), beta_inc(2.5, 1.0, 0.3), 1e-14);
+        assert_almost_eq(0.5248, beta_inc(2.0, 3.0, 0.4), 1e-14);
+        assert_almost_eq(1.0 - 0.5248, beta_inc(3.0, 2.0, 0.6), 1e-14);
+        assert_almost_eq(0.5, beta_inc(10.0, 10.0, 0.5), 1e-14);
+        assert_eq!(0.0, beta_inc(2.0, 3.0, 0.0));
+        assert_eq!(1.0, beta_inc(2.0, 3.0, 1.0));
+
+        assert!(beta_inc(2.0, 3.0, 1.5).is_nan());
+        assert!(beta_inc(-2.0, 3.0, 0.5).is_nan());
+    }
+
+    #[test]
+    fn norm_inv_test() {
+        assert_eq!(0.0, norm_inv(0.5));
+        assert_almost_eq(1.959_963_984_540_054, norm_inv(0.975), 1e-14);
+        assert_almost_eq(-2.326_347_874_040_841, norm_inv(0.01), 1e-14);
+        assert_almost_eq(-4.753_424_308_822_899, norm_inv(1e-6), 1e-12);
+        assert_almost_eq(3.090_232_306_167_813, norm_inv(0.999), 1e-12);
+        assert_eq!(f64::NEG_INFINITY, norm_inv(0.0));
+        assert!(norm_inv(1.5).is_nan());
+    }
+
+    #[test]
+    fn erf_inv_test() {
+        assert_eq!(0.0, erf_inv(0.0));
+        assert_almost_eq(0.088_855_990_494_257_69, erf_inv(0.1), 1e-15);
+        assert_almost_eq(0.476_936_276_204_469_9, erf_inv(0.5), 1e-15);
+        assert_almost_eq(1.163_087_153_676_674_2, erf_inv(0.9), 1e-14);
+        assert_almost_eq(2.326_753_765_513_524_5, erf_inv(0.999), 1e-13);
+        assert_almost_eq(-0.732_869_077_959_216_8, erf_inv(-0.7), 1e-15);
+        assert_almost_eq(8.862_269_254_527_58e-11, erf_inv(1e-10), 1e-24);
+        assert_eq!(f64::INFINITY, erf_inv(1.0));
+        assert_eq!(f64::NEG_INFINITY, erf_inv(-1.0));
+        assert!(erf_inv(1.5).is_nan());
+    }
+
+    #[test]
+    fn digamma_test() {
+        assert_almost_eq(-1.963_510_026_021_423_5, digamma(0.5), 1e-14);
+        assert_almost_eq(0.422_784_335_098_467_14, digamma(2.0), 1e-14);
+        assert_almost_eq(2.251_752_589_066_721, digamma(10.0), 1e-14);
+        assert_almost_eq(4.600_161_852_738_087, digamma(100.0), 1e-14);
+        assert_almost_eq(0.036_489_973_978_576_52, digamma(-0.5), 1e-14);
+        assert_almost_eq(1.103_156_640_645_243_2, digamma(-2.5), 1e-13);
+        assert!(digamma(0.0).is_nan());
+        assert!(digamma(-3.0).is_nan());
+    }
+
+    #[test]
+    fn beta_test() {
+        assert_almost_eq(consts::PI, beta(0.5, 0.5), 1e-14);
+        assert_almost_eq(0.196_349_540_849_362_08, beta(2.5, 1.5), 1e-15);
+        assert_almost_eq(4.992_508_740_634_678e-9, beta(10.0, 20.0), 1e-21);
+        assert_almost_eq(-4.0, beta(-0.5, 2.0), 1e-13);
+        assert_eq!(beta(2.0, 7.5), beta(7.5, 2.0));
+    }
+
+    #[test]
+    fn zeta_test() {
+        assert_almost_eq(-1.460_354_508_809_586_8, zeta(0.5), 1e-13);
+        assert_almost_eq(2.612_375_348_685_488_3, zeta(1.5), 1e-13);
+        assert_almost_eq(1.202_056_903_159_594_3, zeta(3.0), 1e-14);
+        assert_almost_eq(1.000_994_575_127_818, zeta(10.0), 1e-14);
+        assert_almost_eq(-0.5, zeta(0.0), 1e-14);
+        assert_almost_eq(-1.0 / 12.0, zeta(-1.0), 1e-14);
+        assert_almost_eq(0.008_516_928_777_850_331, zeta(-2.5), 1e-14);
+        assert_eq!(0.0, zeta(-4.0));
+        assert!(zeta(1.0).is_nan());
+    }
+
+    #[test]
+    fn bessel_j_test() {
+        assert_almost_eq(-0.048_383_776_468_197_996, bessel_j(0, 2.5), 1e-15);
+        assert_almost_eq(0.440_050_585_744_933_5, bessel_j(1, 1.0), 1e-15);
+        assert_almost_eq(0.046_565_116_277_752_216, bessel_j(2, 5.0), 1e-15);
+        assert_almost_eq(-0.234_061_528_186_793_64, bessel_j(5, 10.0), 1e-15);
+        assert_almost_eq(1.292_835_164_571_588_4e-5, bessel_j(10, 3.0), 1e-19);
+        assert_almost_eq(1.151_336_924_781_34e-5, bessel_j(20, 10.0), 1e-19);
+        assert_almost_eq(-0.086_367_983_581_040_21, bessel_j(0, 30.0), 1e-15);
+        assert_almost_eq(-0.077_145_352_014_112_16, bessel_j(1, 100.0), 1e-15);
+        assert_almost_eq(-0.128_943_249_474_402_05, bessel_j(3, -2.0), 1e-15);
+        assert_almost_eq(-0.128_943_249_474_402_05, bessel_j(-3, 2.0), 1e-15);
+        assert_eq!(1.0, bessel_j(0, 0.0));
+        assert_eq!(0.0, bessel_j(2, 0.0));
+        assert_almost_eq(0.0, bessel_j(BESSEL_MAX_ORDER, 1.0), 1e-300);
+        assert!(bessel_j(BESSEL_MAX_ORDER + 1, 1.0).is_nan());
+        assert!(bessel_j(i32::MIN, 1.0).is_nan());
+    }
+
+    #[test]
+    fn bessel_y_test() {
+        assert_almost_eq(0.498_070_359_615_231_9, bessel_y(0, 2.5), 1e-15);
+        assert_almost_eq(-0.781_212_821_300_288_7, bessel_y(1, 1.0), 1e-15);
+        assert_almost_eq(0.367_662_882_605_524_5, bessel_y(2, 5.0), 1e-15);
+        assert_almost_eq(0.135_403_047_689_362_3, bessel_y(5, 10.0), 1e-15);
+        assert_almost_eq(-2_582.607_129_484_299_7, bessel_y(10, 3.0), 1e-10);
+        assert_almost_eq(-0.117_295_731_686_664_03, bessel_y(0, 30.0), 1e-15);
+        assert_almost_eq(-0.020_372_312_002_759_793, bessel_y(1, 100.0), 1e-15);
+        assert_almost_eq(1.127_783_776_840_427_8, bessel_y(-3, 2.0), 1e-14);
+        assert_almost_eq(-3.005_455_637_083_646, bessel_y(0, 0.01), 1e-14);
+        assert!(bessel_y(0, 0.0).is_nan());
+        assert!(bessel_y(1, -1.0).is_nan());
+        assert!(bessel_y(i32::MAX, 1.0).is_nan());
+    }
+
+    #[test]
+    fn lambert_w_test() {
+        assert_almost_eq(0.567_143_290_409_783_9, lambert_w(0, 1.0), 1e-15);
+        assert_almost_eq(1.745_528_002_740_699_4, lambert_w(0, 10.0), 1e-15);
+        assert_almost_eq(20.028_685_413_304_95, lambert_w(0, 1e10), 1e-13);
+        assert_almost_eq(-0.111_832_559_158_962_97, lambert_w(0, -0.1), 1e-15);
+        assert_almost_eq(-0.489_402_227_180_214_9, lambert_w(0, -0.3), 1e-14);
+        assert_almost_eq(-0.716_638_816_456_073_7, lambert_w(0, -0.35), 1e-13);
+        assert_almost_eq(-1.349_717_252_192_249, lambert_w(-1, -0.35), 1e-13);
+        assert_almost_eq(-1.781_337_023_421_627_7, lambert_w(-1, -0.3), 1e-14);
+        assert_almost_eq(-3.577_152_063_957_297, lambert_w(-1, -0.1), 1e-14);
+        assert_almost_eq(-14.163_600_815_810_183, lambert_w(-1, -1e-5), 1e-13);
+        assert_eq!(0.0, lambert_w(0, 0.0));
+        assert_eq!(f64::NEG_INFINITY, lambert_w(-1, 0.0));
+        assert!(lambert_w(0, -1.0).is_nan());
+        assert!(lambert_w(-1, 1.0).is_nan());
+        assert!(lambert_w(1, 1.0).is_nan());
+    }
+}
@@ -1,7 +1,6 @@
 use std::any::TypeId;
-use std::cell::UnsafeCell;
 use std::collections::HashMap;
-use std::sync::Once;
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 /// A cache for store static data.
 ///
@@ -35,10 +34,8 @@ use std::sync::Once;
 /// assert_eq!(add("Hello"), 1);
 /// ```
 pub struct StaticStore<C = HashMap<TypeId, *const ()>> {
-    /// Holds the `HashMap` to store the data.
-    store: UnsafeCell<Option<C>>,
-    /// Used for a thread safe initialization of the `store`.
-    once: Once
+    /// Holds the `HashMap` to store the data, locked for a thread safe initialization and access.
+    store: Mutex<Option<C>>,
 }
 
 impl StaticStore<HashMap<TypeId, *const ()>> {
@@ -46,8 +43,7 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
     #[inline]
     pub const fn new() -> Self{
         StaticStore{
-            store: UnsafeCell::new(None),
-            once: Once::new()
+            store: Mutex::new(None),
         }
     }
 
@@ -55,13 +51,15 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
     ///
     /// # Parameters
     /// - f: the function that provides the value to store.
+    ///
+    /// # Remarks
+    /// The store is locked while calling `f`, so `f` must not load values from the same store.
     #[inline]
     pub fn load<T: 'static, F: FnOnce() -> T>(&self, f: F) -> &'static T{
-        let map = Self::get_or_init(self);
-
-        let raw = map.entry(TypeId::of::<T>())
-            .or_insert_with(|| Box::into_raw(Box::new(f())) as *const ())
-            .clone();
+        let mut store = self.lock();
+        let raw = *store.get_or_insert_with(HashMap::new)
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::into_raw(Box::new(f())) as *const ());
 
         unsafe { &*(raw as *const T) }
     }
@@ -72,10 +70,9 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
     /// - f: the function that provides the value to store.
     #[inline]
     pub fn load_mut<T: 'static, F: FnOnce() -> T>(&mut self, f: F) -> &'static mut T{
-        let map = Self::get_or_init(self);
-        let raw = map.entry(TypeId::of::<T>())
-            .or_insert_with(|| Box::into_raw(Box::new(f())) as *const ())
-            .clone();
+        let raw = *self.get_mut_map()
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::into_raw(Box::new(f())) as *const ());
 
         unsafe { &mut *(raw as *mut T) }
     }
@@ -83,8 +80,8 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
     /// Gets a reference of the data of the specified type.
     #[inline]
     pub fn get<T: 'static>(&self) -> Option<&'static T>{
-        let map = Self::get_or_init(self);
-        let raw = map.get(&TypeId::of::<T>());
+        let store = self.lock();
+        let raw = store.as_ref().and_then(|map| map.get(&TypeId::of::<T>()));
         match raw {
             Some(ptr) => {
                 unsafe { Some(&*(*ptr as *const T)) }
@@ -96,8 +93,7 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
     /// Gets a mutable reference of the data of the specified type.
     #[inline]
     pub fn get_mut<T: 'static>(&mut self) -> Option<&'static mut T>{
-        let map = Self::get_or_init(self);
-        let raw = map.get(&TypeId::of::<T>());
+        let raw = self.get_mut_map().get(&TypeId::of::<T>());
         match raw {
             Some(ptr) => {
                 unsafe { Some(&mut *(*ptr as *mut T)) }
@@ -106,22 +102,16 @@ impl StaticStore<HashMap<TypeId, *const ()>> {
         }
     }
 
-    fn get_or_init(&self) -> &mut HashMap<TypeId, *const ()>{
-        let raw = self.store.get();
-        let data = unsafe { &mut *raw };
-
-        match data {
-            None => {
-                unsafe{
-                    self.once.call_once(||{
-                        raw.write(Some(HashMap::new()))
-                    });
+    fn lock(&self) -> MutexGuard<'_, Option<HashMap<TypeId, *const ()>>>{
+        // The map is not left in an invalid state if a panic occurs while is locked
+        self.store.lock().unwrap_or_else(PoisonError::into_inner)
+    }
 
-                    (*raw).as_mut().unwrap()
-                }
-            },
-            Some(ref mut map) => map,
-        }
+    fn get_mut_map(&mut self) -> &mut HashMap<TypeId, *const ()>{
+        self.store
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get_or_insert_with(HashMap::new)
     }
 }
 
@@ -177,6 +167,23 @@ mod tests{
         });
     }
 
+    #[test]
+    fn threads_load_test(){
+        static STORE : StaticStore = StaticStore::new();
+
+        let handles = (0..8)
+            .map(|_| std::thread::spawn(|| STORE.load(|| vec![1, 2, 3]) as *const Vec<i32> as usize))
+            .collect::<Vec<_>>();
+
+        // All the threads get the same value
+        let first = STORE.load(|| vec![1, 2, 3]) as *const Vec<i32> as usize;
+        for handle in handles {
+            assert_eq!(first, handle.join().unwrap());
+        }
+
+        assert_eq!(STORE.get::<Vec<i32>>().unwrap().as_slice(), &[1, 2, 3]);
+    }
+
     #[test]
     fn static_store_test(){
         assert_eq!(add(10), 1);
@@ -20,12 +20,31 @@ pub enum TokenizeKind {
 /// assert_eq!(["2", "+", "3"].to_vec(), tokens);
 /// ```
 #[derive(Copy, Clone, Eq, PartialEq)]
-pub struct StringTokenizer(TokenizeKind);
+pub struct StringTokenizer {
+    kind: TokenizeKind,
+    /// Reads `<<` as a single token.
+    shift_left: bool,
+    /// Reads `>>` as a single token.
+    shift_right: bool,
+}
 
 impl StringTokenizer {
     #[inline]
     pub const fn new(kind: TokenizeKind) -> StringTokenizer {
-        StringTokenizer(kind)
+        StringTokenizer {
+            kind,
+            shift_left: false,
+            shift_right: false,
+        }
+    }
+
+    /// Sets if `<<` and `>>` are read as a single token, otherwise each `<` and `>` is a token,
+    /// as used for close the custom grouping symbols: `Max<1, Sum<2, 3>>`.
+    #[inline]
+    pub const fn with_shift_operators(mut self, shift_left: bool, shift_right: bool) -> StringTokenizer {
+        self.shift_left = shift_left;
+        self.shift_right = shift_right;
+        self
     }
 
     pub fn get_tokens(&self, expression: &str) -> Vec<String> {
@@ -44,10 +63,15 @@ impl StringTokenizer {
                     Self::next_numeric(&mut temp, &mut iterator);
                     tokens.push(temp);
                 }
-                ' ' => match self.0 {
+                ' ' => match self.kind {
                     TokenizeKind::None => tokens.push(String::from(" ")),
                     TokenizeKind::RemoveWhiteSpaces => {}
                 },
+                // Shift operators `<<` and `>>`
+                '<' | '>' if iterator.peek() == Some(&next) && self.is_shift_operator(next) => {
+                    iterator.next();
+                    tokens.push([next, next].iter().collect());
+                }
                 c => tokens.push(c.to_string()),
             }
         }
@@ -55,6 +79,15 @@ impl StringTokenizer {
         tokens
     }
 
+    #[inline]
+    fn is_shift_operator(&self, c: char) -> bool {
+        match c {
+            '<' => self.shift_left,
+            '>' => self.shift_right,
+            _ => false,
+        }
+    }
+
     fn next_alphanumeric(dest: &mut String, iterator: &mut Peekable<Chars>) {
         while let Some(c) = iterator.peek() {
             if c.is_alphanumeric() {
@@ -90,7 +123,7 @@ impl StringTokenizer {
 
 impl Default for StringTokenizer {
     fn default() -> Self {
-        StringTokenizer(TokenizeKind::RemoveWhiteSpaces)
+        StringTokenizer::new(TokenizeKind::RemoveWhiteSpaces)
     }
 }
 
@@ -123,6 +156,18 @@ mod tests {
             tokenizer.get_tokens("2PI + 10")
         );
         assert_eq!(["x", "=", "10"].to_vec(), tokenizer.get_tokens("x = 10"));
+        assert_eq!(
+            ["1", "<", "<", "3", ">", ">", "1"].to_vec(),
+            tokenizer.get_tokens("1 << 3 >> 1")
+        );
+        assert_eq!(
+            ["1", "<<", "3", ">>", "1", "<", "2"].to_vec(),
+            tokenizer.with_shift_operators(true, true).get_tokens("1 << 3 >> 1 < 2")
+        );
+        assert_eq!(
+            ["Max", "<", "1", ",", "2", "<<", "3", ">", ">"].to_vec(),
+            tokenizer.with_shift_operators(true, false).get_tokens("Max<1, 2 << 3>>")
+        );
 
         assert_eq!(
             ["5", " ", "*", " ", "2"].to_vec(),